@@ -0,0 +1,111 @@
+/*
+ * benches/batch.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Compares rendering a forum thread post by post against rendering it as a parallel batch.
+//!
+//! Run with `cargo bench --bench batch --features parallel`.
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use ftml::batch::{render_batch, BatchInput};
+use ftml::data::PageInfo;
+use ftml::render::html::HtmlRender;
+use ftml::render::Render;
+use ftml::settings::{WikitextMode, WikitextSettings};
+use sloggers::null::NullLoggerBuilder;
+use sloggers::Build;
+use std::borrow::Cow;
+
+const THREAD_LENGTH: usize = 200;
+
+fn build_thread() -> Vec<String> {
+    (0..THREAD_LENGTH)
+        .map(|index| {
+            format!(
+                "Reply number {}, responding to the **above** post.\n\n\
+                 > I think [[[scp-{:03}]]] is //much// better than that.\n\n\
+                 Not sure I agree, see [https://example.com/{} this] instead.",
+                index,
+                index % 1000,
+                index,
+            )
+        })
+        .collect()
+}
+
+fn render_thread(c: &mut Criterion) {
+    let log = NullLoggerBuilder.build().expect("Unable to build logger");
+    let page_info = PageInfo {
+        page: Cow::Borrowed("forum:thread"),
+        category: None,
+        site: Cow::Borrowed("sandbox"),
+        title: Cow::Borrowed("A forum thread"),
+        alt_title: None,
+        rating: 0.0,
+        tags: vec![],
+        language: Cow::Borrowed("default"),
+    };
+    let thread = build_thread();
+
+    let settings = WikitextSettings::from_mode(WikitextMode::ForumPost);
+    let mut group = c.benchmark_group("forum-thread");
+
+    // Looping over the full pipeline one post at a time
+    group.bench_function("loop", |b| {
+        b.iter(|| {
+            let outputs: Vec<_> = thread
+                .iter()
+                .map(|post| {
+                    let mut text = post.clone();
+                    ftml::preprocess(&log, &mut text, &settings);
+
+                    let tokens = ftml::tokenize(&log, &text);
+                    let (tree, warnings) =
+                        ftml::parse(&log, &tokens, &page_info, &settings).into();
+                    let output = HtmlRender.render(&log, &tree, &page_info, &settings);
+
+                    (output, warnings)
+                })
+                .collect();
+
+            black_box(outputs)
+        })
+    });
+
+    group.bench_function("batch", |b| {
+        b.iter_batched(
+            || {
+                thread
+                    .iter()
+                    .map(|text| BatchInput {
+                        text,
+                        page_info: &page_info,
+                    })
+                    .collect::<Vec<_>>()
+            },
+            |inputs| black_box(render_batch(&log, &HtmlRender, &inputs, &settings)),
+            BatchSize::SmallInput,
+        )
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, render_thread);
+criterion_main!(benches);
@@ -110,6 +110,10 @@ int main(int argc, char **argv)
 		}
 	}
 
+	if (output.json_ld) {
+		printf("JSON-LD:\n%s\n\n", output.json_ld);
+	}
+
 	printf("Warnings:\n");
 	for (size_t i = 0; i < output.warning_len; i++) {
 		struct ftml_warning *warn = &output.warning_list[i];
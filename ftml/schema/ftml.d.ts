@@ -0,0 +1,420 @@
+// Generated from the ftml JSON Schemas, version 1. Do not edit.
+
+export type Alignment = "left" | "right" | "center" | "justify";
+
+export type AnchorTarget = "new-tab" | "parent" | "top" | "same";
+
+export type AttributeMap = { [key: string]: string; };
+
+export interface Backlinks {
+    "external-links": string[];
+    "included-pages": PageRef[];
+    "internal-links": PageRef[];
+    "missing-links": PageRef[];
+}
+
+export interface Bibliography {
+    entries: BibliographyEntry[];
+}
+
+export interface BibliographyEntry {
+    citations: number;
+    elements: Element[];
+    label: string;
+}
+
+export type ClearFloat = "left" | "right" | "both";
+
+export interface Container {
+    attributes: AttributeMap;
+    elements: Element[];
+    type: ContainerType;
+}
+
+export type ContainerType =
+    | "bold"
+    | "italics"
+    | "underline"
+    | "superscript"
+    | "subscript"
+    | "strikethrough"
+    | "monospace"
+    | "span"
+    | "div"
+    | "mark"
+    | "ruby"
+    | "ruby-base"
+    | "ruby-text"
+    | "blockquote"
+    | "insertion"
+    | "deletion"
+    | "hidden"
+    | "invisible"
+    | "size"
+    | "paragraph"
+    | { align: Alignment; }
+    | { header: Heading; };
+
+export type Date = string;
+
+export interface DefinitionListItem {
+    key: Element[];
+    value: Element[];
+}
+
+export type Element =
+    | { data: Container; element: "container"; }
+    | { data: Module; element: "module"; }
+    | { data: string; element: "text"; }
+    | { data: string; element: "raw"; }
+    | { data: string; element: "variable"; }
+    | { data: string; element: "email"; }
+    | { data: Table; element: "table"; }
+    | { data: Tab[]; element: "tab-view"; }
+    | { data: { attributes: AttributeMap; elements: Element[]; target?: AnchorTarget | null; }; element: "anchor"; }
+    | { data: { label: LinkLabel; link: LinkLocation; target?: AnchorTarget | null; }; element: "link"; }
+    | { data: { alignment?: FloatAlignment | null; attributes: AttributeMap; link?: LinkLocation | null; source: ImageSource; }; element: "image"; }
+    | { data: { attributes: AttributeMap; images: GalleryImage[]; size: GallerySize; }; element: "gallery"; }
+    | { data: Media; element: "media"; }
+    | { data: { attributes: AttributeMap; items: ListItem[]; type: ListType; }; element: "list"; }
+    | { data: DefinitionListItem[]; element: "definition-list"; }
+    | { data: { attributes: AttributeMap; checked: boolean; name: string; }; element: "radio-button"; }
+    | { data: { attributes: AttributeMap; checked: boolean; }; element: "check-box"; }
+    | { data: { attributes: AttributeMap; elements: Element[]; "hide-text"?: string | null; "show-bottom": boolean; "show-text"?: string | null; "show-top": boolean; "start-open": boolean; }; element: "collapsible"; }
+    | { data: { align?: Alignment | null; attributes: AttributeMap; }; element: "table-of-contents"; }
+    | { element: "footnote"; }
+    | { data: { hide: boolean; title?: string | null; }; element: "footnote-block"; }
+    | { data: { label: string; }; element: "bibliography-cite"; }
+    | { data: { hide: boolean; index: number; title?: string | null; }; element: "bibliography-block"; }
+    | { data: { name: string; "show-avatar": boolean; }; element: "user"; }
+    | { data: { format?: string | null; hover: boolean; value: Date; }; element: "date"; }
+    | { data: { color: string; elements: Element[]; }; element: "color"; }
+    | { data: { contents: string; language?: string | null; }; element: "code"; }
+    | { data: { "latex-source": string; name?: string | null; }; element: "math"; }
+    | { data: { "latex-source": string; }; element: "math-inline"; }
+    | { data: string; element: "equation-reference"; }
+    | { data: Embed; element: "embed"; }
+    | { data: { contents: string; }; element: "html"; }
+    | { data: { attributes: AttributeMap; url: string; }; element: "iframe"; }
+    | { data: { elements: Element[]; location: PageRef; "paragraph-safe": boolean; variables: { [key: string]: string; }; }; element: "include"; }
+    | { element: "line-break"; }
+    | { data: number; element: "line-breaks"; }
+    | { data: ClearFloat; element: "clear-float"; }
+    | { element: "horizontal-rule"; }
+    | { data: PartialElement; element: "partial"; };
+
+export type Embed =
+    | { data: { "video-id": string; }; embed: "youtube"; }
+    | { data: { "video-id": string; }; embed: "vimeo"; }
+    | { data: { hash: string; username: string; }; embed: "github-gist"; }
+    | { data: { "snippet-id": string; }; embed: "gitlab-snippet"; };
+
+export interface FloatAlignment {
+    align: Alignment;
+    float: boolean;
+}
+
+export interface GalleryImage {
+    attributes: AttributeMap;
+    caption?: string | null;
+    link?: LinkLocation | null;
+    source: ImageSource;
+    title?: string | null;
+}
+
+export type GallerySize = "square" | "thumbnail" | "small" | "medium";
+
+export interface Heading {
+    "has-toc": boolean;
+    level: HeadingLevel;
+}
+
+export type HeadingLevel = 1 | 2 | 3 | 4 | 5 | 6;
+
+export type HtmlBlockHosting = "inline" | "remote";
+
+export interface HtmlBlockSettings {
+    "auto-height"?: boolean;
+    hosting?: HtmlBlockHosting;
+    sandbox?: string[];
+    stylesheet?: string | null;
+}
+
+export interface HtmlMeta {
+    name: string;
+    tag_type: HtmlMetaType;
+    value: string;
+}
+
+export type HtmlMetaType = "name" | "http-equiv" | "property";
+
+export interface HtmlOutput {
+    backlinks: Backlinks;
+    body: string;
+    json_ld?: string | null;
+    meta: HtmlMeta[];
+    styles: string[];
+}
+
+export type HtmlSyntax = "html" | "xhtml" | "epub";
+
+export interface IframeSettings {
+    "allowed-domains"?: string[];
+    "denied-domains"?: string[];
+    "referrer-policy"?: string;
+    sandbox?: string[];
+}
+
+export type ImageSource =
+    | { data: string; type: "url"; }
+    | { data: { file: string; }; type: "file1"; }
+    | { data: { file: string; page: string; }; type: "file2"; }
+    | { data: { file: string; page: string; site: string; }; type: "file3"; };
+
+export type LinkLabel = { text: string; } | { url: string | null; } | "page";
+
+export type LinkLocation = PageRef | string;
+
+export type ListItem =
+    | { attributes: AttributeMap; elements: Element[]; "item-type": "elements"; }
+    | { "item-type": "sub-list"; } & Element;
+
+export type ListType = "bullet" | "numbered" | "generic";
+
+export interface Media {
+    attributes: AttributeMap;
+    autoplay: boolean;
+    captions?: ImageSource | null;
+    controls: boolean;
+    loop: boolean;
+    poster?: ImageSource | null;
+    source: ImageSource;
+    type: MediaType;
+}
+
+export type MediaType = "audio" | "video";
+
+export type Module =
+    | { data: { page?: string | null; }; module: "backlinks"; }
+    | { data: { "include-hidden": boolean; }; module: "categories"; }
+    | { data: { attributes: AttributeMap; "button-text"?: string | null; }; module: "join"; }
+    | { data: { depth?: number | null; root?: string | null; "show-root": boolean; }; module: "page-tree"; }
+    | { module: "rate"; };
+
+export interface PageInfo {
+    "alt-title"?: string | null;
+    category?: string | null;
+    language: string;
+    page: string;
+    rating: number;
+    site: string;
+    tags: string[];
+    title: string;
+}
+
+export interface PageRef {
+    page: string;
+    site?: string | null;
+}
+
+export interface ParseWarning {
+    kind: ParseWarningKind;
+    rule: string;
+    span: Range_of_uint;
+    token: Token;
+}
+
+export type ParseWarningKind =
+    | "recursion-depth-exceeded"
+    | "end-of-input"
+    | "no-rules-match"
+    | "rule-failed"
+    | "not-supported-mode"
+    | "not-start-of-line"
+    | "invalid-include"
+    | "list-empty"
+    | "list-contains-non-item"
+    | "list-item-outside-list"
+    | "list-depth-exceeded"
+    | "table-contains-non-row"
+    | "table-row-contains-non-cell"
+    | "table-row-outside-table"
+    | "table-cell-outside-table"
+    | "tab-view-empty"
+    | "tab-view-contains-non-tab"
+    | "tab-outside-tab-view"
+    | "footnotes-nested"
+    | "bibliography-contains-non-entry"
+    | "no-such-bibliography-label"
+    | "unused-bibliography-label"
+    | "blockquote-depth-exceeded"
+    | "no-such-block"
+    | "block-disallows-star"
+    | "block-disallows-score"
+    | "block-missing-name"
+    | "block-missing-close-brackets"
+    | "block-malformed-arguments"
+    | "block-missing-arguments"
+    | "block-expected-end"
+    | "block-end-mismatch"
+    | "no-such-embed"
+    | "no-such-module"
+    | "module-missing-name"
+    | "no-such-page"
+    | "no-such-variable"
+    | "invalid-url"
+    | "iframe-not-permitted"
+    | "input-too-long"
+    | "too-many-elements"
+    | "too-many-table-cells"
+    | "too-many-includes"
+    | "css-too-large"
+    | "step-limit-exceeded";
+
+export type PartialElement =
+    | { ListItem: ListItem; }
+    | { TableRow: TableRow; }
+    | { TableCell: TableCell; }
+    | { Tab: Tab; };
+
+export interface PreprocessPass {
+    pattern: string;
+    replacement: string;
+}
+
+export interface PreprocessSettings {
+    passes?: PreprocessPass[];
+}
+
+export type QuoteStyle = "english" | "french" | "german" | "japanese";
+
+export interface Range_of_uint {
+    end: number;
+    start: number;
+}
+
+export interface ResourceLimits {
+    "max-css-size"?: number;
+    "max-elements"?: number;
+    "max-include-expansions"?: number;
+    "max-input-length"?: number;
+    "max-steps"?: number;
+    "max-table-cells"?: number;
+}
+
+export interface SyntaxTree {
+    bibliographies?: Bibliography[];
+    elements: Element[];
+    footnotes: Element[][];
+    styles: string[];
+    "table-of-contents": Element[];
+}
+
+export interface Tab {
+    elements: Element[];
+    label: string;
+}
+
+export interface Table {
+    attributes: AttributeMap;
+    rows: TableRow[];
+}
+
+export interface TableCell {
+    align?: Alignment | null;
+    attributes: AttributeMap;
+    "column-span": number;
+    elements: Element[];
+    header: boolean;
+}
+
+export interface TableRow {
+    attributes: AttributeMap;
+    cells: TableCell[];
+}
+
+export type Token =
+    | "left-bracket"
+    | "left-bracket-anchor"
+    | "left-bracket-star"
+    | "right-bracket"
+    | "left-block"
+    | "left-block-end"
+    | "left-block-star"
+    | "left-math"
+    | "right-block"
+    | "right-math"
+    | "double-dash"
+    | "triple-dash"
+    | "left-double-angle"
+    | "clear-float-both"
+    | "clear-float-left"
+    | "clear-float-right"
+    | "pipe"
+    | "equals"
+    | "colon"
+    | "underscore"
+    | "quote"
+    | "heading"
+    | "line-break"
+    | "paragraph-break"
+    | "whitespace"
+    | "bold"
+    | "italics"
+    | "underline"
+    | "superscript"
+    | "subscript"
+    | "left-monospace"
+    | "right-monospace"
+    | "color"
+    | "raw"
+    | "left-raw"
+    | "right-raw"
+    | "bullet-item"
+    | "numbered-item"
+    | "left-link"
+    | "left-link-star"
+    | "right-link"
+    | "table-column"
+    | "table-column-left"
+    | "table-column-right"
+    | "table-column-center"
+    | "table-column-title"
+    | "identifier"
+    | "email"
+    | "url"
+    | "variable"
+    | "bibcite"
+    | "string"
+    | "left-comment"
+    | "right-comment"
+    | "input-start"
+    | "input-end"
+    | "other";
+
+export interface TypographySettings {
+    enabled?: boolean;
+    quotes?: QuoteStyle;
+}
+
+export type WikitextMode =
+    | "page"
+    | "draft"
+    | "forum-post"
+    | "direct-message"
+    | "list";
+
+export interface WikitextSettings {
+    "allow-local-paths": boolean;
+    "enable-json-ld"?: boolean;
+    "enable-page-syntax": boolean;
+    "enable-section-edit"?: boolean;
+    "html-block"?: HtmlBlockSettings;
+    "html-syntax"?: HtmlSyntax;
+    iframe?: IframeSettings;
+    limits?: ResourceLimits;
+    mode: WikitextMode;
+    preprocess?: PreprocessSettings;
+    typography?: TypographySettings;
+    "use-true-ids": boolean;
+}
@@ -0,0 +1,116 @@
+/*
+ * batch/mod.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Parses and renders many inputs at once, such as the posts in a forum thread.
+//!
+//! Each input goes through preprocessing, tokenization, parsing, and rendering,
+//! exactly as if those functions were called in turn. Without the `parallel`
+//! feature that is all this does, so it is no faster than a loop. It exists so
+//! that callers rendering many inputs can get parallel rendering, with outputs
+//! in the same order as their inputs, without changing their code.
+//!
+//! With the `parallel` feature, inputs are rendered on [`rayon`]'s thread pool.
+//! To use a pool other than the global one, call [`render_batch`] from within
+//! [`ThreadPool::install`].
+//!
+//! [`rayon`]: https://docs.rs/rayon
+//! [`ThreadPool::install`]: https://docs.rs/rayon/1/rayon/struct.ThreadPool.html#method.install
+
+#[cfg(test)]
+mod test;
+
+use crate::data::PageInfo;
+use crate::log::prelude::*;
+use crate::parsing::ParseOutcome;
+use crate::render::Render;
+use crate::settings::WikitextSettings;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// A single piece of wikitext to render as part of a batch.
+#[derive(Debug, Copy, Clone)]
+pub struct BatchInput<'a> {
+    /// The wikitext to render.
+    ///
+    /// Includes are not run, so any should already be expanded.
+    pub text: &'a str,
+
+    /// Information about the page this input is on.
+    pub page_info: &'a PageInfo<'a>,
+}
+
+/// Renders each of the given inputs, returning their outputs and any warnings in order.
+pub fn render_batch<R>(
+    log: &Logger,
+    renderer: &R,
+    inputs: &[BatchInput],
+    settings: &WikitextSettings,
+) -> Vec<ParseOutcome<R::Output>>
+where
+    R: Render + Sync,
+    R::Output: Send,
+{
+    info!(
+        log,
+        "Rendering batch of inputs";
+        "inputs" => inputs.len(),
+        "parallel" => cfg!(feature = "parallel"),
+    );
+
+    let render = |(index, input): (usize, &BatchInput)| {
+        render_one(log, renderer, index, input, settings)
+    };
+
+    cfg_if! {
+        if #[cfg(feature = "parallel")] {
+            inputs.par_iter().enumerate().map(render).collect()
+        } else {
+            inputs.iter().enumerate().map(render).collect()
+        }
+    }
+}
+
+fn render_one<R>(
+    log: &Logger,
+    renderer: &R,
+    index: usize,
+    input: &BatchInput,
+    settings: &WikitextSettings,
+) -> ParseOutcome<R::Output>
+where
+    R: Render,
+{
+    debug!(
+        log,
+        "Rendering batch input";
+        "index" => index,
+        "page" => input.page_info.page.as_ref(),
+    );
+
+    let mut text = str!(input.text);
+    crate::preprocess(log, &mut text, settings);
+
+    let tokens = crate::tokenize(log, &text);
+    let (tree, warnings) = crate::parse(log, &tokens, input.page_info, settings).into();
+    let output = renderer.render(log, &tree, input.page_info, settings);
+
+    ParseOutcome::new(output, warnings)
+}
@@ -0,0 +1,83 @@
+/*
+ * batch/test.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::{render_batch, BatchInput};
+use crate::data::PageInfo;
+use crate::render::html::HtmlRender;
+use crate::render::text::TextRender;
+use crate::render::Render;
+use crate::settings::{WikitextMode, WikitextSettings};
+
+const POSTS: [&str; 4] = [
+    "First post! **Hello** everyone.",
+    "Replying to the above:\n\n> quoted text\n\nAgreed.",
+    "[[div]]\nUnclosed block",
+    "Some //italics// and a [[[page-link]]].",
+];
+
+#[test]
+fn batch() {
+    let log = crate::build_logger();
+    let page_info = PageInfo::dummy();
+    let settings = WikitextSettings::from_mode(WikitextMode::ForumPost);
+
+    let inputs: Vec<_> = POSTS
+        .iter()
+        .map(|text| BatchInput {
+            text,
+            page_info: &page_info,
+        })
+        .collect();
+
+    // Same results as rendering each input separately, in the same order
+    let outputs = render_batch(&log, &TextRender, &inputs, &settings);
+    assert_eq!(outputs.len(), POSTS.len());
+
+    for (text, outcome) in POSTS.iter().zip(outputs) {
+        let mut text = str!(text);
+        crate::preprocess(&log, &mut text, &settings);
+
+        let tokens = crate::tokenize(&log, &text);
+        let (tree, warnings) = crate::parse(&log, &tokens, &page_info, &settings).into();
+        let output = TextRender.render(&log, &tree, &page_info, &settings);
+
+        assert_eq!(outcome.value(), &output, "Output differs for {:?}", text);
+        assert_eq!(
+            outcome.warnings(),
+            warnings,
+            "Warnings differ for {:?}",
+            text
+        );
+    }
+
+    // Only the unclosed block produces warnings
+    let outputs = render_batch(&log, &HtmlRender, &inputs, &settings);
+    let has_warnings: Vec<bool> = outputs
+        .iter()
+        .map(|outcome| !outcome.warnings().is_empty())
+        .collect();
+
+    assert_eq!(has_warnings, [false, false, true, false]);
+    assert!(outputs[0].value().body.contains("<strong>Hello</strong>"));
+
+    // Empty batch
+    let outputs = render_batch(&log, &HtmlRender, &[], &settings);
+    assert!(outputs.is_empty());
+}
@@ -0,0 +1,138 @@
+/*
+ * bin/ftml/epub.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Renders each input as a page, and writes them together as an EPUB publication.
+
+use super::render::{expand, read_input};
+use super::warnings::print_warnings;
+use super::Options;
+use chrono::Utc;
+use ftml::data::PageInfo;
+use ftml::epub::{write_epub, EpubBook, EpubPage};
+use ftml::render::html::{HtmlOutput, HtmlRender};
+use ftml::render::Render;
+use ftml::settings::HtmlSyntax;
+use ftml::tree::Element;
+use slog::Logger;
+use std::borrow::Cow;
+use std::fs::{self, File};
+use std::io::{self, BufWriter};
+
+/// A page which has been rendered, ready to be added to the book.
+#[derive(Debug)]
+struct RenderedPage {
+    title: String,
+    html: HtmlOutput,
+    table_of_contents: Vec<Element<'static>>,
+}
+
+pub fn run(log: &Logger, options: &Options) -> io::Result<()> {
+    let mut settings = options.settings.clone();
+    settings.html_syntax = HtmlSyntax::Epub;
+
+    // Read each page, or standard input if there are no files.
+    let sources = if options.inputs.is_empty() {
+        vec![(str!("<stdin>"), str!("stdin"), read_input(options)?)]
+    } else {
+        let mut sources = Vec::new();
+
+        for path in &options.inputs {
+            let slug = path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_else(|| str!("page"));
+
+            sources.push((path.display().to_string(), slug, fs::read_to_string(path)?));
+        }
+
+        sources
+    };
+
+    // Render each page
+    let mut pages = Vec::new();
+
+    for (name, slug, source) in sources {
+        let page_info = PageInfo {
+            page: Cow::Owned(slug.clone()),
+            title: Cow::Owned(slug),
+            ..options.page_info.clone()
+        };
+
+        let text = expand(log, options, &source)?;
+        let tokens = ftml::tokenize(log, &text);
+        let (tree, warnings) = ftml::parse(log, &tokens, &page_info, &settings).into();
+
+        if options.warnings {
+            print_warnings(options, &name, &text, &warnings);
+        }
+
+        pages.push(RenderedPage {
+            title: page_info.title.to_string(),
+            html: HtmlRender.render(log, &tree, &page_info, &settings),
+            table_of_contents: tree
+                .table_of_contents
+                .iter()
+                .map(|element| element.to_owned())
+                .collect(),
+        });
+    }
+
+    // Build the book
+    let PageInfo {
+        site,
+        page,
+        title,
+        language,
+        ..
+    } = &options.page_info;
+
+    let identifier = format!("urn:ftml:{}:{}", site, page);
+    let language = match language.as_ref() {
+        "default" => "en",
+        language => language,
+    };
+
+    let book = EpubBook {
+        identifier: &identifier,
+        title,
+        language,
+        creators: vec![],
+        modified: Utc::now(),
+        stylesheet: None,
+        pages: pages
+            .iter()
+            .map(|page| EpubPage {
+                title: &page.title,
+                html: &page.html,
+                table_of_contents: &page.table_of_contents,
+            })
+            .collect(),
+        images: vec![],
+    };
+
+    let path = options
+        .output
+        .as_ref()
+        .expect("No output file for EPUB, should have been rejected");
+
+    let file = BufWriter::new(File::create(path)?);
+    write_epub(log, &book, file)?;
+    Ok(())
+}
@@ -0,0 +1,160 @@
+/*
+ * bin/ftml/includer.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! An `Includer` which reads included pages from a local directory.
+//!
+//! Pages are resolved to files at `<root>/<site>/<page>.ftml`,
+//! where colons in the page name (that is, categories) become
+//! directory separators. On-site includes use the site given for
+//! the page being rendered.
+//!
+//! For instance, with the site `scp-wiki`:
+//! * `[[include-messy component:license-box]]` reads `<root>/scp-wiki/component/license-box.ftml`
+//! * `[[include-messy :snippets:theme]]` reads `<root>/snippets/theme.ftml`
+//!
+//! Names which would resolve outside of the root, such as `..`,
+//! are treated the same as missing pages.
+
+use ftml::data::PageRef;
+use ftml::includes::{FetchedPage, IncludeRef, Includer};
+use std::borrow::Cow;
+use std::fs;
+use std::io;
+use std::iter;
+use std::path::{Component, Path, PathBuf};
+use wikidot_normalize::normalize;
+
+#[derive(Debug)]
+pub struct DirectoryIncluder<'a> {
+    root: &'a PathBuf,
+    site: &'a str,
+}
+
+impl<'a> DirectoryIncluder<'a> {
+    #[inline]
+    pub fn new(root: &'a PathBuf, site: &'a str) -> Self {
+        DirectoryIncluder { root, site }
+    }
+
+    /// Gets the file path for this page, if it stays within the root directory.
+    ///
+    /// The site and each part of the page name must be a plain file name,
+    /// so names like `..` or ones containing path separators are rejected.
+    pub fn page_path(&self, page_ref: &PageRef) -> Option<PathBuf> {
+        let site = page_ref.site().unwrap_or(self.site);
+        let mut page = str!(page_ref.page());
+        normalize(&mut page);
+
+        let mut path = PathBuf::from(self.root);
+        for part in iter::once(site).chain(page.split(':')) {
+            if !is_file_name(part) {
+                return None;
+            }
+
+            path.push(part);
+        }
+
+        path.set_extension("ftml");
+        Some(path)
+    }
+}
+
+/// Determines if this is a single, regular path component.
+fn is_file_name(part: &str) -> bool {
+    let mut components = Path::new(part).components();
+
+    matches!(
+        (components.next(), components.next()),
+        (Some(Component::Normal(_)), None),
+    ) && !part.contains(&['/', '\\'][..])
+}
+
+impl<'t> Includer<'t> for DirectoryIncluder<'_> {
+    type Error = io::Error;
+
+    fn include_pages(
+        &mut self,
+        includes: &[IncludeRef<'t>],
+    ) -> Result<Vec<FetchedPage<'t>>, io::Error> {
+        let mut pages = Vec::new();
+
+        for include in includes {
+            let page_ref = include.page_ref().clone();
+            let content = match self.page_path(&page_ref).map(fs::read_to_string) {
+                Some(Ok(content)) => Some(Cow::Owned(content)),
+                Some(Err(error)) if error.kind() == io::ErrorKind::NotFound => None,
+                Some(Err(error)) => return Err(error),
+                None => None,
+            };
+
+            pages.push(FetchedPage { page_ref, content });
+        }
+
+        Ok(pages)
+    }
+
+    fn no_such_include(
+        &mut self,
+        page_ref: &PageRef<'t>,
+    ) -> Result<Cow<'t, str>, io::Error> {
+        let path = match self.page_path(page_ref) {
+            Some(path) => path.display().to_string(),
+            None => str!("invalid path"),
+        };
+
+        Ok(Cow::Owned(format!(
+            "[[div class=\"wj-error\"]]\nNo such page '{}' ({})\n[[/div]]",
+            page_ref, path,
+        )))
+    }
+}
+
+#[test]
+fn page_path() {
+    let root = PathBuf::from("/srv/pages");
+    let includer = DirectoryIncluder::new(&root, "scp-wiki");
+
+    assert_eq!(
+        includer.page_path(&PageRef::page_only("component:license-box")),
+        Some(PathBuf::from(
+            "/srv/pages/scp-wiki/component/license-box.ftml"
+        )),
+    );
+    assert_eq!(
+        includer.page_path(&PageRef::page_and_site("snippets", "theme")),
+        Some(PathBuf::from("/srv/pages/snippets/theme.ftml")),
+    );
+
+    assert_eq!(includer.page_path(&PageRef::page_and_site("..", "x")), None);
+    assert_eq!(
+        includer.page_path(&PageRef::page_and_site("/etc", "x")),
+        None
+    );
+    assert_eq!(
+        includer.page_path(&PageRef::page_and_site("a/b", "x")),
+        None
+    );
+    assert_eq!(
+        includer.page_path(&PageRef::page_and_site("a\\b", "x")),
+        None
+    );
+    assert_eq!(includer.page_path(&PageRef::page_and_site("", "x")), None);
+    assert_eq!(includer.page_path(&PageRef::page_only("a:..:b")), None);
+}
@@ -0,0 +1,344 @@
+/*
+ * bin/ftml/main.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Command-line interface for ftml.
+//!
+//! Runs the full wikitext pipeline (include, preprocess, tokenize, parse, render)
+//! on a file or standard input, and outputs the result in the requested format.
+//!
+//! This binary is only built with the `cli` feature:
+//!
+//! ```sh
+//! $ cargo run --features cli -- --format text page.ftml
+//! ```
+
+#[macro_use]
+extern crate str_macro;
+
+#[macro_use]
+extern crate slog;
+
+mod epub;
+mod includer;
+mod render;
+mod warnings;
+mod watch;
+
+use clap::{App, Arg, ArgMatches};
+use ftml::data::PageInfo;
+use ftml::settings::{WikitextMode, WikitextSettings};
+use slog::Logger;
+use std::borrow::Cow;
+use std::path::{Path, PathBuf};
+use std::process;
+
+fn main() {
+    let matches = build_app().get_matches();
+    let options = Options::from_matches(&matches);
+    let log = build_logger(options.verbose);
+
+    if options.watch {
+        watch::run(&log, &options);
+    }
+
+    if let Err(error) = render::run(&log, &options) {
+        eprintln!("ftml: {}", error);
+        process::exit(1);
+    }
+}
+
+fn build_app() -> App<'static, 'static> {
+    App::new("ftml")
+        .version(env!("CARGO_PKG_VERSION"))
+        .about("Parse and render Wikidot text")
+        .arg(
+            Arg::with_name("input")
+                .value_name("FILE")
+                .multiple(true)
+                .help("The wikitext file to render. Reads from standard input if omitted. \
+                       EPUB output accepts several files, one for each page."),
+        )
+        .arg(
+            Arg::with_name("output")
+                .short("o")
+                .long("output")
+                .takes_value(true)
+                .value_name("FILE")
+                .help("Write the rendered output here instead of standard output."),
+        )
+        .arg(
+            Arg::with_name("format")
+                .short("f")
+                .long("format")
+                .takes_value(true)
+                .possible_values(&["html", "text", "json", "debug", "search", "latex", "epub"])
+                .default_value("html")
+                .help("Which renderer to produce output with."),
+        )
+        .arg(
+            Arg::with_name("mode")
+                .short("m")
+                .long("mode")
+                .takes_value(true)
+                .possible_values(&["page", "draft", "forum-post", "direct-message", "list"])
+                .default_value("page")
+                .help("The wikitext mode to parse and render in."),
+        )
+        .arg(
+            Arg::with_name("warnings")
+                .short("w")
+                .long("warnings")
+                .help("Print a report of parser warnings to standard error."),
+        )
+        .arg(
+            Arg::with_name("all-warnings")
+                .long("all-warnings")
+                .requires("warnings")
+                .help("Also report warnings for internal parser fallbacks."),
+        )
+        .arg(
+            Arg::with_name("locales")
+                .long("locales")
+                .takes_value(true)
+                .value_name("DIR")
+                .help("Directory of wikitext Fluent files, to localize warnings into --language."),
+        )
+        .arg(
+            Arg::with_name("includes")
+                .short("I")
+                .long("includes")
+                .takes_value(true)
+                .value_name("DIR")
+                .help("Directory to resolve [[include-messy]] pages from."),
+        )
+        .arg(
+            Arg::with_name("watch")
+                .long("watch")
+                .requires("input")
+                .help("Re-render whenever the input file or included pages change."),
+        )
+        .arg(
+            Arg::with_name("verbose")
+                .short("v")
+                .long("verbose")
+                .help("Print the library's log output to standard error."),
+        )
+        .arg(
+            Arg::with_name("site")
+                .long("site")
+                .takes_value(true)
+                .default_value("www")
+                .help("The slug of the site the page is on."),
+        )
+        .arg(
+            Arg::with_name("page")
+                .long("page")
+                .takes_value(true)
+                .help("The slug of the page. Defaults to the input file's name."),
+        )
+        .arg(
+            Arg::with_name("category")
+                .long("category")
+                .takes_value(true)
+                .help("The category the page is in, if not _default."),
+        )
+        .arg(
+            Arg::with_name("title")
+                .long("title")
+                .takes_value(true)
+                .help("The title of the page. Defaults to the page slug."),
+        )
+        .arg(
+            Arg::with_name("alt-title")
+                .long("alt-title")
+                .takes_value(true)
+                .help("The alternate title of the page."),
+        )
+        .arg(
+            Arg::with_name("rating")
+                .long("rating")
+                .takes_value(true)
+                .default_value("0")
+                .help("The current rating of the page."),
+        )
+        .arg(
+            Arg::with_name("tags")
+                .short("t")
+                .long("tags")
+                .takes_value(true)
+                .use_delimiter(true)
+                .multiple(true)
+                .help("The tags the page has, separated by commas."),
+        )
+        .arg(
+            Arg::with_name("language")
+                .long("language")
+                .takes_value(true)
+                .default_value("default")
+                .help("The language the page is being rendered for."),
+        )
+}
+
+fn build_logger(verbose: bool) -> Logger {
+    if !verbose {
+        return Logger::root(slog::Discard, o!());
+    }
+
+    use sloggers::terminal::{Destination, TerminalLoggerBuilder};
+    use sloggers::types::Severity;
+    use sloggers::Build;
+
+    TerminalLoggerBuilder::new()
+        .level(Severity::Debug)
+        .destination(Destination::Stderr)
+        .build()
+        .expect("Unable to initialize logger")
+}
+
+/// Which renderer to pass the syntax tree to.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum OutputFormat {
+    Html,
+    Text,
+    Json,
+    Debug,
+    Search,
+    Latex,
+    Epub,
+}
+
+/// Parsed command-line options.
+#[derive(Debug)]
+pub struct Options {
+    pub inputs: Vec<PathBuf>,
+    pub output: Option<PathBuf>,
+    pub format: OutputFormat,
+    pub settings: WikitextSettings,
+    pub page_info: PageInfo<'static>,
+    pub includes: Option<PathBuf>,
+    pub locales: Option<PathBuf>,
+    pub warnings: bool,
+    pub all_warnings: bool,
+    pub watch: bool,
+    pub verbose: bool,
+}
+
+impl Options {
+    fn from_matches(matches: &ArgMatches) -> Self {
+        macro_rules! owned {
+            ($name:expr) => {
+                matches.value_of($name).map(|s| Cow::Owned(str!(s)))
+            };
+        }
+
+        let inputs: Vec<PathBuf> = matches
+            .values_of_os("input")
+            .map(|inputs| inputs.map(PathBuf::from).collect())
+            .unwrap_or_default();
+
+        let output = matches.value_of_os("output").map(PathBuf::from);
+        let includes = matches.value_of_os("includes").map(PathBuf::from);
+        let locales = matches.value_of_os("locales").map(PathBuf::from);
+
+        let format = match matches.value_of("format") {
+            Some("html") => OutputFormat::Html,
+            Some("text") => OutputFormat::Text,
+            Some("json") => OutputFormat::Json,
+            Some("debug") => OutputFormat::Debug,
+            Some("search") => OutputFormat::Search,
+            Some("latex") => OutputFormat::Latex,
+            Some("epub") => OutputFormat::Epub,
+            _ => unreachable!("Invalid format passed validation"),
+        };
+
+        let mode = match matches.value_of("mode") {
+            Some("page") => WikitextMode::Page,
+            Some("draft") => WikitextMode::Draft,
+            Some("forum-post") => WikitextMode::ForumPost,
+            Some("direct-message") => WikitextMode::DirectMessage,
+            Some("list") => WikitextMode::List,
+            _ => unreachable!("Invalid mode passed validation"),
+        };
+
+        let rating = match matches.value_of("rating").unwrap().parse() {
+            Ok(rating) => rating,
+            Err(error) => {
+                eprintln!("ftml: invalid rating: {}", error);
+                process::exit(1);
+            }
+        };
+
+        // Default the page slug to the input file's name, if any.
+        let page: Cow<str> = owned!("page").unwrap_or_else(|| {
+            let stem = inputs
+                .first()
+                .and_then(|path| path.file_stem())
+                .map(|stem| stem.to_string_lossy().into_owned());
+
+            Cow::Owned(stem.unwrap_or_else(|| str!("stdin")))
+        });
+
+        let page_info = PageInfo {
+            title: owned!("title").unwrap_or_else(|| page.clone()),
+            page,
+            category: owned!("category"),
+            site: owned!("site").unwrap(),
+            alt_title: owned!("alt-title"),
+            rating,
+            tags: matches
+                .values_of("tags")
+                .map(|tags| tags.map(|tag| Cow::Owned(str!(tag))).collect())
+                .unwrap_or_default(),
+            language: owned!("language").unwrap(),
+        };
+
+        if format == OutputFormat::Epub {
+            if output.is_none() {
+                eprintln!("ftml: EPUB output must be written to a file with --output");
+                process::exit(1);
+            }
+        } else if inputs.len() > 1 {
+            eprintln!("ftml: only EPUB output accepts more than one input file");
+            process::exit(1);
+        }
+
+        Options {
+            inputs,
+            output,
+            format,
+            settings: WikitextSettings::from_mode(mode),
+            page_info,
+            includes,
+            locales,
+            warnings: matches.is_present("warnings"),
+            all_warnings: matches.is_present("all-warnings"),
+            watch: matches.is_present("watch"),
+            verbose: matches.is_present("verbose"),
+        }
+    }
+}
+
+impl Options {
+    /// The input file, for formats which render a single page.
+    #[inline]
+    pub fn input(&self) -> Option<&Path> {
+        self.inputs.first().map(PathBuf::as_path)
+    }
+}
@@ -0,0 +1,172 @@
+/*
+ * bin/ftml/render.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Runs the wikitext pipeline for a single input, and writes its output.
+
+use super::epub;
+use super::includer::DirectoryIncluder;
+use super::warnings::print_warnings;
+use super::{Options, OutputFormat};
+use ftml::includes::NullIncluder;
+use ftml::parsing::ParseWarning;
+use ftml::render::debug::DebugRender;
+use ftml::render::html::HtmlRender;
+use ftml::render::json::JsonRender;
+use ftml::render::latex::LatexRender;
+use ftml::render::search::SearchRender;
+use ftml::render::text::TextRender;
+use ftml::render::Render;
+use slog::Logger;
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use void::ResultVoidExt;
+
+/// Reads the input, renders it, and writes the output.
+///
+/// Parser warnings are reported if they were requested,
+/// they do not cause this function to fail.
+pub fn run(log: &Logger, options: &Options) -> io::Result<()> {
+    if options.format == OutputFormat::Epub {
+        return epub::run(log, options);
+    }
+
+    let source = read_input(options)?;
+    let (text, output, warnings) = process(log, options, &source)?;
+
+    if options.warnings {
+        print_warnings(options, &input_name(options), &text, &warnings);
+    }
+
+    write_output(options, &output)
+}
+
+/// Runs include, preprocess, tokenize, parse, and render on the given source.
+///
+/// Returns the fully expanded and preprocessed wikitext (which is what
+/// the spans in the warnings refer to), the rendered output, and the warnings.
+pub fn process(
+    log: &Logger,
+    options: &Options,
+    source: &str,
+) -> io::Result<(String, String, Vec<ParseWarning>)> {
+    let Options {
+        settings,
+        page_info,
+        ..
+    } = options;
+
+    let text = expand(log, options, source)?;
+    let tokens = ftml::tokenize(log, &text);
+    let (tree, warnings) = ftml::parse(log, &tokens, page_info, settings).into();
+
+    let output = match options.format {
+        OutputFormat::Html => {
+            let html = HtmlRender.render(log, &tree, page_info, settings);
+            let mut output = String::new();
+
+            for style in &html.styles {
+                output.push_str("<style>\n");
+                output.push_str(style);
+                output.push_str("\n</style>\n");
+            }
+
+            output.push_str(&html.body);
+            output
+        }
+        OutputFormat::Text => TextRender.render(log, &tree, page_info, settings),
+        OutputFormat::Json => {
+            JsonRender::pretty().render(log, &tree, page_info, settings)
+        }
+        OutputFormat::Debug => DebugRender.render(log, &tree, page_info, settings),
+        OutputFormat::Search => {
+            let search = SearchRender.render(log, &tree, page_info, settings);
+
+            serde_json::to_string_pretty(&search).expect("Unable to serialize JSON")
+        }
+        OutputFormat::Latex => LatexRender.render(log, &tree, page_info, settings),
+        OutputFormat::Epub => unreachable!("EPUB output is written separately"),
+    };
+
+    Ok((text, output, warnings))
+}
+
+/// Runs include and preprocess on the given source.
+///
+/// Included pages are resolved from the `--includes` directory, if given.
+pub fn expand(log: &Logger, options: &Options, source: &str) -> io::Result<String> {
+    let Options {
+        settings,
+        page_info,
+        ..
+    } = options;
+
+    let mut text = match options.includes {
+        Some(ref root) => {
+            let includer = DirectoryIncluder::new(root, &page_info.site);
+            let (text, _pages) = ftml::include(log, source, settings, includer, || {
+                io::Error::new(io::ErrorKind::Other, "Includer returned invalid pages")
+            })?;
+
+            text
+        }
+        None => {
+            let (text, _pages) =
+                ftml::include(log, source, settings, NullIncluder, || unreachable!())
+                    .void_unwrap();
+
+            text
+        }
+    };
+
+    ftml::preprocess(log, &mut text, settings);
+    Ok(text)
+}
+
+pub fn input_name(options: &Options) -> String {
+    match options.input() {
+        Some(path) => path.display().to_string(),
+        None => str!("<stdin>"),
+    }
+}
+
+pub fn read_input(options: &Options) -> io::Result<String> {
+    match options.input() {
+        Some(path) => fs::read_to_string(path),
+        None => {
+            let mut source = String::new();
+            io::stdin().read_to_string(&mut source)?;
+            Ok(source)
+        }
+    }
+}
+
+fn write_output(options: &Options, output: &str) -> io::Result<()> {
+    match options.output {
+        Some(ref path) => {
+            let mut file = File::create(path)?;
+            writeln!(file, "{}", output)
+        }
+        None => {
+            let stdout = io::stdout();
+            let mut stdout = stdout.lock();
+            writeln!(stdout, "{}", output)
+        }
+    }
+}
@@ -0,0 +1,87 @@
+/*
+ * bin/ftml/warnings.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Prints parser warnings as a human-readable report.
+
+use super::Options;
+use ftml::diagnostics::{diagnose, LineIndex, Localizer, Severity};
+use ftml::parsing::ParseWarning;
+use std::fs;
+use std::path::Path;
+
+/// The English messages, used when no other locale has a message.
+const WIKITEXT_EN: &str = include_str!("../../../../locales/fluent/wikitext/en.ftl");
+
+/// Loads the localizer for the page's language, falling back to English.
+///
+/// Additional locales are read from `<locales>/<language>.ftl`, if a
+/// locales directory was given.
+pub fn build_localizer(options: &Options) -> Localizer {
+    let mut localizer = Localizer::new();
+
+    if let Some(ref directory) = options.locales {
+        let language = &options.page_info.language;
+        let path = Path::new(directory).join(format!("{}.ftl", language));
+
+        match fs::read_to_string(&path) {
+            Ok(source) => {
+                if let Err(error) = localizer.add_locale(language, source) {
+                    eprintln!("ftml: unable to load {}: {:?}", path.display(), error);
+                }
+            }
+            Err(error) => eprintln!("ftml: unable to read {}: {}", path.display(), error),
+        }
+    }
+
+    localizer
+        .add_locale("en", str!(WIKITEXT_EN))
+        .expect("Built-in English locale is invalid");
+
+    localizer
+}
+
+/// Prints each warning to standard error as a source snippet.
+///
+/// Positions refer to the preprocessed wikitext, which is what the parser saw.
+/// Warnings for internal parser fallbacks are only shown if requested.
+pub fn print_warnings(
+    options: &Options,
+    name: &str,
+    text: &str,
+    warnings: &[ParseWarning],
+) {
+    let minimum = if options.all_warnings {
+        Severity::Internal
+    } else {
+        Severity::Info
+    };
+
+    let localizer = build_localizer(options);
+    let index = LineIndex::new(text);
+    let diagnostics = diagnose(text, warnings, &localizer, minimum);
+
+    for diagnostic in &diagnostics {
+        eprintln!("{}\n", diagnostic.render_snippet(&index, &localizer, name));
+    }
+
+    if !diagnostics.is_empty() {
+        eprintln!("{} warning(s) emitted", diagnostics.len());
+    }
+}
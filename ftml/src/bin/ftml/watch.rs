@@ -0,0 +1,92 @@
+/*
+ * bin/ftml/watch.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Polls the input files and include directory, re-rendering on changes.
+
+use super::render;
+use super::Options;
+use slog::Logger;
+use std::fs;
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+pub fn run(log: &Logger, options: &Options) -> ! {
+    let mut last_modified = None;
+
+    loop {
+        let modified = latest_modification(options);
+
+        if modified != last_modified {
+            last_modified = modified;
+
+            match render::run(log, options) {
+                Ok(()) => eprintln!("ftml: rendered {}", render::input_name(options)),
+                Err(error) => eprintln!("ftml: {}", error),
+            }
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Gets the most recent modification time of the inputs or any included page.
+fn latest_modification(options: &Options) -> Option<SystemTime> {
+    let mut latest = None;
+
+    for path in &options.inputs {
+        update_latest(&mut latest, path);
+    }
+
+    if let Some(ref root) = options.includes {
+        walk_directory(&mut latest, root);
+    }
+
+    latest
+}
+
+fn walk_directory(latest: &mut Option<SystemTime>, directory: &Path) {
+    let entries = match fs::read_dir(directory) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+
+        if path.is_dir() {
+            walk_directory(latest, &path);
+        } else {
+            update_latest(latest, &path);
+        }
+    }
+}
+
+fn update_latest(latest: &mut Option<SystemTime>, path: &Path) {
+    let modified = fs::metadata(path).and_then(|metadata| metadata.modified());
+
+    if let Ok(modified) = modified {
+        if latest.map(|latest| modified > latest).unwrap_or(true) {
+            *latest = Some(modified);
+        }
+    }
+}
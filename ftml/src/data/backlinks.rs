@@ -21,11 +21,14 @@
 use crate::data::PageRef;
 use std::borrow::Cow;
 
-#[derive(Serialize, Deserialize, Debug, Clone, Hash, PartialEq, Eq, Default)]
+#[derive(
+    Serialize, Deserialize, JsonSchema, Debug, Clone, Hash, PartialEq, Eq, Default,
+)]
 #[serde(rename_all = "kebab-case")]
 pub struct Backlinks<'a> {
     pub included_pages: Vec<PageRef<'a>>,
     pub internal_links: Vec<PageRef<'a>>,
+    pub missing_links: Vec<PageRef<'a>>,
     pub external_links: Vec<Cow<'a, str>>,
 }
 
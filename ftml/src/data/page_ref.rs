@@ -32,7 +32,7 @@ use std::fmt::{self, Display};
 /// * `page` (on-site)
 ///
 /// Additionally "`page`" here may also contain colons, such as `component:some-thing`.
-#[derive(Serialize, Deserialize, Debug, Clone, Hash, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, Hash, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
 pub struct PageRef<'t> {
     pub site: Option<Cow<'t, str>>,
@@ -0,0 +1,242 @@
+/*
+ * diagnostics/localizer.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::Severity;
+use crate::parsing::{ParseWarning, ParseWarningKind};
+use fluent_bundle::{FluentArgs, FluentBundle, FluentResource, FluentValue};
+use std::fmt::{self, Debug};
+use unic_langid::LanguageIdentifier;
+
+/// Fluent message containing the descriptions of each `ParseWarningKind`.
+const WARNING_MESSAGE: &str = "wikitext-warning";
+
+/// Fluent message containing the labels for each `Severity`.
+const SEVERITY_MESSAGE: &str = "wikitext-severity";
+
+/// Produces localized messages for diagnostics.
+///
+/// This loads the Fluent files for the `wikitext` component,
+/// as found in `locales/fluent/wikitext`. Locales are tried in
+/// the order they were added, so the preferred language should be
+/// added first, and a complete locale (such as `en`) last.
+///
+/// If no locale has a message, then the warning's kind name is used.
+pub struct Localizer {
+    bundles: Vec<FluentBundle<FluentResource>>,
+}
+
+impl Localizer {
+    #[inline]
+    pub fn new() -> Self {
+        Localizer {
+            bundles: Vec::new(),
+        }
+    }
+
+    /// Adds the Fluent source for a locale to the end of the fallback chain.
+    pub fn add_locale(
+        &mut self,
+        locale: &str,
+        source: String,
+    ) -> Result<(), LocalizerError> {
+        let locale: LanguageIdentifier =
+            locale.parse().map_err(|_| LocalizerError::InvalidLocale)?;
+
+        let resource = FluentResource::try_new(source).map_err(|(_, errors)| {
+            LocalizerError::InvalidResource(
+                errors.iter().map(|error| error.to_string()).collect(),
+            )
+        })?;
+
+        let mut bundle = FluentBundle::new(vec![locale]);
+
+        // Unicode isolation marks just get in the way for plain text output.
+        bundle.set_use_isolating(false);
+        bundle
+            .add_resource(resource)
+            .map_err(|_| LocalizerError::DuplicateMessage)?;
+
+        self.bundles.push(bundle);
+        Ok(())
+    }
+
+    /// Gets the localized description of a parser warning.
+    ///
+    /// The `slice` is the section of wikitext that the warning's span covers.
+    pub fn warning_message(&self, warning: &ParseWarning, slice: &str) -> String {
+        let mut args = FluentArgs::new();
+        args.set("rule", FluentValue::from(warning.rule()));
+        args.set("token", FluentValue::from(warning.token().name()));
+        args.set("kind", FluentValue::from(warning.kind().name()));
+        args.set("slice", FluentValue::from(slice));
+
+        let attribute = kind_attribute(warning.kind());
+        self.format(WARNING_MESSAGE, &attribute, &args)
+            .unwrap_or_else(|| str!(warning.kind().name()))
+    }
+
+    /// Gets the localized label for a severity, e.g. "warning".
+    pub fn severity_label(&self, severity: Severity) -> String {
+        self.format(SEVERITY_MESSAGE, severity.name(), &FluentArgs::new())
+            .unwrap_or_else(|| str!(severity.name()))
+    }
+
+    fn format(
+        &self,
+        message: &str,
+        attribute: &str,
+        args: &FluentArgs,
+    ) -> Option<String> {
+        for bundle in &self.bundles {
+            let pattern = match bundle
+                .get_message(message)
+                .and_then(|message| message.get_attribute(attribute))
+            {
+                Some(attribute) => attribute.value(),
+                None => continue,
+            };
+
+            let mut errors = Vec::new();
+            let value = bundle.format_pattern(pattern, Some(args), &mut errors);
+
+            if errors.is_empty() {
+                return Some(value.into_owned());
+            }
+        }
+
+        None
+    }
+}
+
+impl Default for Localizer {
+    #[inline]
+    fn default() -> Self {
+        Localizer::new()
+    }
+}
+
+impl Debug for Localizer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let locales = self
+            .bundles
+            .iter()
+            .flat_map(|bundle| bundle.locales.iter())
+            .collect::<Vec<_>>();
+
+        f.debug_struct("Localizer")
+            .field("locales", &locales)
+            .finish()
+    }
+}
+
+/// Error value for `Localizer::add_locale()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LocalizerError {
+    /// The locale name is not a valid language identifier.
+    InvalidLocale,
+
+    /// The Fluent source could not be parsed.
+    InvalidResource(Vec<String>),
+
+    /// The Fluent source defines the same message more than once.
+    DuplicateMessage,
+}
+
+/// Converts the kind name into the kebab-case Fluent attribute name.
+///
+/// For instance, `BlockEndMismatch` becomes `block-end-mismatch`.
+fn kind_attribute(kind: ParseWarningKind) -> String {
+    let mut attribute = String::new();
+
+    for ch in kind.name().chars() {
+        if ch.is_ascii_uppercase() {
+            if !attribute.is_empty() {
+                attribute.push('-');
+            }
+
+            attribute.push(ch.to_ascii_lowercase());
+        } else {
+            attribute.push(ch);
+        }
+    }
+
+    attribute
+}
+
+#[cfg(test)]
+const WIKITEXT_EN: &str = include_str!("../../../locales/fluent/wikitext/en.ftl");
+
+#[test]
+fn all_kinds() {
+    use strum::IntoEnumIterator;
+
+    let mut localizer = Localizer::new();
+    localizer
+        .add_locale("en", str!(WIKITEXT_EN))
+        .expect("Unable to load wikitext locale");
+
+    for kind in ParseWarningKind::iter() {
+        let warning = super::test_warning(kind, "fallback", 0..2);
+        let message = localizer.warning_message(&warning, "[[");
+
+        assert_ne!(
+            message,
+            kind.name(),
+            "No message for warning kind {} in locales/fluent/wikitext/en.ftl",
+            kind.name(),
+        );
+    }
+}
+
+#[test]
+fn messages() {
+    let warning = super::test_warning(ParseWarningKind::NoSuchBlock, "block", 2..6);
+
+    // No locales, use fallback
+    let localizer = Localizer::new();
+    assert_eq!(localizer.warning_message(&warning, "blah"), "NoSuchBlock");
+    assert_eq!(localizer.severity_label(Severity::Error), "error");
+
+    // Preferred locale is missing the message, fall back to the next
+    let mut localizer = Localizer::new();
+    localizer
+        .add_locale("fr", str!("wikitext-severity =\n  .error = erreur\n"))
+        .expect("Unable to load French locale");
+    localizer
+        .add_locale("en", str!(WIKITEXT_EN))
+        .expect("Unable to load English locale");
+
+    assert_eq!(
+        localizer.warning_message(&warning, "blah"),
+        "Unknown block 'blah'.",
+    );
+    assert_eq!(localizer.severity_label(Severity::Error), "erreur");
+    assert_eq!(localizer.severity_label(Severity::Warning), "warning");
+
+    // Invalid inputs
+    assert_eq!(
+        Localizer::new().add_locale("!!", str!("")),
+        Err(LocalizerError::InvalidLocale),
+    );
+    assert!(matches!(
+        Localizer::new().add_locale("en", str!("= broken")),
+        Err(LocalizerError::InvalidResource(_)),
+    ));
+}
@@ -0,0 +1,132 @@
+/*
+ * diagnostics/mod.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Human-friendly diagnostics for parser warnings.
+//!
+//! A `ParseWarning` only carries a byte span, which is not what editors
+//! or people want to look at. This module converts warnings into
+//! `Diagnostic`s, which have line and column positions (in both UTF-8
+//! and UTF-16), a severity, and a localized message. They can also be
+//! rendered as source snippets in the style of `rustc`:
+//!
+//! ```text
+//! error: The block of type 'block-div' was expected to end by at least this point.
+//!  --> page.ftml:5:9
+//!   |
+//! 5 | unclosed
+//!   |         ^
+//! ```
+//!
+//! Messages are loaded from Fluent resources, specifically the `wikitext`
+//! component in the repository's `locales/fluent` directory, via `Localizer`.
+
+mod localizer;
+mod position;
+mod severity;
+mod snippet;
+
+pub use self::localizer::{Localizer, LocalizerError};
+pub use self::position::{LineIndex, Position};
+pub use self::severity::Severity;
+
+use crate::parsing::{ParseWarning, ParseWarningKind, Token};
+use std::ops::Range;
+
+/// A parser warning, annotated for display to users.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct Diagnostic {
+    pub kind: ParseWarningKind,
+    pub severity: Severity,
+    pub rule: String,
+    pub token: Token,
+
+    /// The UTF-8 byte span of the warning.
+    pub span: Range<usize>,
+
+    /// The UTF-16 code unit span of the warning.
+    pub utf16_span: Range<usize>,
+
+    /// The position the warning's span starts at.
+    pub start: Position,
+
+    /// The position the warning's span ends at.
+    pub end: Position,
+
+    /// The localized, human-readable description of the warning.
+    pub message: String,
+}
+
+impl Diagnostic {
+    pub fn new(warning: &ParseWarning, index: &LineIndex, localizer: &Localizer) -> Self {
+        let span = warning.span();
+        let slice = index.slice(span.clone());
+        let start = index.position(span.start);
+        let end = index.position(span.end);
+        let utf16_span = index.utf16_index(span.start)..index.utf16_index(span.end);
+        let message = localizer.warning_message(warning, slice);
+
+        Diagnostic {
+            kind: warning.kind(),
+            severity: warning.kind().severity(),
+            rule: str!(warning.rule()),
+            token: warning.token(),
+            span,
+            utf16_span,
+            start,
+            end,
+            message,
+        }
+    }
+}
+
+/// Converts each warning for the given text into a `Diagnostic`.
+///
+/// The text must be what was tokenized, that is, after includes
+/// and preprocessing, since that is what warning spans refer to.
+///
+/// Warnings below the given severity are dropped, which can be used
+/// to filter out internal parser fallbacks such as `RuleFailed`.
+pub fn diagnose(
+    text: &str,
+    warnings: &[ParseWarning],
+    localizer: &Localizer,
+    minimum: Severity,
+) -> Vec<Diagnostic> {
+    let index = LineIndex::new(text);
+
+    warnings
+        .iter()
+        .filter(|warning| warning.kind().severity() >= minimum)
+        .map(|warning| Diagnostic::new(warning, &index, localizer))
+        .collect()
+}
+
+/// Builds a `ParseWarning` directly, since its constructor requires parser internals.
+#[cfg(test)]
+fn test_warning(kind: ParseWarningKind, rule: &str, span: Range<usize>) -> ParseWarning {
+    serde_json::from_value(serde_json::json!({
+        "token": Token::Identifier,
+        "rule": rule,
+        "span": span,
+        "kind": kind,
+    }))
+    .expect("Unable to build test warning")
+}
@@ -0,0 +1,198 @@
+/*
+ * diagnostics/position.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use std::ops::Range;
+
+/// A line and column position within some text.
+///
+/// All values are one-indexed, as is conventional for displaying to people.
+/// Columns are offered in each unit an editor might count in.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, Hash, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct Position {
+    /// The line number.
+    pub line: usize,
+
+    /// The column, counted in Unicode scalar values (Rust `char`s).
+    pub column: usize,
+
+    /// The column, counted in UTF-8 bytes.
+    pub utf8_column: usize,
+
+    /// The column, counted in UTF-16 code units.
+    ///
+    /// This is what Javascript-based editors use.
+    pub utf16_column: usize,
+}
+
+/// Index of where each line begins, for converting byte offsets into positions.
+#[derive(Debug, Clone)]
+pub struct LineIndex<'t> {
+    text: &'t str,
+
+    /// The UTF-8 and UTF-16 offsets each line starts at.
+    line_starts: Vec<(usize, usize)>,
+}
+
+impl<'t> LineIndex<'t> {
+    pub fn new(text: &'t str) -> Self {
+        let mut line_starts = vec![(0, 0)];
+        let mut utf16_index = 0;
+
+        for (index, ch) in text.char_indices() {
+            utf16_index += ch.len_utf16();
+
+            if ch == '\n' {
+                line_starts.push((index + 1, utf16_index));
+            }
+        }
+
+        LineIndex { text, line_starts }
+    }
+
+    #[inline]
+    pub fn text(&self) -> &'t str {
+        self.text
+    }
+
+    #[inline]
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// Gets the zero-indexed line containing this byte offset.
+    fn line_index(&self, index: usize) -> usize {
+        match self
+            .line_starts
+            .binary_search_by_key(&index, |&(start, _)| start)
+        {
+            Ok(line) => line,
+            Err(line) => line - 1,
+        }
+    }
+
+    /// Converts a UTF-8 byte offset into a `Position`.
+    ///
+    /// # Panics
+    /// Panics if the offset is out of range for the text,
+    /// or is not on a character boundary.
+    pub fn position(&self, index: usize) -> Position {
+        let line = self.line_index(index);
+        let (line_start, _) = self.line_starts[line];
+        let before = &self.text[line_start..index];
+
+        Position {
+            line: line + 1,
+            column: before.chars().count() + 1,
+            utf8_column: before.len() + 1,
+            utf16_column: before.encode_utf16().count() + 1,
+        }
+    }
+
+    /// Converts a UTF-8 byte offset into a UTF-16 code unit offset.
+    ///
+    /// # Panics
+    /// Has the same conditions as `position()`.
+    pub fn utf16_index(&self, index: usize) -> usize {
+        let line = self.line_index(index);
+        let (line_start, utf16_line_start) = self.line_starts[line];
+        let before = &self.text[line_start..index];
+
+        utf16_line_start + before.encode_utf16().count()
+    }
+
+    /// Gets the contents of the given one-indexed line, without its newline.
+    pub fn line(&self, line: usize) -> &'t str {
+        let (start, _) = self.line_starts[line - 1];
+        let end = match self.line_starts.get(line) {
+            Some(&(next_start, _)) => next_start - 1,
+            None => self.text.len(),
+        };
+
+        &self.text[start..end]
+    }
+
+    #[inline]
+    pub fn slice(&self, span: Range<usize>) -> &'t str {
+        &self.text[span]
+    }
+}
+
+#[test]
+fn position() {
+    macro_rules! check {
+        ($text:expr, $index:expr, $line:expr, $column:expr, $utf8:expr, $utf16:expr $(,)?) => {{
+            let index = LineIndex::new($text);
+            let actual = index.position($index);
+            let expected = Position {
+                line: $line,
+                column: $column,
+                utf8_column: $utf8,
+                utf16_column: $utf16,
+            };
+
+            assert_eq!(
+                actual, expected,
+                "Actual position doesn't match expected (index {} in {:?})",
+                $index, $text,
+            );
+        }};
+    }
+
+    check!("", 0, 1, 1, 1, 1);
+    check!("abc", 0, 1, 1, 1, 1);
+    check!("abc", 2, 1, 3, 3, 3);
+    check!("abc", 3, 1, 4, 4, 4);
+    check!("ab\ncd", 3, 2, 1, 1, 1);
+    check!("ab\ncd", 2, 1, 3, 3, 3);
+    check!("ab\ncd\n", 6, 3, 1, 1, 1);
+    check!("aß\nxℝ🦀c", 4, 2, 1, 1, 1);
+    check!("aß\nxℝ🦀c", 5, 2, 2, 2, 2);
+    check!("aß\nxℝ🦀c", 8, 2, 3, 5, 3);
+    check!("aß\nxℝ🦀c", 12, 2, 4, 9, 5);
+}
+
+#[test]
+fn lines() {
+    let index = LineIndex::new("apple\nbanana\n\ncherry");
+
+    assert_eq!(index.line_count(), 4);
+    assert_eq!(index.line(1), "apple");
+    assert_eq!(index.line(2), "banana");
+    assert_eq!(index.line(3), "");
+    assert_eq!(index.line(4), "cherry");
+}
+
+#[test]
+fn utf16_index() {
+    use crate::utf16::Utf16IndexMap;
+
+    let text = "x💣y\nßz🦀\n\nℝ";
+    let index = LineIndex::new(text);
+    let map = Utf16IndexMap::new(text);
+
+    for (utf8_index, _) in text.char_indices() {
+        assert_eq!(
+            index.utf16_index(utf8_index),
+            map.get_index(utf8_index),
+            "UTF-16 index doesn't match Utf16IndexMap",
+        );
+    }
+}
@@ -0,0 +1,129 @@
+/*
+ * diagnostics/severity.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use crate::parsing::ParseWarningKind;
+use strum_macros::IntoStaticStr;
+
+/// How important a parser warning is to the person writing the wikitext.
+///
+/// The ordering goes from least to most severe, so filtering can be done
+/// with a simple comparison, e.g. `severity >= Severity::Warning`.
+#[derive(
+    Serialize,
+    Deserialize,
+    IntoStaticStr,
+    Debug,
+    Copy,
+    Clone,
+    Hash,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+)]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+pub enum Severity {
+    /// The parser tried something and fell back to another rule.
+    ///
+    /// This is normal during parsing and not something users need to see.
+    Internal,
+
+    /// Not necessarily a problem, but may be worth pointing out.
+    Info,
+
+    /// Probably a mistake, but the output is still reasonable.
+    Warning,
+
+    /// Definitely a mistake, the output will not be what was intended.
+    Error,
+}
+
+impl Severity {
+    #[inline]
+    pub fn name(self) -> &'static str {
+        self.into()
+    }
+}
+
+impl ParseWarningKind {
+    /// Gets how severe this kind of warning is.
+    ///
+    /// This matches the linting configuration used by the editor.
+    pub fn severity(self) -> Severity {
+        use self::ParseWarningKind::*;
+
+        match self {
+            EndOfInput | NoRulesMatch | RuleFailed | NotStartOfLine => Severity::Internal,
+            NotSupportedMode | NoSuchVariable | UnusedBibliographyLabel => Severity::Info,
+            ListEmpty
+            | TabViewEmpty
+            | BlockDisallowsStar
+            | BlockDisallowsScore
+            | NoSuchBibliographyLabel
+            | InvalidUrl
+            | IframeNotPermitted => Severity::Warning,
+            RecursionDepthExceeded
+            | InvalidInclude
+            | ListContainsNonItem
+            | ListItemOutsideList
+            | ListDepthExceeded
+            | TableContainsNonRow
+            | TableRowContainsNonCell
+            | TableRowOutsideTable
+            | TableCellOutsideTable
+            | TabViewContainsNonTab
+            | TabOutsideTabView
+            | FootnotesNested
+            | BibliographyContainsNonEntry
+            | BlockquoteDepthExceeded
+            | NoSuchBlock
+            | BlockMissingName
+            | BlockMissingCloseBrackets
+            | BlockMalformedArguments
+            | BlockMissingArguments
+            | BlockExpectedEnd
+            | BlockEndMismatch
+            | NoSuchEmbed
+            | NoSuchModule
+            | ModuleMissingName
+            | NoSuchPage
+            | InputTooLong
+            | TooManyElements
+            | TooManyTableCells
+            | TooManyIncludes
+            | CssTooLarge
+            | StepLimitExceeded => Severity::Error,
+        }
+    }
+}
+
+#[test]
+fn ordering() {
+    assert!(Severity::Internal < Severity::Info);
+    assert!(Severity::Info < Severity::Warning);
+    assert!(Severity::Warning < Severity::Error);
+
+    assert_eq!(ParseWarningKind::RuleFailed.severity(), Severity::Internal);
+    assert_eq!(
+        ParseWarningKind::BlockEndMismatch.severity(),
+        Severity::Error
+    );
+}
@@ -0,0 +1,116 @@
+/*
+ * diagnostics/snippet.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::{Diagnostic, LineIndex, Localizer};
+
+impl Diagnostic {
+    /// Renders this diagnostic as a source snippet, in the style of `rustc`.
+    ///
+    /// The `source_name` is the name of the file (or other source) the text
+    /// came from, and is shown next to the position. Only the first line of
+    /// the span is displayed, with carets under the portion it covers.
+    pub fn render_snippet(
+        &self,
+        index: &LineIndex,
+        localizer: &Localizer,
+        source_name: &str,
+    ) -> String {
+        let mut output = String::new();
+        let line_number = str!(self.start.line);
+        let gutter = " ".repeat(line_number.len());
+        let line = index.line(self.start.line);
+
+        // Carets start at the column and continue to the end of the span,
+        // or of the line, whichever comes first. Empty spans get one caret.
+        let caret_offset = self.start.column - 1;
+        let caret_length = if self.end.line == self.start.line {
+            self.end.column - self.start.column
+        } else {
+            line.chars().count() - caret_offset
+        };
+
+        str_write!(
+            output,
+            "{}: {}\n",
+            localizer.severity_label(self.severity),
+            self.message,
+        );
+        str_write!(
+            output,
+            "{}--> {}:{}:{}\n",
+            gutter,
+            source_name,
+            self.start.line,
+            self.start.column,
+        );
+        str_write!(output, "{} |\n", gutter);
+        str_write!(output, "{} | {}\n", line_number, line);
+        str_write!(
+            output,
+            "{} | {}{}",
+            gutter,
+            " ".repeat(caret_offset),
+            "^".repeat(caret_length.max(1)),
+        );
+
+        output
+    }
+}
+
+#[test]
+fn snippet() {
+    use super::{diagnose, Severity};
+    use crate::parsing::ParseWarningKind;
+
+    let text = "apple\n[[div]]\nbanana [[/span]] cherry\n";
+    let warnings = vec![
+        super::test_warning(ParseWarningKind::BlockEndMismatch, "block-div", 14..23),
+        super::test_warning(ParseWarningKind::RuleFailed, "block-div", 6..13),
+        super::test_warning(ParseWarningKind::BlockExpectedEnd, "block-div", 38..38),
+    ];
+
+    let localizer = Localizer::new();
+    let index = LineIndex::new(text);
+    let diagnostics = diagnose(text, &warnings, &localizer, Severity::Info);
+
+    assert_eq!(
+        diagnostics.len(),
+        2,
+        "Internal-severity diagnostic wasn't filtered out",
+    );
+
+    assert_eq!(
+        diagnostics[0].render_snippet(&index, &localizer, "page.ftml"),
+        "error: BlockEndMismatch
+ --> page.ftml:3:1
+  |
+3 | banana [[/span]] cherry
+  | ^^^^^^^^^",
+    );
+
+    assert_eq!(
+        diagnostics[1].render_snippet(&index, &localizer, "page.ftml"),
+        "error: BlockExpectedEnd
+ --> page.ftml:4:1
+  |
+4 | 
+  | ^",
+    );
+}
@@ -0,0 +1,336 @@
+/*
+ * epub/document.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Builds the XML documents within an EPUB archive.
+
+use super::{image_path, page_path, page_style_path, EpubBook, EpubPage};
+use crate::log::prelude::*;
+use crate::render::html::escape::escape;
+use crate::render::Handle;
+use crate::tree::{Element, LinkLabel, LinkLocation, ListItem};
+
+const XML_DECLARATION: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n";
+
+/// Builds `META-INF/container.xml`, which points to the package document.
+pub fn build_container() -> String {
+    let mut buffer = str!(XML_DECLARATION);
+    buffer.push_str(
+        "<container version=\"1.0\" xmlns=\"urn:oasis:names:tc:opendocument:xmlns:container\">\n\
+         <rootfiles>\n\
+         <rootfile full-path=\"EPUB/package.opf\" media-type=\"application/oebps-package+xml\" />\n\
+         </rootfiles>\n\
+         </container>\n",
+    );
+    buffer
+}
+
+/// Builds the package document, with the publication's metadata,
+/// the list of files within it, and the reading order.
+pub fn build_package(book: &EpubBook) -> String {
+    let mut buffer = str!(XML_DECLARATION);
+
+    buffer.push_str("<package xmlns=\"http://www.idpf.org/2007/opf\" version=\"3.0\" unique-identifier=\"book-id\" xml:lang=\"");
+    escape(&mut buffer, book.language);
+    buffer.push_str("\">\n");
+
+    // Metadata
+    buffer.push_str("<metadata xmlns:dc=\"http://purl.org/dc/elements/1.1/\">\n");
+    push_element(
+        &mut buffer,
+        "dc:identifier id=\"book-id\"",
+        "dc:identifier",
+        book.identifier,
+    );
+    push_element(&mut buffer, "dc:title", "dc:title", book.title);
+    push_element(&mut buffer, "dc:language", "dc:language", book.language);
+
+    for creator in &book.creators {
+        push_element(&mut buffer, "dc:creator", "dc:creator", creator);
+    }
+
+    let modified = book.modified.format("%Y-%m-%dT%H:%M:%SZ").to_string();
+    push_element(
+        &mut buffer,
+        "meta property=\"dcterms:modified\"",
+        "meta",
+        &modified,
+    );
+    buffer.push_str("</metadata>\n");
+
+    // Manifest
+    buffer.push_str("<manifest>\n");
+    buffer.push_str("<item id=\"nav\" href=\"nav.xhtml\" media-type=\"application/xhtml+xml\" properties=\"nav\" />\n");
+
+    if book.stylesheet.is_some() {
+        buffer.push_str("<item id=\"style-book\" href=\"styles/book.css\" media-type=\"text/css\" />\n");
+    }
+
+    for (index, page) in book.pages.iter().enumerate() {
+        let body = rewrite_image_urls(book, &page.html.body);
+        let properties = get_page_properties(&body);
+
+        str_write!(
+            buffer,
+            "<item id=\"page-{}\" href=\"{}\" media-type=\"application/xhtml+xml\"",
+            index + 1,
+            page_path(index),
+        );
+
+        if !properties.is_empty() {
+            str_write!(buffer, " properties=\"{}\"", properties.join(" "));
+        }
+
+        buffer.push_str(" />\n");
+
+        if !page.html.styles.is_empty() {
+            str_write!(
+                buffer,
+                "<item id=\"style-page-{}\" href=\"{}\" media-type=\"text/css\" />\n",
+                index + 1,
+                page_style_path(index),
+            );
+        }
+    }
+
+    for (index, image) in book.images.iter().enumerate() {
+        str_write!(
+            buffer,
+            "<item id=\"image-{}\" href=\"{}\" media-type=\"",
+            index + 1,
+            image_path(index, image),
+        );
+        escape(&mut buffer, image.media_type);
+        buffer.push_str("\" />\n");
+    }
+
+    buffer.push_str("</manifest>\n");
+
+    // Spine, the reading order
+    buffer.push_str("<spine>\n");
+
+    for index in 0..book.pages.len() {
+        str_write!(buffer, "<itemref idref=\"page-{}\" />\n", index + 1);
+    }
+
+    buffer.push_str("</spine>\n</package>\n");
+    buffer
+}
+
+/// Builds the navigation document, listing each page and its headings.
+pub fn build_nav(log: &Logger, book: &EpubBook) -> String {
+    let title = Handle.get_message(log, book.language, "table-of-contents");
+    let mut buffer = start_document(book.language, title);
+
+    buffer.push_str("<nav epub:type=\"toc\" id=\"toc\">\n<h1>");
+    escape(&mut buffer, title);
+    buffer.push_str("</h1>\n<ol>\n");
+
+    for (index, page) in book.pages.iter().enumerate() {
+        let path = page_path(index);
+
+        buffer.push_str("<li><a href=\"");
+        escape(&mut buffer, &path);
+        buffer.push_str("\">");
+        escape(&mut buffer, page.title);
+        buffer.push_str("</a>");
+
+        for element in page.table_of_contents {
+            if let Element::List { items, .. } = element {
+                if has_toc_links(items) {
+                    buffer.push_str("\n<ol>\n");
+                    push_toc_items(&mut buffer, &path, items);
+                    buffer.push_str("</ol>\n");
+                }
+            }
+        }
+
+        buffer.push_str("</li>\n");
+    }
+
+    buffer.push_str("</ol>\n</nav>\n");
+    end_document(buffer)
+}
+
+/// Builds the document for a single page.
+pub fn build_page(book: &EpubBook, page: &EpubPage, index: usize) -> String {
+    let mut buffer = start_document_head(book.language, page.title);
+
+    if book.stylesheet.is_some() {
+        buffer.push_str(
+            "<link rel=\"stylesheet\" type=\"text/css\" href=\"styles/book.css\" />\n",
+        );
+    }
+
+    if !page.html.styles.is_empty() {
+        str_write!(
+            buffer,
+            "<link rel=\"stylesheet\" type=\"text/css\" href=\"{}\" />\n",
+            page_style_path(index),
+        );
+    }
+
+    buffer.push_str("</head>\n<body>\n<h1 class=\"wj-page-title\">");
+    escape(&mut buffer, page.title);
+    buffer.push_str("</h1>\n");
+    buffer.push_str(&rewrite_image_urls(book, &page.html.body));
+    buffer.push('\n');
+
+    end_document(buffer)
+}
+
+/// Points images in a page body to their copies within the archive.
+pub fn rewrite_image_urls(book: &EpubBook, body: &str) -> String {
+    let mut body = str!(body);
+
+    for (index, image) in book.images.iter().enumerate() {
+        let mut from = str!("src=\"");
+        escape(&mut from, image.url);
+        from.push('"');
+
+        let to = format!("src=\"{}\"", image_path(index, image));
+        body = body.replace(&from, &to);
+    }
+
+    body
+}
+
+/// Gets the manifest properties a page needs, based on its contents.
+fn get_page_properties(body: &str) -> Vec<&'static str> {
+    let mut properties = Vec::new();
+
+    if body.contains("<math") {
+        properties.push("mathml");
+    }
+
+    if body.contains("<svg") {
+        properties.push("svg");
+    }
+
+    if body.contains("src=\"http://") || body.contains("src=\"https://") {
+        properties.push("remote-resources");
+    }
+
+    properties
+}
+
+// Table of contents
+
+/// Adds the items of a table of contents list.
+///
+/// Sublists are nested within the item before them. A sublist
+/// with no item before it is added at the current level instead,
+/// since each item in the navigation document must have a label.
+fn push_toc_items(buffer: &mut String, path: &str, items: &[ListItem]) {
+    let mut item_open = false;
+
+    for item in items {
+        match item {
+            ListItem::Elements { elements, .. } => {
+                if let Some((anchor, label)) = get_toc_link(elements) {
+                    if item_open {
+                        buffer.push_str("</li>\n");
+                    }
+
+                    buffer.push_str("<li><a href=\"");
+                    escape(buffer, path);
+                    escape(buffer, anchor);
+                    buffer.push_str("\">");
+                    escape(buffer, label);
+                    buffer.push_str("</a>");
+                    item_open = true;
+                }
+            }
+            ListItem::SubList { element } => {
+                if let Element::List { items, .. } = element.as_ref() {
+                    if !has_toc_links(items) {
+                        continue;
+                    }
+
+                    if item_open {
+                        buffer.push_str("\n<ol>\n");
+                        push_toc_items(buffer, path, items);
+                        buffer.push_str("</ol>\n");
+                    } else {
+                        push_toc_items(buffer, path, items);
+                    }
+                }
+            }
+        }
+    }
+
+    if item_open {
+        buffer.push_str("</li>\n");
+    }
+}
+
+/// Gets the anchor and label of a table of contents entry.
+fn get_toc_link<'a>(elements: &'a [Element]) -> Option<(&'a str, &'a str)> {
+    elements.iter().find_map(|element| match element {
+        Element::Link {
+            link: LinkLocation::Url(url),
+            label: LinkLabel::Text(label),
+            ..
+        } if url.starts_with('#') => Some((url.as_ref(), label.as_ref())),
+        _ => None,
+    })
+}
+
+/// Whether a table of contents list has any entries, so it isn't empty.
+fn has_toc_links(items: &[ListItem]) -> bool {
+    items.iter().any(|item| match item {
+        ListItem::Elements { elements, .. } => get_toc_link(elements).is_some(),
+        ListItem::SubList { element } => match element.as_ref() {
+            Element::List { items, .. } => has_toc_links(items),
+            _ => false,
+        },
+    })
+}
+
+// Helpers
+
+fn push_element(buffer: &mut String, start: &str, end: &str, contents: &str) {
+    str_write!(buffer, "<{}>", start);
+    escape(buffer, contents);
+    str_write!(buffer, "</{}>\n", end);
+}
+
+fn start_document_head(language: &str, title: &str) -> String {
+    let mut buffer = str!(XML_DECLARATION);
+
+    buffer.push_str("<!DOCTYPE html>\n<html xmlns=\"http://www.w3.org/1999/xhtml\" xmlns:epub=\"http://www.idpf.org/2007/ops\" xml:lang=\"");
+    escape(&mut buffer, language);
+    buffer.push_str("\" lang=\"");
+    escape(&mut buffer, language);
+    buffer.push_str("\">\n<head>\n<meta charset=\"utf-8\" />\n<title>");
+    escape(&mut buffer, title);
+    buffer.push_str("</title>\n");
+    buffer
+}
+
+fn start_document(language: &str, title: &str) -> String {
+    let mut buffer = start_document_head(language, title);
+    buffer.push_str("</head>\n<body>\n");
+    buffer
+}
+
+fn end_document(mut buffer: String) -> String {
+    buffer.push_str("</body>\n</html>\n");
+    buffer
+}
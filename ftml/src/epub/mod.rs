@@ -0,0 +1,201 @@
+/*
+ * epub/mod.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Exports a set of rendered pages as an EPUB 3 publication.
+//!
+//! Each page becomes its own XHTML document in the book, in the order given,
+//! and the navigation document is built from each page's table of contents.
+//!
+//! Pages must be rendered by [`HtmlRender`] with [`HtmlSyntax::Epub`], so that
+//! their bodies are well-formed XML and footnotes are EPUB notes, which
+//! reading systems can show as popups. The table of contents links rely on
+//! heading IDs, so [`use_true_ids`] should be enabled as well.
+//!
+//! Nothing is fetched while building the archive, so any images the pages use
+//! must be provided. Images which are referenced but not provided are left
+//! pointing at their original URLs.
+//!
+//! [`HtmlRender`]: crate::render::html::HtmlRender
+//! [`HtmlSyntax::Epub`]: crate::settings::HtmlSyntax::Epub
+//! [`use_true_ids`]: crate::settings::WikitextSettings::use_true_ids
+
+#[cfg(test)]
+mod test;
+
+mod document;
+
+use self::document::{build_container, build_nav, build_package, build_page};
+use crate::log::prelude::*;
+use crate::render::html::HtmlOutput;
+use crate::tree::Element;
+use chrono::{DateTime, Utc};
+use std::io::{self, Seek, Write};
+use zip::write::FileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+/// The metadata and contents of an EPUB publication.
+#[derive(Debug, Clone)]
+pub struct EpubBook<'a> {
+    /// A unique identifier for this publication, such as a URL or UUID URN.
+    pub identifier: &'a str,
+
+    /// The title of the publication.
+    pub title: &'a str,
+
+    /// The language of the publication, as a BCP 47 tag, such as `en`.
+    pub language: &'a str,
+
+    /// The authors of the publication, if any.
+    pub creators: Vec<&'a str>,
+
+    /// When the publication was last modified.
+    pub modified: DateTime<Utc>,
+
+    /// CSS which applies to every page.
+    pub stylesheet: Option<&'a str>,
+
+    /// The pages of the publication, in reading order.
+    pub pages: Vec<EpubPage<'a>>,
+
+    /// Images which are referenced by the pages.
+    pub images: Vec<EpubImage<'a>>,
+}
+
+/// A single rendered page within a publication.
+#[derive(Debug, Clone)]
+pub struct EpubPage<'a> {
+    /// The title of the page, shown above it and in the navigation document.
+    pub title: &'a str,
+
+    /// The page as rendered by `HtmlRender`.
+    ///
+    /// Its styles are included as a stylesheet for this page only.
+    pub html: &'a HtmlOutput,
+
+    /// The table of contents from the page's syntax tree.
+    pub table_of_contents: &'a [Element<'a>],
+}
+
+/// An image to be included in a publication.
+#[derive(Debug, Clone)]
+pub struct EpubImage<'a> {
+    /// The URL used to refer to this image in the rendered pages.
+    pub url: &'a str,
+
+    /// The media type of the image, such as `image/png`.
+    pub media_type: &'a str,
+
+    /// The contents of the image file.
+    pub data: &'a [u8],
+}
+
+/// Writes the given publication as an EPUB archive.
+///
+/// Returns the writer once the archive has been finished.
+pub fn write_epub<W>(log: &Logger, book: &EpubBook, writer: W) -> io::Result<W>
+where
+    W: Write + Seek,
+{
+    info!(
+        log,
+        "Writing EPUB archive";
+        "title" => book.title,
+        "pages" => book.pages.len(),
+        "images" => book.images.len(),
+    );
+
+    let stored = FileOptions::default().compression_method(CompressionMethod::Stored);
+    let deflated = FileOptions::default().compression_method(CompressionMethod::Deflated);
+    let mut zip = ZipWriter::new(writer);
+
+    // The media type must be the first file, and uncompressed,
+    // so the archive can be identified from its first bytes.
+    zip.start_file("mimetype", stored)?;
+    zip.write_all(b"application/epub+zip")?;
+
+    zip.start_file("META-INF/container.xml", deflated)?;
+    zip.write_all(build_container().as_bytes())?;
+
+    zip.start_file("EPUB/package.opf", deflated)?;
+    zip.write_all(build_package(book).as_bytes())?;
+
+    zip.start_file("EPUB/nav.xhtml", deflated)?;
+    zip.write_all(build_nav(log, book).as_bytes())?;
+
+    if let Some(stylesheet) = book.stylesheet {
+        zip.start_file("EPUB/styles/book.css", deflated)?;
+        zip.write_all(stylesheet.as_bytes())?;
+    }
+
+    for (index, page) in book.pages.iter().enumerate() {
+        debug!(log, "Adding page to EPUB"; "index" => index, "title" => page.title);
+
+        zip.start_file(format!("EPUB/{}", page_path(index)), deflated)?;
+        zip.write_all(build_page(book, page, index).as_bytes())?;
+
+        if !page.html.styles.is_empty() {
+            zip.start_file(format!("EPUB/{}", page_style_path(index)), deflated)?;
+            zip.write_all(page.html.styles.join("\n").as_bytes())?;
+        }
+    }
+
+    // Images are already compressed
+    for (index, image) in book.images.iter().enumerate() {
+        debug!(log, "Adding image to EPUB"; "index" => index, "url" => image.url);
+
+        zip.start_file(format!("EPUB/{}", image_path(index, image)), stored)?;
+        zip.write_all(image.data)?;
+    }
+
+    let writer = zip.finish()?;
+    Ok(writer)
+}
+
+/// The path of a page's document, relative to the package.
+fn page_path(index: usize) -> String {
+    format!("page-{}.xhtml", index + 1)
+}
+
+/// The path of a page's own stylesheet, relative to the package.
+fn page_style_path(index: usize) -> String {
+    format!("styles/page-{}.css", index + 1)
+}
+
+/// The path of an image, relative to the package.
+///
+/// The extension is kept from the original URL, if it has one.
+fn image_path(index: usize, image: &EpubImage) -> String {
+    let name = image.url.rsplit('/').next().unwrap_or("");
+    let name = name.split(|c| c == '?' || c == '#').next().unwrap_or("");
+
+    match name.rsplit_once('.') {
+        Some((_, extension))
+            if !extension.is_empty()
+                && extension.chars().all(|c| c.is_ascii_alphanumeric()) =>
+        {
+            format!(
+                "images/image-{}.{}",
+                index + 1,
+                extension.to_ascii_lowercase()
+            )
+        }
+        _ => format!("images/image-{}", index + 1),
+    }
+}
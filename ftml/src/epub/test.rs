@@ -0,0 +1,149 @@
+/*
+ * epub/test.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::{write_epub, EpubBook, EpubImage, EpubPage};
+use crate::data::PageInfo;
+use crate::log::prelude::*;
+use crate::render::html::{HtmlOutput, HtmlRender};
+use crate::render::Render;
+use crate::settings::{HtmlSyntax, WikitextMode, WikitextSettings};
+use crate::tree::Element;
+use chrono::{TimeZone, Utc};
+use std::io::{Cursor, Read};
+use zip::{CompressionMethod, ZipArchive};
+
+fn render(
+    log: &Logger,
+    input: &str,
+    page_info: &PageInfo,
+) -> (HtmlOutput, Vec<Element<'static>>) {
+    let mut settings = WikitextSettings::from_mode(WikitextMode::Page);
+    settings.html_syntax = HtmlSyntax::Epub;
+
+    let mut text = str!(input);
+    crate::preprocess(log, &mut text, &settings);
+    let tokens = crate::tokenize(log, &text);
+    let (tree, _warnings) = crate::parse(log, &tokens, page_info, &settings).into();
+    let html = HtmlRender.render(log, &tree, page_info, &settings);
+    let table_of_contents = tree
+        .table_of_contents
+        .iter()
+        .map(|element| element.to_owned())
+        .collect();
+
+    (html, table_of_contents)
+}
+
+#[test]
+fn epub() {
+    let log = crate::build_logger();
+    let page_info = PageInfo::dummy();
+
+    let (html_1, toc_1) = render(
+        &log,
+        "+ Apple\n\nFruit[[footnote]]Usually red.[[/footnote]]\n\n++ Seeds\n\nInside.\n\n[[image https://example.com/apple.png]]\n\n----",
+        &page_info,
+    );
+    let (html_2, toc_2) = render(&log, "Just text & more.", &page_info);
+
+    let book = EpubBook {
+        identifier: "urn:uuid:00000000-0000-0000-0000-000000000000",
+        title: "Fruit & Veg",
+        language: "en",
+        creators: vec!["Wikijump"],
+        modified: Utc.ymd(2021, 6, 1).and_hms(12, 0, 0),
+        stylesheet: Some("body { margin: 0; }"),
+        pages: vec![
+            EpubPage {
+                title: "Apples",
+                html: &html_1,
+                table_of_contents: &toc_1,
+            },
+            EpubPage {
+                title: "Other",
+                html: &html_2,
+                table_of_contents: &toc_2,
+            },
+        ],
+        images: vec![EpubImage {
+            url: "https://example.com/apple.png",
+            media_type: "image/png",
+            data: b"not really a png",
+        }],
+    };
+
+    let buffer =
+        write_epub(&log, &book, Cursor::new(Vec::new())).expect("Unable to write EPUB");
+
+    let mut archive = ZipArchive::new(buffer).expect("Unable to read EPUB");
+
+    macro_rules! read {
+        ($name:expr) => {{
+            let mut file = archive.by_name($name).expect("File missing from EPUB");
+            let mut contents = String::new();
+            file.read_to_string(&mut contents)
+                .expect("Unable to read file");
+            contents
+        }};
+    }
+
+    // The media type must come first, uncompressed
+    {
+        let file = archive.by_index(0).unwrap();
+        assert_eq!(file.name(), "mimetype");
+        assert_eq!(file.compression(), CompressionMethod::Stored);
+    }
+    assert_eq!(read!("mimetype"), "application/epub+zip");
+    assert!(read!("META-INF/container.xml").contains("full-path=\"EPUB/package.opf\""));
+
+    // Package
+    let package = read!("EPUB/package.opf");
+    assert!(package.contains("<dc:title>Fruit &amp; Veg</dc:title>"));
+    assert!(package
+        .contains("<meta property=\"dcterms:modified\">2021-06-01T12:00:00Z</meta>"));
+    assert!(package.contains("href=\"images/image-1.png\" media-type=\"image/png\""));
+    assert!(
+        package.contains("<itemref idref=\"page-1\" />\n<itemref idref=\"page-2\" />")
+    );
+
+    // Navigation, nested from the table of contents
+    let nav = read!("EPUB/nav.xhtml");
+    assert!(
+        nav.contains(
+            "<li><a href=\"page-1.xhtml\">Apples</a>\n<ol>\n\
+             <li><a href=\"page-1.xhtml#toc0\">Apple</a>\n<ol>\n\
+             <li><a href=\"page-1.xhtml#toc1\">Seeds</a></li>\n</ol>\n</li>\n</ol>\n</li>\n\
+             <li><a href=\"page-2.xhtml\">Other</a></li>",
+        ),
+        "Navigation document doesn't match:\n{}",
+        nav,
+    );
+
+    // Pages are XHTML, with popup footnotes and local images
+    let page = read!("EPUB/page-1.xhtml");
+    assert!(page.contains("xmlns:epub=\"http://www.idpf.org/2007/ops\""));
+    assert!(page.contains("epub:type=\"noteref\""));
+    assert!(page.contains("<aside class=\"wj-footnote-list-item\" id=\"wj-footnote-1\" epub:type=\"footnote\""));
+    assert!(page.contains("src=\"images/image-1.png\""));
+    assert!(page.contains("<hr />"));
+    assert!(!page.contains("<hr>"));
+    assert!(read!("EPUB/page-2.xhtml").contains("Just text &amp; more."));
+    assert_eq!(read!("EPUB/images/image-1.png"), "not really a png");
+}
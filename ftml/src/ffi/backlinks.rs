@@ -33,6 +33,10 @@ pub struct ftml_backlinks {
     pub internal_links_list: *mut ftml_page_ref,
     pub internal_links_len: usize,
 
+    // Internal links to pages which don't exist
+    pub missing_links_list: *mut ftml_page_ref,
+    pub missing_links_len: usize,
+
     // External links
     pub external_links_list: *mut *mut c_char,
     pub external_links_len: usize,
@@ -54,6 +58,9 @@ impl From<Backlinks<'_>> for ftml_backlinks {
         let (internal_links_list, internal_links_len) =
             convert_vec!(backlinks.internal_links, ftml_page_ref::from);
 
+        let (missing_links_list, missing_links_len) =
+            convert_vec!(backlinks.missing_links, ftml_page_ref::from);
+
         let (external_links_list, external_links_len) =
             convert_vec!(backlinks.external_links, cow_to_cstr);
 
@@ -63,6 +70,8 @@ impl From<Backlinks<'_>> for ftml_backlinks {
             included_pages_len,
             internal_links_list,
             internal_links_len,
+            missing_links_list,
+            missing_links_len,
             external_links_list,
             external_links_len,
         }
@@ -89,6 +98,15 @@ impl ftml_backlinks {
             },
         );
 
+        drop_cptr(
+            self.missing_links_list,
+            self.missing_links_len,
+            |page_ref| {
+                drop_cstr(page_ref.site);
+                drop_cstr(page_ref.page);
+            },
+        );
+
         drop_cptr(self.external_links_list, self.external_links_len, |s| {
             drop_cstr(s)
         });
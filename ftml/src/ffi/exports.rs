@@ -23,10 +23,12 @@ use super::page_info::ftml_page_info;
 use super::prelude::*;
 use super::settings::ftml_wikitext_settings;
 use super::text::ftml_text_output;
+use crate::data::PageInfo;
 use crate::parsing::ParseWarning;
 use crate::render::html::HtmlRender;
 use crate::render::text::TextRender;
 use crate::render::Render;
+use crate::settings::WikitextSettings;
 
 fn render<R: Render>(
     c_text: *const c_char,
@@ -38,6 +40,43 @@ fn render<R: Render>(
 
     // Convert data from C to Rust
     let mut text = unsafe { cstr_to_string(c_text) };
+    let (page_info, settings) = convert_context(c_page_info, c_settings);
+
+    // TODO includer
+
+    crate::preprocess(log, &mut text, &settings);
+    let tokens = crate::tokenize(log, &text);
+    let (tree, warnings) = crate::parse(log, &tokens, &page_info, &settings).into();
+    let output = renderer.render(log, &tree, &page_info, &settings);
+    (output, warnings)
+}
+
+fn render_stored<R: Render>(
+    c_data: *const u8,
+    c_data_len: usize,
+    c_page_info: *const ftml_page_info,
+    c_settings: *const ftml_wikitext_settings,
+    renderer: &R,
+) -> Option<R::Output> {
+    let log = &get_logger();
+
+    // Convert data from C to Rust
+    let data = unsafe { cptr_to_slice(c_data, c_data_len) };
+    let (page_info, settings) = convert_context(c_page_info, c_settings);
+
+    match crate::stored::load(log, data) {
+        Ok(tree) => Some(renderer.render(log, &tree, &page_info, &settings)),
+        Err(error) => {
+            warn!(log, "Unable to load stored syntax tree"; "error" => error.to_string());
+            None
+        }
+    }
+}
+
+fn convert_context(
+    c_page_info: *const ftml_page_info,
+    c_settings: *const ftml_wikitext_settings,
+) -> (PageInfo<'static>, WikitextSettings) {
     let page_info = unsafe {
         c_page_info
             .as_ref()
@@ -51,13 +90,7 @@ fn render<R: Render>(
             .to_wikitext_settings()
     };
 
-    // TODO includer
-
-    crate::preprocess(log, &mut text);
-    let tokens = crate::tokenize(log, &text);
-    let (tree, warnings) = crate::parse(log, &tokens, &page_info, &settings).into();
-    let output = renderer.render(log, &tree, &page_info, &settings);
-    (output, warnings)
+    (page_info, settings)
 }
 
 /// Runs the entire ftml rendering pipeline for HTML.
@@ -91,3 +124,59 @@ pub extern "C" fn ftml_render_text(
     let c_output = unsafe { &mut *output };
     c_output.write_from(rust_output, &rust_warnings);
 }
+
+/// Renders a stored syntax tree as HTML, without parsing.
+///
+/// The data is a syntax tree previously stored as either JSON
+/// or MessagePack, see the `stored` module for details.
+///
+/// The first argument must be a pointer to space where
+/// the output can be written to. It does not need to be initialized.
+///
+/// Returns false if the stored tree could not be loaded,
+/// in which case the output is not written to, and must not be freed.
+#[no_mangle]
+pub extern "C" fn ftml_render_stored_html(
+    output: *mut ftml_html_output,
+    data: *const u8,
+    data_len: usize,
+    page_info: *const ftml_page_info,
+    settings: *const ftml_wikitext_settings,
+) -> bool {
+    match render_stored(data, data_len, page_info, settings, &HtmlRender) {
+        Some(rust_output) => {
+            let c_output = unsafe { &mut *output };
+            c_output.write_from(rust_output, &[]);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Renders a stored syntax tree as text, without parsing.
+///
+/// The data is a syntax tree previously stored as either JSON
+/// or MessagePack, see the `stored` module for details.
+///
+/// The first argument must be a pointer to space where
+/// the output can be written to. It does not need to be initialized.
+///
+/// Returns false if the stored tree could not be loaded,
+/// in which case the output is not written to, and must not be freed.
+#[no_mangle]
+pub extern "C" fn ftml_render_stored_text(
+    output: *mut ftml_text_output,
+    data: *const u8,
+    data_len: usize,
+    page_info: *const ftml_page_info,
+    settings: *const ftml_wikitext_settings,
+) -> bool {
+    match render_stored(data, data_len, page_info, settings, &TextRender) {
+        Some(rust_output) => {
+            let c_output = unsafe { &mut *output };
+            c_output.write_from(rust_output, &[]);
+            true
+        }
+        None => false,
+    }
+}
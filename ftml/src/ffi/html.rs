@@ -77,6 +77,7 @@ pub struct ftml_html_output {
     pub styles_len: usize,
     pub meta_list: *mut ftml_html_meta,
     pub meta_len: usize,
+    pub json_ld: *mut c_char,
     pub warning_list: *mut ftml_warning,
     pub warning_len: usize,
     pub backlinks: ftml_backlinks,
@@ -96,6 +97,8 @@ impl ftml_html_output {
         self.meta_list = meta_ptr;
         self.meta_len = meta_len;
 
+        self.json_ld = string_to_cstr_null(output.json_ld);
+
         let c_warnings = warnings.iter().map(ftml_warning::from).collect();
         let (warning_ptr, warning_len) = vec_to_cptr(c_warnings);
         self.warning_list = warning_ptr;
@@ -117,5 +120,6 @@ pub unsafe extern "C" fn ftml_destroy_html_output(ptr: *mut ftml_html_output) {
         drop_cstr(item.name);
         drop_cstr(item.value);
     });
+    drop_cstr(this.json_ld);
     this.backlinks.drop_c();
 }
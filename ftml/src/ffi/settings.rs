@@ -18,7 +18,10 @@
  * along with this program. If not, see <http://www.gnu.org/licenses/>.
  */
 
-use crate::settings::{WikitextMode, WikitextSettings};
+use crate::settings::{
+    HtmlBlockSettings, HtmlSyntax, IframeSettings, PreprocessSettings, ResourceLimits,
+    TypographySettings, WikitextMode, WikitextSettings,
+};
 use std::ptr;
 
 #[repr(C)]
@@ -28,6 +31,8 @@ pub struct ftml_wikitext_settings {
     pub enable_page_syntax: bool,
     pub use_true_ids: bool,
     pub allow_local_paths: bool,
+    pub enable_section_edit: bool,
+    pub enable_json_ld: bool,
 }
 
 impl ftml_wikitext_settings {
@@ -37,6 +42,14 @@ impl ftml_wikitext_settings {
             enable_page_syntax: self.enable_page_syntax,
             use_true_ids: self.use_true_ids,
             allow_local_paths: self.allow_local_paths,
+            enable_section_edit: self.enable_section_edit,
+            enable_json_ld: self.enable_json_ld,
+            html_syntax: HtmlSyntax::default(),
+            preprocess: PreprocessSettings::default(),
+            typography: TypographySettings::default(),
+            html_block: HtmlBlockSettings::default(),
+            iframe: IframeSettings::default(),
+            limits: ResourceLimits::default(),
         }
     }
 }
@@ -48,6 +61,8 @@ impl From<WikitextSettings> for ftml_wikitext_settings {
             enable_page_syntax: settings.enable_page_syntax,
             use_true_ids: settings.use_true_ids,
             allow_local_paths: settings.allow_local_paths,
+            enable_section_edit: settings.enable_section_edit,
+            enable_json_ld: settings.enable_json_ld,
         }
     }
 }
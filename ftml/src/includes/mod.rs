@@ -34,7 +34,7 @@ mod parse;
 pub use self::include_ref::IncludeRef;
 pub use self::includer::{DebugIncluder, FetchedPage, Includer, NullIncluder};
 
-use self::parse::parse_include_block;
+pub(crate) use self::parse::parse_include_block;
 use crate::data::PageRef;
 use crate::log::prelude::*;
 use crate::settings::WikitextSettings;
@@ -108,6 +108,27 @@ where
         }
     }
 
+    // Enforce include limit
+    //
+    // The text is cut off after the first include past the limit,
+    // which is left unexpanded. The parser then reports it as
+    // a resource limit warning and stops there.
+    let mut output = String::from(input);
+    let max_includes = settings.limits.max_include_expansions;
+
+    if includes.len() > max_includes {
+        warn!(
+            log,
+            "Too many includes, cutting off text";
+            "includes-len" => includes.len(),
+            "max-includes" => max_includes,
+        );
+
+        output.truncate(ranges[max_includes].end);
+        ranges.truncate(max_includes);
+        includes.truncate(max_includes);
+    }
+
     // Retrieve included pages
     let fetched_pages = includer.include_pages(&includes)?;
 
@@ -129,7 +150,6 @@ where
     // Borrowing from the original text and doing in-place insertions
     // will not work here. We are trying to both return the page names
     // (slices from the input string), and replace it with new content.
-    let mut output = String::from(input);
     let mut pages = Vec::new();
 
     for ((range, include), fetched) in joined_iter {
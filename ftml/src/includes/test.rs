@@ -19,6 +19,8 @@
  */
 
 use super::{include, DebugIncluder, PageRef};
+use crate::data::PageInfo;
+use crate::parsing::ParseWarningKind;
 use crate::settings::{WikitextMode, WikitextSettings};
 
 #[test]
@@ -271,3 +273,44 @@ fn includes() {
         vec![],
     );
 }
+
+#[test]
+fn include_limit() {
+    let log = crate::build_logger();
+    let mut settings = WikitextSettings::from_mode(WikitextMode::Page);
+    settings.limits.max_include_expansions = 1;
+
+    let input = "A\n[[include-messy first]]\nB\n[[include-messy second]]\nC";
+    let (output, pages) = include(&log, input, &settings, DebugIncluder, || panic!())
+        .expect("Fetching pages failed");
+
+    assert_eq!(
+        output, "A\n<INCLUDED-PAGE first {}>\nB\n[[include-messy second]]",
+        "Text wasn't cut off after the include past the limit",
+    );
+    assert_eq!(
+        pages,
+        vec![PageRef::page_only("first")],
+        "Pages past the limit were included",
+    );
+
+    // The include left in place is reported when parsing
+    let mut text = output;
+    let page_info = PageInfo::dummy();
+    crate::preprocess(&log, &mut text, &settings);
+    let tokens = crate::tokenize(&log, &text);
+    let (_, warnings) = crate::parse(&log, &tokens, &page_info, &settings).into();
+
+    assert!(
+        warnings
+            .iter()
+            .any(|warning| warning.kind() == ParseWarningKind::TooManyIncludes),
+        "Include past the limit didn't produce a warning",
+    );
+    assert!(
+        warnings
+            .iter()
+            .all(|warning| warning.kind() != ParseWarningKind::InvalidInclude),
+        "Include past the limit was reported as invalid",
+    );
+}
@@ -109,6 +109,9 @@ extern crate maplit;
 #[macro_use]
 extern crate pest_derive;
 
+#[macro_use]
+extern crate schemars;
+
 #[macro_use]
 extern crate serde;
 
@@ -148,12 +151,21 @@ pub mod ffi;
 #[cfg(target_arch = "wasm32")]
 pub mod wasm;
 
+#[cfg(feature = "epub")]
+pub mod epub;
+
+pub mod batch;
 pub mod data;
+pub mod diagnostics;
 pub mod includes;
 pub mod info;
+pub mod lint;
 pub mod parsing;
 pub mod render;
+pub mod schema;
+pub mod sections;
 pub mod settings;
+pub mod stored;
 pub mod tokenizer;
 pub mod tree;
 
@@ -163,7 +175,7 @@ pub use self::log::{build_logger, build_null_logger, build_terminal_logger};
 
 pub use self::includes::include;
 pub use self::parsing::parse;
-pub use self::preproc::preprocess;
+pub use self::preproc::{preprocess, PreprocessChanges};
 pub use self::tokenizer::{tokenize, Tokenization};
 pub use self::utf16::Utf16IndexMap;
 
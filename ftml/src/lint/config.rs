@@ -0,0 +1,104 @@
+/*
+ * lint/config.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::LintRule;
+use crate::diagnostics::Severity;
+use std::collections::HashMap;
+
+/// What level a lint rule is configured to run at.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, Hash, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum LintLevel {
+    /// The rule does not run.
+    Off,
+    Info,
+    Warning,
+    Error,
+}
+
+impl LintLevel {
+    #[inline]
+    pub fn severity(self) -> Option<Severity> {
+        match self {
+            LintLevel::Off => None,
+            LintLevel::Info => Some(Severity::Info),
+            LintLevel::Warning => Some(Severity::Warning),
+            LintLevel::Error => Some(Severity::Error),
+        }
+    }
+}
+
+/// Per-site configuration for the linter.
+///
+/// This is intended to be stored as JSON (or similar) alongside the
+/// site's other settings, so every field has a default.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct LintConfig {
+    /// Overrides the level of individual rules, by rule name.
+    ///
+    /// Rules not listed here run at their default severity.
+    pub rules: HashMap<String, LintLevel>,
+
+    /// Block names which should be replaced with another.
+    ///
+    /// Maps the deprecated name to its replacement, e.g. `module654` to `module`.
+    pub deprecated_aliases: HashMap<String, String>,
+}
+
+impl LintConfig {
+    /// Gets the severity a rule should run with, or `None` if it is disabled.
+    pub fn severity(&self, rule: &LintRule) -> Option<Severity> {
+        match self.rules.get(rule.name) {
+            Some(level) => level.severity(),
+            None => Some(rule.default_severity),
+        }
+    }
+}
+
+impl Default for LintConfig {
+    fn default() -> Self {
+        LintConfig {
+            rules: HashMap::new(),
+            deprecated_aliases: hashmap! {
+                str!("module654") => str!("module"),
+            },
+        }
+    }
+}
+
+#[test]
+fn config() {
+    use super::LINT_RULES;
+
+    let rule = &LINT_RULES[0];
+    let mut config = LintConfig::default();
+    assert_eq!(config.severity(rule), Some(rule.default_severity));
+
+    config.rules.insert(str!(rule.name), LintLevel::Off);
+    assert_eq!(config.severity(rule), None);
+
+    let config: LintConfig =
+        serde_json::from_str(r#"{"rules": {"image-missing-alt": "error"}}"#)
+            .expect("Unable to parse lint config");
+
+    assert_eq!(config.rules["image-missing-alt"], LintLevel::Error);
+    assert_eq!(config.deprecated_aliases["module654"], "module");
+}
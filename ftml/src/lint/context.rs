@@ -0,0 +1,396 @@
+/*
+ * lint/context.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::LintConfig;
+use crate::parsing::{ExtractedToken, Token};
+use crate::tokenizer::Tokenization;
+use crate::tree::visit::{walk_container, walk_element, Visitor};
+use crate::tree::{Container, ContainerType, Element, SyntaxTree};
+use regex::Regex;
+use std::ops::Range;
+
+lazy_static! {
+    static ref ARGUMENT_REGEX: Regex =
+        Regex::new(r#"([A-Za-z0-9_\-]+)\s*=\s*"([^"]*)""#).unwrap();
+}
+
+/// Blocks whose bodies are not wikitext, and so should not be scanned.
+const RAW_BODY_BLOCKS: [&str; 4] = ["code", "css", "embed", "html"];
+
+/// The shared state all lint rules run against.
+#[derive(Debug)]
+pub struct LintContext<'r, 't> {
+    pub text: &'t str,
+    pub tokens: &'r [ExtractedToken<'t>],
+    pub config: &'r LintConfig,
+
+    /// All block heads and ends in the text, in order.
+    ///
+    /// These are found from the tokens, without regard to whether
+    /// the parser actually accepted them.
+    pub blocks: Vec<BlockHead<'t>>,
+
+    /// For each token, whether it is wikitext.
+    ///
+    /// Tokens within comments, raw text, and the bodies of blocks
+    /// like `[[code]]` are not, and should not be checked.
+    pub wikitext: Vec<bool>,
+
+    /// The headings the parser accepted, in order.
+    ///
+    /// Headings from included pages are not present, since
+    /// they don't appear in the text.
+    pub headings: Vec<LintHeading>,
+}
+
+impl<'r, 't> LintContext<'r, 't> {
+    pub fn new(
+        tokenization: &'r Tokenization<'t>,
+        tree: &'r SyntaxTree<'t>,
+        config: &'r LintConfig,
+    ) -> Self {
+        let text = tokenization.full_text().inner();
+        let tokens = tokenization.tokens();
+        let TokenScan { blocks, wikitext } = scan_tokens(text, tokens);
+        let headings = find_headings(tokens, &wikitext, tree);
+
+        LintContext {
+            text,
+            tokens,
+            config,
+            blocks,
+            wikitext,
+            headings,
+        }
+    }
+
+    /// Iterates over the tokens which are wikitext, with their indices.
+    pub fn wikitext_tokens(
+        &self,
+    ) -> impl Iterator<Item = (usize, &'r ExtractedToken<'t>)> + '_ {
+        self.tokens
+            .iter()
+            .enumerate()
+            .filter(move |&(index, _)| self.wikitext[index])
+    }
+
+    /// Finds the block end which closes the block head at the given index.
+    ///
+    /// Nested blocks with the same name are accounted for.
+    pub fn matching_end(&self, index: usize) -> Option<&BlockHead<'t>> {
+        let head = &self.blocks[index];
+        let mut depth = 0;
+
+        for block in &self.blocks[index + 1..] {
+            if !block.name.eq_ignore_ascii_case(head.name) {
+                continue;
+            }
+
+            if !block.end {
+                depth += 1;
+            } else if depth == 0 {
+                return Some(block);
+            } else {
+                depth -= 1;
+            }
+        }
+
+        None
+    }
+
+    /// Determines if the token at the given index begins a line.
+    pub fn at_line_start(&self, index: usize) -> bool {
+        match index.checked_sub(1).map(|i| self.tokens[i].token) {
+            None => true,
+            Some(Token::InputStart | Token::LineBreak | Token::ParagraphBreak) => true,
+            Some(_) => false,
+        }
+    }
+}
+
+/// A heading in the text which the parser accepted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintHeading {
+    /// The heading level, from 1 to 6.
+    pub level: usize,
+
+    /// The span of the `+` characters (and `*`, if present).
+    pub span: Range<usize>,
+}
+
+/// Finds headings in the text, keeping only those which appear in the syntax tree.
+///
+/// Heading lines are matched in order against the headings in the tree,
+/// so lines the parser didn't accept as headings are skipped.
+fn find_headings(
+    tokens: &[ExtractedToken],
+    wikitext: &[bool],
+    tree: &SyntaxTree,
+) -> Vec<LintHeading> {
+    let mut collector = HeadingCollector::default();
+    collector.visit_elements(&tree.elements);
+
+    let mut levels = collector.levels.into_iter().peekable();
+    let mut headings = Vec::new();
+
+    for (index, token) in tokens.iter().enumerate() {
+        // Same conditions as the parser, headings must be at the
+        // start of a line and followed by a space.
+        let at_line_start = matches!(
+            index.checked_sub(1).map(|i| tokens[i].token),
+            None | Some(Token::InputStart | Token::LineBreak | Token::ParagraphBreak),
+        );
+
+        if token.token != Token::Heading
+            || !wikitext[index]
+            || !at_line_start
+            || tokens.get(index + 1).map(|t| t.token) != Some(Token::Whitespace)
+        {
+            continue;
+        }
+
+        let level = token.slice.trim_end_matches('*').len();
+
+        if levels.peek() == Some(&level) {
+            levels.next();
+            headings.push(LintHeading {
+                level,
+                span: token.span.clone(),
+            });
+        }
+    }
+
+    headings
+}
+
+/// Gathers the levels of all headings in the tree, except within includes.
+#[derive(Debug, Default)]
+struct HeadingCollector {
+    levels: Vec<usize>,
+}
+
+impl<'a> Visitor<'a> for HeadingCollector {
+    fn visit_element(&mut self, element: &'a Element<'a>) {
+        if !matches!(element, Element::Include { .. }) {
+            walk_element(self, element);
+        }
+    }
+
+    fn visit_container(&mut self, container: &'a Container<'a>) {
+        if let ContainerType::Header(heading) = container.ctype() {
+            self.levels.push(usize::from(heading.level.value()));
+        }
+
+        walk_container(self, container);
+    }
+}
+
+/// A block head (`[[name ...]]`) or block end (`[[/name]]`) in the text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockHead<'t> {
+    /// The block name, as written.
+    pub name: &'t str,
+    pub name_span: Range<usize>,
+
+    /// The span of the whole head, from `[[` to `]]` inclusive.
+    pub span: Range<usize>,
+
+    /// Whether this is a block end, i.e. `[[/name]]`.
+    pub end: bool,
+
+    /// Whether this block was given the star flag, i.e. `[[*name]]`.
+    pub star: bool,
+
+    /// The raw text of the arguments, after the name.
+    pub arguments: &'t str,
+}
+
+impl<'t> BlockHead<'t> {
+    /// Determines if this block has the given name, case-insensitively.
+    #[inline]
+    pub fn is(&self, name: &str) -> bool {
+        self.name.eq_ignore_ascii_case(name)
+    }
+
+    /// Determines if this block's body is not wikitext, such as `[[code]]`.
+    #[inline]
+    pub fn has_raw_body(&self) -> bool {
+        RAW_BODY_BLOCKS.iter().any(|&name| self.is(name))
+    }
+
+    /// Gets the value of a `key="value"` argument.
+    pub fn argument(&self, key: &str) -> Option<&'t str> {
+        ARGUMENT_REGEX
+            .captures_iter(self.arguments)
+            .find(|captures| captures[1].eq_ignore_ascii_case(key))
+            .map(|captures| captures.get(2).unwrap().as_str())
+    }
+}
+
+/// The results of `scan_tokens()`.
+#[derive(Debug)]
+pub(crate) struct TokenScan<'t> {
+    /// All block heads and ends in the text, in order.
+    pub blocks: Vec<BlockHead<'t>>,
+
+    /// For each token, whether it is wikitext.
+    pub wikitext: Vec<bool>,
+}
+
+/// Finds all block heads, and which tokens are not wikitext.
+///
+/// Comments, raw text, and the bodies of blocks like `[[code]]` are skipped.
+pub(crate) fn scan_tokens<'t>(
+    text: &'t str,
+    tokens: &[ExtractedToken<'t>],
+) -> TokenScan<'t> {
+    let mut blocks = Vec::new();
+    let mut wikitext = vec![true; tokens.len()];
+    let mut raw_body: Option<&str> = None;
+    let mut has_heads = true;
+    let mut index = 0;
+
+    while index < tokens.len() {
+        let token = &tokens[index];
+        wikitext[index] = raw_body.is_none();
+        index += 1;
+
+        // Skip over text which isn't wikitext
+        let closing = match token.token {
+            Token::LeftComment => Some(Token::RightComment),
+            Token::LeftRaw => Some(Token::RightRaw),
+            Token::Raw => Some(Token::Raw),
+            _ => None,
+        };
+
+        if let Some(closing) = closing {
+            if raw_body.is_none() {
+                wikitext[index - 1] = false;
+
+                while index < tokens.len() && tokens[index].token != closing {
+                    wikitext[index] = false;
+                    index += 1;
+                }
+
+                if index < tokens.len() {
+                    wikitext[index] = false;
+                }
+
+                index += 1;
+            }
+
+            continue;
+        }
+
+        let (end, star) = match token.token {
+            Token::LeftBlock => (false, false),
+            Token::LeftBlockStar => (false, true),
+            Token::LeftBlockEnd => (true, false),
+            _ => continue,
+        };
+
+        if !has_heads {
+            continue;
+        }
+
+        // Find the end of this head
+        let close = match tokens[index..]
+            .iter()
+            .position(|token| token.token == Token::RightBlock)
+        {
+            Some(offset) => &tokens[index + offset],
+            None => {
+                // No more heads can be closed, but keep checking for comments
+                has_heads = false;
+                continue;
+            }
+        };
+
+        let inner = &text[token.span.end..close.span.start];
+        let leading = inner.len() - inner.trim_start().len();
+        let name_length = inner[leading..]
+            .find(|c: char| c.is_whitespace())
+            .unwrap_or(inner.len() - leading);
+
+        let name_start = token.span.end + leading;
+        let name_span = name_start..name_start + name_length;
+        let name = &text[name_span.clone()];
+        let block = BlockHead {
+            name,
+            name_span: name_span.clone(),
+            span: token.span.start..close.span.end,
+            end,
+            star,
+            arguments: &text[name_span.end..close.span.start],
+        };
+
+        // Within a raw body, only look for its end
+        match raw_body {
+            Some(body) if end && block.is(body) => {
+                raw_body = None;
+                wikitext[index - 1] = true;
+            }
+            Some(_) => continue,
+            None if !end && block.has_raw_body() => raw_body = Some(block.name),
+            None => (),
+        }
+
+        blocks.push(block);
+    }
+
+    TokenScan { blocks, wikitext }
+}
+
+#[test]
+fn blocks() {
+    let log = crate::build_logger();
+    let text = "[[div class=\"a\"]]\n[[*user aismallard]]\n[!-- [[span]] --]\n[[code]]\n[[collapsible]]\n[[/code]]\n[[/div]]";
+    let tokenization = crate::tokenize(&log, text);
+    let TokenScan { blocks, wikitext } = scan_tokens(text, tokenization.tokens());
+    let names: Vec<_> = blocks
+        .iter()
+        .map(|block| (block.name, block.end, block.star))
+        .collect();
+
+    assert_eq!(
+        names,
+        vec![
+            ("div", false, false),
+            ("user", false, true),
+            ("code", false, false),
+            ("code", true, false),
+            ("div", true, false),
+        ],
+    );
+
+    assert_eq!(blocks[0].argument("class"), Some("a"));
+    assert_eq!(blocks[0].argument("style"), None);
+    assert_eq!(&text[blocks[1].name_span.clone()], "user");
+
+    let skipped: String = tokenization
+        .tokens()
+        .iter()
+        .zip(wikitext)
+        .filter(|(_, wikitext)| !wikitext)
+        .map(|(token, _)| token.slice)
+        .collect();
+
+    assert_eq!(skipped, "[!-- [[span]] --]code]]\n[[collapsible]]\n");
+}
@@ -0,0 +1,143 @@
+/*
+ * lint/fix.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use std::ops::Range;
+
+/// An automatic fix for a lint, made up of one or more text edits.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct Fix {
+    /// A short description of what the fix does, e.g. "Replace with `[[div]]`".
+    pub message: String,
+    pub edits: Vec<TextEdit>,
+}
+
+impl Fix {
+    #[inline]
+    pub fn new<S: Into<String>>(message: S, edits: Vec<TextEdit>) -> Self {
+        Fix {
+            message: message.into(),
+            edits,
+        }
+    }
+}
+
+/// Replaces a span of the source text with new text.
+///
+/// An empty span is an insertion, and an empty replacement is a deletion.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct TextEdit {
+    pub span: Range<usize>,
+    pub replacement: String,
+}
+
+impl TextEdit {
+    #[inline]
+    pub fn new<S: Into<String>>(span: Range<usize>, replacement: S) -> Self {
+        TextEdit {
+            span,
+            replacement: replacement.into(),
+        }
+    }
+
+    #[inline]
+    pub fn insert<S: Into<String>>(index: usize, text: S) -> Self {
+        TextEdit::new(index..index, text)
+    }
+
+    #[inline]
+    pub fn delete(span: Range<usize>) -> Self {
+        TextEdit::new(span, "")
+    }
+}
+
+/// Applies the given fixes to the text, returning the fixed text.
+///
+/// Fixes are applied in order, and all edits within a fix are applied together.
+/// If any edit in a fix overlaps with an edit already applied, that whole fix
+/// is skipped, so running the linter again may find more to fix.
+///
+/// Returns the new text and the number of fixes applied.
+pub fn apply_fixes<'a, I>(text: &str, fixes: I) -> (String, usize)
+where
+    I: IntoIterator<Item = &'a Fix>,
+{
+    let mut edits: Vec<&TextEdit> = Vec::new();
+    let mut applied = 0;
+
+    let overlaps = |a: &Range<usize>, b: &Range<usize>| {
+        // Two insertions at the same point are ambiguous, so treat
+        // touching spans as overlapping if either is empty.
+        if a.start == a.end || b.start == b.end {
+            a.start <= b.end && b.start <= a.end
+        } else {
+            a.start < b.end && b.start < a.end
+        }
+    };
+
+    for fix in fixes {
+        let conflicts = fix.edits.iter().enumerate().any(|(i, edit)| {
+            edits.iter().any(|other| overlaps(&edit.span, &other.span))
+                || fix.edits[..i]
+                    .iter()
+                    .any(|other| overlaps(&edit.span, &other.span))
+        });
+
+        if conflicts {
+            continue;
+        }
+
+        edits.extend(&fix.edits);
+        applied += 1;
+    }
+
+    edits.sort_by_key(|edit| (edit.span.start, edit.span.end));
+
+    let mut output = String::with_capacity(text.len());
+    let mut last = 0;
+
+    for edit in edits {
+        output.push_str(&text[last..edit.span.start]);
+        output.push_str(&edit.replacement);
+        last = edit.span.end;
+    }
+
+    output.push_str(&text[last..]);
+    (output, applied)
+}
+
+#[test]
+fn apply() {
+    let text = "[[span style=\"display: block\"]]x[[/span]]";
+    let fixes = vec![
+        Fix::new(
+            "Rename",
+            vec![TextEdit::new(2..6, "div"), TextEdit::new(35..39, "div")],
+        ),
+        // Overlaps with the first fix, and so is skipped
+        Fix::new("Delete", vec![TextEdit::delete(0..10)]),
+        Fix::new("Append", vec![TextEdit::insert(text.len(), "\n")]),
+    ];
+
+    let (output, applied) = apply_fixes(text, &fixes);
+    assert_eq!(output, "[[div style=\"display: block\"]]x[[/div]]\n");
+    assert_eq!(applied, 2);
+}
@@ -0,0 +1,132 @@
+/*
+ * lint/mod.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Style checks for wikitext, with suggested fixes.
+//!
+//! Parser warnings only cover syntax that failed to parse. This module
+//! checks for syntax which parses fine, but is likely to be a mistake
+//! or is discouraged, such as images without alt text or headings which
+//! skip levels.
+//!
+//! Each check is a `LintRule`, all of which are listed in `LINT_RULES`.
+//! Rules are run over the tokens and the syntax tree, and produce `Lint`s,
+//! some of which carry a `Fix` that can be applied automatically with
+//! `apply_fixes()`. Which rules run, and at what severity, is controlled
+//! per site by `LintConfig`.
+
+mod config;
+mod context;
+mod fix;
+mod rule;
+mod rules;
+
+pub use self::config::{LintConfig, LintLevel};
+pub use self::context::{BlockHead, LintContext};
+pub use self::fix::{apply_fixes, Fix, TextEdit};
+pub use self::rule::LintRule;
+pub use self::rules::LINT_RULES;
+
+pub(crate) use self::context::{scan_tokens, TokenScan};
+
+use crate::diagnostics::Severity;
+use crate::log::prelude::*;
+use crate::tokenizer::Tokenization;
+use crate::tree::SyntaxTree;
+use crate::utf16::Utf16IndexMap;
+use std::ops::Range;
+
+/// An issue found by a lint rule.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct Lint {
+    /// The name of the rule which produced this lint.
+    pub rule: &'static str,
+    pub severity: Severity,
+    pub message: String,
+
+    /// The UTF-8 byte span of the wikitext this lint refers to.
+    pub span: Range<usize>,
+
+    /// An automatic fix for this issue, if one is known.
+    pub fix: Option<Fix>,
+}
+
+impl Lint {
+    /// Converts the span and fix edits to use UTF-16 indices.
+    #[must_use]
+    pub fn to_utf16_indices(&self, map: &Utf16IndexMap) -> Self {
+        let convert =
+            |span: &Range<usize>| map.get_index(span.start)..map.get_index(span.end);
+
+        Lint {
+            rule: self.rule,
+            severity: self.severity,
+            message: self.message.clone(),
+            span: convert(&self.span),
+            fix: self.fix.as_ref().map(|fix| Fix {
+                message: fix.message.clone(),
+                edits: fix
+                    .edits
+                    .iter()
+                    .map(|edit| TextEdit {
+                        span: convert(&edit.span),
+                        replacement: edit.replacement.clone(),
+                    })
+                    .collect(),
+            }),
+        }
+    }
+}
+
+/// Runs all enabled lint rules over the given wikitext.
+///
+/// The tokens and syntax tree must come from the same (preprocessed) text.
+/// Lints are returned ordered by their position in the text.
+pub fn lint(
+    log: &Logger,
+    tokenization: &Tokenization,
+    tree: &SyntaxTree,
+    config: &LintConfig,
+) -> Vec<Lint> {
+    let ctx = LintContext::new(tokenization, tree, config);
+    let mut lints = Vec::new();
+
+    for rule in &LINT_RULES {
+        let severity = match config.severity(rule) {
+            Some(severity) => severity,
+            None => {
+                debug!(log, "Skipping disabled lint rule"; "rule" => rule.name);
+                continue;
+            }
+        };
+
+        info!(log, "Running lint rule"; "rule" => rule.name);
+
+        let start = lints.len();
+        (rule.check_fn)(&ctx, &mut lints);
+
+        for lint in &mut lints[start..] {
+            lint.severity = severity;
+        }
+    }
+
+    lints.sort_by_key(|lint| lint.span.start);
+    lints
+}
@@ -0,0 +1,69 @@
+/*
+ * lint/rule.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::{Lint, LintContext};
+use crate::diagnostics::Severity;
+use std::fmt::{self, Debug};
+use std::ops::Range;
+
+/// Defines a lint rule, which checks wikitext for a particular issue.
+#[derive(Copy, Clone)]
+pub struct LintRule {
+    /// The name of this rule, as used in `LintConfig`.
+    ///
+    /// By convention this is in kebab-case.
+    pub name: &'static str,
+
+    /// A short description of what this rule checks for.
+    pub description: &'static str,
+
+    /// The severity of lints from this rule, unless configured otherwise.
+    pub default_severity: Severity,
+
+    /// The function to run this rule, which appends any lints it finds.
+    ///
+    /// The severity of the lints it produces is overwritten afterwards
+    /// with whatever is configured for this rule.
+    pub check_fn: fn(&LintContext, &mut Vec<Lint>),
+}
+
+impl LintRule {
+    /// Creates a new lint from this rule, with no fix.
+    pub fn lint<S: Into<String>>(&self, span: Range<usize>, message: S) -> Lint {
+        Lint {
+            rule: self.name,
+            severity: self.default_severity,
+            message: message.into(),
+            span,
+            fix: None,
+        }
+    }
+}
+
+impl Debug for LintRule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("LintRule")
+            .field("name", &self.name)
+            .field("description", &self.description)
+            .field("default_severity", &self.default_severity)
+            .field("check_fn", &(self.check_fn as *const ()))
+            .finish()
+    }
+}
@@ -0,0 +1,53 @@
+/*
+ * lint/rules/deprecated_alias.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::prelude::*;
+
+pub const RULE_DEPRECATED_ALIAS: LintRule = LintRule {
+    name: "deprecated-block-alias",
+    description: "Block names which have been replaced by another name",
+    default_severity: Severity::Warning,
+    check_fn,
+};
+
+fn check_fn(ctx: &LintContext, lints: &mut Vec<Lint>) {
+    for block in &ctx.blocks {
+        let name = block.name.to_ascii_lowercase();
+        let replacement = match ctx.config.deprecated_aliases.get(&name) {
+            Some(replacement) => replacement,
+            None => continue,
+        };
+
+        let mut lint = RULE_DEPRECATED_ALIAS.lint(
+            block.span.clone(),
+            format!(
+                "[[{}]] is deprecated, use [[{}]] instead",
+                name, replacement
+            ),
+        );
+
+        lint.fix = Some(Fix::new(
+            format!("Rename to [[{}]]", replacement),
+            vec![TextEdit::new(block.name_span.clone(), replacement.as_str())],
+        ));
+
+        lints.push(lint);
+    }
+}
@@ -0,0 +1,84 @@
+/*
+ * lint/rules/empty_link.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::prelude::*;
+
+pub const RULE_EMPTY_LINK: LintRule = LintRule {
+    name: "empty-link",
+    description: "Links with no target or with an empty label",
+    default_severity: Severity::Warning,
+    check_fn,
+};
+
+fn check_fn(ctx: &LintContext, lints: &mut Vec<Lint>) {
+    let tokens = ctx.tokens;
+
+    for (index, token) in ctx.wikitext_tokens() {
+        if !matches!(token.token, Token::LeftLink | Token::LeftLinkStar) {
+            continue;
+        }
+
+        // Find the end of the link, which must be on the same line
+        let mut pipe = None;
+        let mut close = None;
+
+        for (offset, next) in tokens[index + 1..].iter().enumerate() {
+            match next.token {
+                Token::Pipe if pipe.is_none() => pipe = Some(index + 1 + offset),
+                Token::RightLink => {
+                    close = Some(next);
+                    break;
+                }
+                Token::LineBreak | Token::ParagraphBreak | Token::InputEnd => break,
+                _ => (),
+            }
+        }
+
+        let close = match close {
+            Some(close) => close,
+            None => continue,
+        };
+
+        let span = token.span.start..close.span.end;
+        let target_end = pipe.map(|pipe| tokens[pipe].span.start);
+        let target = &ctx.text[token.span.end..target_end.unwrap_or(close.span.start)];
+
+        if target.trim().is_empty() {
+            let mut lint = RULE_EMPTY_LINK.lint(span.clone(), "Link has no target");
+            lint.fix = Some(Fix::new("Remove link", vec![TextEdit::delete(span)]));
+            lints.push(lint);
+            continue;
+        }
+
+        if let Some(pipe) = pipe {
+            let pipe_start = tokens[pipe].span.start;
+            let label = &ctx.text[tokens[pipe].span.end..close.span.start];
+
+            if label.trim().is_empty() {
+                let mut lint = RULE_EMPTY_LINK.lint(span, "Link has an empty label");
+                lint.fix = Some(Fix::new(
+                    "Use the page name as the label",
+                    vec![TextEdit::delete(pipe_start..close.span.start)],
+                ));
+                lints.push(lint);
+            }
+        }
+    }
+}
@@ -0,0 +1,60 @@
+/*
+ * lint/rules/heading_level.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::prelude::*;
+
+pub const RULE_HEADING_LEVEL: LintRule = LintRule {
+    name: "heading-level-skip",
+    description: "Headings which are more than one level below the previous heading",
+    default_severity: Severity::Info,
+    check_fn,
+};
+
+fn check_fn(ctx: &LintContext, lints: &mut Vec<Lint>) {
+    let mut previous = None;
+
+    // Only headings the parser accepted, and not those from included pages
+    for heading in &ctx.headings {
+        let level = heading.level;
+
+        if let Some(previous) = previous {
+            if level > previous + 1 {
+                let expected = previous + 1;
+                let start = heading.span.start;
+                let mut lint = RULE_HEADING_LEVEL.lint(
+                    heading.span.clone(),
+                    format!(
+                        "Heading level {} follows level {}, skipping a level",
+                        level, previous,
+                    ),
+                );
+
+                lint.fix = Some(Fix::new(
+                    format!("Change to level {}", expected),
+                    vec![TextEdit::new(start..start + level, "+".repeat(expected))],
+                ));
+
+                lints.push(lint);
+            }
+        }
+
+        previous = Some(level);
+    }
+}
@@ -0,0 +1,48 @@
+/*
+ * lint/rules/image_alt.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::prelude::*;
+
+pub const RULE_IMAGE_ALT: LintRule = LintRule {
+    name: "image-missing-alt",
+    description: "Images without alternative text",
+    default_severity: Severity::Warning,
+    check_fn,
+};
+
+/// The names an image block can have, with alignment prefixes.
+const IMAGE_NAMES: [&str; 6] =
+    ["image", "=image", "<image", ">image", "f<image", "f>image"];
+
+fn check_fn(ctx: &LintContext, lints: &mut Vec<Lint>) {
+    for block in &ctx.blocks {
+        if block.end || !IMAGE_NAMES.iter().any(|name| block.is(name)) {
+            continue;
+        }
+
+        match block.argument("alt") {
+            Some(alt) if !alt.trim().is_empty() => (),
+            _ => lints.push(RULE_IMAGE_ALT.lint(
+                block.span.clone(),
+                "Image has no alt text, add an alt=\"...\" argument describing it",
+            )),
+        }
+    }
+}
@@ -0,0 +1,57 @@
+/*
+ * lint/rules/mod.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+mod prelude {
+    pub use super::super::{Fix, Lint, LintContext, LintRule, TextEdit};
+    pub use crate::diagnostics::Severity;
+    pub use crate::parsing::Token;
+}
+
+mod deprecated_alias;
+mod empty_link;
+mod heading_level;
+mod image_alt;
+mod raw_html;
+mod span_style;
+mod unclosed_collapsible;
+
+use super::LintRule;
+
+pub use self::deprecated_alias::RULE_DEPRECATED_ALIAS;
+pub use self::empty_link::RULE_EMPTY_LINK;
+pub use self::heading_level::RULE_HEADING_LEVEL;
+pub use self::image_alt::RULE_IMAGE_ALT;
+pub use self::raw_html::RULE_RAW_HTML;
+pub use self::span_style::RULE_SPAN_STYLE;
+pub use self::unclosed_collapsible::RULE_UNCLOSED_COLLAPSIBLE;
+
+/// All lint rules, in the order they are run.
+pub const LINT_RULES: [LintRule; 7] = [
+    RULE_DEPRECATED_ALIAS,
+    RULE_SPAN_STYLE,
+    RULE_UNCLOSED_COLLAPSIBLE,
+    RULE_EMPTY_LINK,
+    RULE_IMAGE_ALT,
+    RULE_HEADING_LEVEL,
+    RULE_RAW_HTML,
+];
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,39 @@
+/*
+ * lint/rules/raw_html.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::prelude::*;
+
+pub const RULE_RAW_HTML: LintRule = LintRule {
+    name: "raw-html",
+    description: "Uses of the [[html]] block",
+    default_severity: Severity::Info,
+    check_fn,
+};
+
+fn check_fn(ctx: &LintContext, lints: &mut Vec<Lint>) {
+    for block in &ctx.blocks {
+        if !block.end && block.is("html") {
+            lints.push(RULE_RAW_HTML.lint(
+                block.span.clone(),
+                "[[html]] is rendered in a separate frame, prefer wikitext where possible",
+            ));
+        }
+    }
+}
@@ -0,0 +1,68 @@
+/*
+ * lint/rules/span_style.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::prelude::*;
+use regex::Regex;
+
+lazy_static! {
+    static ref BLOCK_STYLE_REGEX: Regex =
+        Regex::new(r"(?i)(^|;)\s*(display\s*:\s*(block|flex|grid|table)|float\s*:)")
+            .unwrap();
+}
+
+pub const RULE_SPAN_STYLE: LintRule = LintRule {
+    name: "span-block-style",
+    description: "Spans which are styled to behave as block elements",
+    default_severity: Severity::Warning,
+    check_fn,
+};
+
+fn check_fn(ctx: &LintContext, lints: &mut Vec<Lint>) {
+    for (index, block) in ctx.blocks.iter().enumerate() {
+        if block.end || !block.is("span") {
+            continue;
+        }
+
+        match block.argument("style") {
+            Some(style) if BLOCK_STYLE_REGEX.is_match(style) => (),
+            _ => continue,
+        }
+
+        let mut lint = RULE_SPAN_STYLE.lint(
+            block.span.clone(),
+            "[[span]] is styled as a block element, use [[div]] instead",
+        );
+
+        // Only suggest the rename if the span is actually closed.
+        //
+        // Note [[div]] must begin on its own line, which this fix does not ensure.
+        if let Some(end) = ctx.matching_end(index) {
+            lint.fix = Some(Fix::new(
+                "Replace with [[div]]",
+                vec![
+                    TextEdit::new(block.name_span.clone(), "div"),
+                    TextEdit::new(end.name_span.clone(), "div"),
+                ],
+            ));
+        }
+
+        lints.push(lint);
+    }
+}
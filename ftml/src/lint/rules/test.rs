@@ -0,0 +1,162 @@
+/*
+ * lint/rules/test.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use crate::data::PageInfo;
+use crate::lint::{apply_fixes, lint, Lint, LintConfig};
+use crate::settings::{WikitextMode, WikitextSettings};
+
+fn run(text: &str) -> Vec<Lint> {
+    let log = crate::build_logger();
+    let tokenization = crate::tokenize(&log, text);
+    let page_info = PageInfo::dummy();
+    let settings = WikitextSettings::from_mode(WikitextMode::Page);
+    let (tree, _warnings) =
+        crate::parse(&log, &tokenization, &page_info, &settings).into();
+
+    lint(&log, &tokenization, &tree, &LintConfig::default())
+}
+
+/// Checks which rules are triggered by the text,
+/// and what the text is after applying all fixes.
+fn check(text: &str, expected_rules: &[&str], expected_fixed: &str) {
+    let lints = run(text);
+    let rules: Vec<_> = lints.iter().map(|lint| lint.rule).collect();
+    let (fixed, _) = apply_fixes(text, lints.iter().filter_map(|lint| lint.fix.as_ref()));
+
+    println!("Lints: {:#?}", lints);
+    assert_eq!(rules, expected_rules, "Lint rules don't match expected");
+    assert_eq!(fixed, expected_fixed, "Fixed text doesn't match expected");
+}
+
+#[test]
+fn deprecated_alias() {
+    check(
+        "[[module654 Rate]]",
+        &["deprecated-block-alias"],
+        "[[module Rate]]",
+    );
+}
+
+#[test]
+fn span_style() {
+    check(
+        "[[span style=\"display: block; color: red\"]]text[[/span]]",
+        &["span-block-style"],
+        "[[div style=\"display: block; color: red\"]]text[[/div]]",
+    );
+
+    check(
+        "[[span style=\"color: red\"]]text[[/span]]",
+        &[],
+        "[[span style=\"color: red\"]]text[[/span]]",
+    );
+}
+
+#[test]
+fn unclosed_collapsible() {
+    check(
+        "[[collapsible]]\nHidden!",
+        &["unclosed-collapsible"],
+        "[[collapsible]]\nHidden!\n[[/collapsible]]",
+    );
+
+    check(
+        "[[collapsible]]\nHidden!\n[[/collapsible]]",
+        &[],
+        "[[collapsible]]\nHidden!\n[[/collapsible]]",
+    );
+}
+
+#[test]
+fn empty_link() {
+    check("See [[[ ]]] here", &["empty-link"], "See  here");
+    check("[[[some-page|]]]", &["empty-link"], "[[[some-page]]]");
+    check("[[[some-page|Label]]]", &[], "[[[some-page|Label]]]");
+
+    // Not wikitext
+    check(
+        "[[code]]\n[[[ ]]]\n[[/code]]",
+        &[],
+        "[[code]]\n[[[ ]]]\n[[/code]]",
+    );
+    check("[!-- [[[ ]]] --]", &[], "[!-- [[[ ]]] --]");
+    check("@@[[[ ]]]@@", &[], "@@[[[ ]]]@@");
+}
+
+#[test]
+fn image_alt() {
+    check("[[image a.png]]", &["image-missing-alt"], "[[image a.png]]");
+    check(
+        "[[=image a.png alt=\"\"]]",
+        &["image-missing-alt"],
+        "[[=image a.png alt=\"\"]]",
+    );
+    check(
+        "[[image a.png alt=\"A cat\"]]",
+        &[],
+        "[[image a.png alt=\"A cat\"]]",
+    );
+}
+
+#[test]
+fn heading_level() {
+    check(
+        "+ Title\n\n+++ Section\n\n++++ Subsection",
+        &["heading-level-skip"],
+        "+ Title\n\n++ Section\n\n++++ Subsection",
+    );
+    check("++ A\n\n+++ B\n\n+ C", &[], "++ A\n\n+++ B\n\n+ C");
+
+    // Not wikitext
+    check(
+        "+ A\n\n[[code]]\n+++ B\n[[/code]]\n\n[[html]]\n+++ C\n[[/html]]",
+        &["raw-html"],
+        "+ A\n\n[[code]]\n+++ B\n[[/code]]\n\n[[html]]\n+++ C\n[[/html]]",
+    );
+    check(
+        "+ A\n\n[!--\n+++ B\n--]\n\n@@\n+++ C@@",
+        &[],
+        "+ A\n\n[!--\n+++ B\n--]\n\n@@\n+++ C@@",
+    );
+}
+
+#[test]
+fn raw_html() {
+    check(
+        "[[html]]\n<b>[[span style=\"display: block\"]]</b>\n[[/html]]",
+        &["raw-html"],
+        "[[html]]\n<b>[[span style=\"display: block\"]]</b>\n[[/html]]",
+    );
+}
+
+#[test]
+fn disabled() {
+    let log = crate::build_logger();
+    let text = "[[html]]\n<b></b>\n[[/html]]";
+    let tokenization = crate::tokenize(&log, text);
+    let page_info = PageInfo::dummy();
+    let settings = WikitextSettings::from_mode(WikitextMode::Page);
+    let (tree, _warnings) =
+        crate::parse(&log, &tokenization, &page_info, &settings).into();
+    let config: LintConfig = serde_json::from_str(r#"{"rules": {"raw-html": "off"}}"#)
+        .expect("Unable to parse lint config");
+
+    assert!(lint(&log, &tokenization, &tree, &config).is_empty());
+}
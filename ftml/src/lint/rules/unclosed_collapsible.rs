@@ -0,0 +1,64 @@
+/*
+ * lint/rules/unclosed_collapsible.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::prelude::*;
+
+pub const RULE_UNCLOSED_COLLAPSIBLE: LintRule = LintRule {
+    name: "unclosed-collapsible",
+    description: "Collapsible blocks which are never closed",
+    default_severity: Severity::Error,
+    check_fn,
+};
+
+fn check_fn(ctx: &LintContext, lints: &mut Vec<Lint>) {
+    let mut open = Vec::new();
+
+    for block in &ctx.blocks {
+        if !block.is("collapsible") {
+            continue;
+        }
+
+        if block.end {
+            open.pop();
+        } else {
+            open.push(block);
+        }
+    }
+
+    // Close the innermost first, since all fixes insert at the same place,
+    // only one can be applied at a time.
+    for block in open.into_iter().rev() {
+        let mut lint = RULE_UNCLOSED_COLLAPSIBLE
+            .lint(block.span.clone(), "[[collapsible]] is never closed");
+
+        let closing = if ctx.text.ends_with('\n') {
+            "[[/collapsible]]\n"
+        } else {
+            "\n[[/collapsible]]"
+        };
+
+        lint.fix = Some(Fix::new(
+            "Close at the end of the page",
+            vec![TextEdit::insert(ctx.text.len(), closing)],
+        ));
+
+        lints.push(lint);
+    }
+}
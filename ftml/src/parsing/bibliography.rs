@@ -0,0 +1,106 @@
+/*
+ * parsing/bibliography.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::ParseWarning;
+use crate::tree::{Bibliography, BibliographyList};
+use std::borrow::Cow;
+use std::collections::HashSet;
+
+/// Bibliographies and citations gathered while parsing.
+///
+/// Citations can come before or after the bibliography they refer to,
+/// so they are only checked against each other once parsing is finished.
+///
+/// Each pending warning is built when its entry or citation is parsed,
+/// so that it points to the right place in the source.
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct BibliographyState<'t> {
+    bibliographies: BibliographyList<'t>,
+    entry_warnings: Vec<ParseWarning>,
+    citations: Vec<(Cow<'t, str>, ParseWarning)>,
+}
+
+impl<'t> BibliographyState<'t> {
+    /// Adds a bibliography, returning its index.
+    ///
+    /// There is one warning for each entry, which is used if it is never cited.
+    pub fn push_bibliography(
+        &mut self,
+        bibliography: Bibliography<'t>,
+        mut warnings: Vec<ParseWarning>,
+    ) -> usize {
+        assert_eq!(
+            bibliography.entries.len(),
+            warnings.len(),
+            "Number of bibliography entries and warnings do not match",
+        );
+
+        self.entry_warnings.append(&mut warnings);
+        self.bibliographies.push(bibliography)
+    }
+
+    /// Adds a citation of the given label.
+    ///
+    /// The warning is used if the label is not in any bibliography.
+    pub fn push_citation(&mut self, label: Cow<'t, str>, warning: ParseWarning) {
+        self.citations.push((label, warning));
+    }
+
+    /// Counts citations for each entry, and produces warnings
+    /// for citations with no entry and entries with no citations.
+    pub fn finish(self) -> (BibliographyList<'t>, Vec<ParseWarning>) {
+        let BibliographyState {
+            mut bibliographies,
+            entry_warnings,
+            citations,
+        } = self;
+
+        let mut warnings = Vec::new();
+
+        for (label, warning) in citations {
+            let entry = bibliographies
+                .iter_mut()
+                .flat_map(|bibliography| bibliography.entries.iter_mut())
+                .find(|entry| entry.label == label);
+
+            match entry {
+                Some(entry) => entry.citations += 1,
+                None => warnings.push(warning),
+            }
+        }
+
+        // Only the first entry with a label can be cited,
+        // so the others are unused as well.
+        let mut seen = HashSet::new();
+        let entries = bibliographies
+            .iter()
+            .flat_map(|bibliography| bibliography.entries.iter());
+
+        for (entry, warning) in entries.zip(entry_warnings) {
+            let first = seen.insert(entry.label.as_ref());
+
+            if !first || entry.citations == 0 {
+                warnings.push(warning);
+            }
+        }
+
+        (bibliographies, warnings)
+    }
+}
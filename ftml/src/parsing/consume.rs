@@ -26,6 +26,7 @@
 //! The parser is not disambiguous because any string of tokens can be interpreted
 //! as raw text as a fallback, which is how Wikidot does it.
 
+use super::limits::Resource;
 use super::prelude::*;
 use super::rule::{get_rules_for_token, impls::RULE_FALLBACK};
 use super::Parser;
@@ -82,6 +83,9 @@ pub fn consume<'p, 'r, 't>(
                 // Decrement recursion depth
                 parser.depth_decrement();
 
+                // Count produced elements against the limit
+                parser.add_usage(Resource::Elements, output.item.len());
+
                 return Ok(output);
             }
             Err(warning) => {
@@ -122,5 +126,8 @@ pub fn consume<'p, 'r, 't>(
     // Decrement recursion depth
     parser.depth_decrement();
 
+    // Count produced elements against the limit
+    parser.add_usage(Resource::Elements, 1);
+
     ok!(element, all_exceptions)
 }
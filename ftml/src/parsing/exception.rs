@@ -22,7 +22,7 @@ use super::{rule::Rule, ExtractedToken, Token};
 use crate::utf16::Utf16IndexMap;
 use std::borrow::Cow;
 use std::ops::Range;
-use strum_macros::IntoStaticStr;
+use strum_macros::{EnumIter, IntoStaticStr};
 
 /// Exceptions that occurred during parsing
 ///
@@ -34,7 +34,7 @@ use strum_macros::IntoStaticStr;
 /// For instance, CSS styles are not present in the syntax tree
 /// like regular elements, and instead must be bubbled up
 /// to the top level.
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq, Eq)]
 pub enum ParseException<'t> {
     Warning(ParseWarning),
     Style(Cow<'t, str>),
@@ -47,7 +47,7 @@ pub enum ParseException<'t> {
 ///
 /// However, as outlined by the crate's philosophy, no parsing issue is fatal.
 /// Instead a fallback rules is applied and parsing continues.
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
 pub struct ParseWarning {
     token: Token,
@@ -123,7 +123,18 @@ impl ParseWarning {
     }
 }
 
-#[derive(Serialize, Deserialize, IntoStaticStr, Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    EnumIter,
+    IntoStaticStr,
+    Debug,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+)]
 #[serde(rename_all = "kebab-case")]
 pub enum ParseWarningKind {
     /// The self-enforced recursion limit has been passed, giving up.
@@ -183,6 +194,15 @@ pub enum ParseWarningKind {
     /// Footnotes are not permitted from inside footnotes.
     FootnotesNested,
 
+    /// This bibliography has elements other than `: label : citation` entries in it.
+    BibliographyContainsNonEntry,
+
+    /// This citation refers to a label which is not in any bibliography.
+    NoSuchBibliographyLabel,
+
+    /// This bibliography entry is never cited.
+    UnusedBibliographyLabel,
+
     /// This native blockquote tries to nest too deeply.
     BlockquoteDepthExceeded,
 
@@ -230,6 +250,27 @@ pub enum ParseWarningKind {
 
     /// The URL passed here was invalid.
     InvalidUrl,
+
+    /// This iframe's URL is not permitted by the site's iframe policy.
+    IframeNotPermitted,
+
+    /// The input is longer than the limit, the rest was not parsed.
+    InputTooLong,
+
+    /// Too many elements were produced, the rest of the input was not parsed.
+    TooManyElements,
+
+    /// Too many table cells were produced, the rest of the input was not parsed.
+    TooManyTableCells,
+
+    /// Too many pages were included, the rest of the input was not parsed.
+    TooManyIncludes,
+
+    /// The CSS is larger than the limit, the rest of the input was not parsed.
+    CssTooLarge,
+
+    /// The parser took too many steps, the rest of the input was not parsed.
+    StepLimitExceeded,
 }
 
 impl ParseWarningKind {
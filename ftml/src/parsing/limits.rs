@@ -0,0 +1,122 @@
+/*
+ * parsing/limits.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Tracking of resource usage while parsing, see `ResourceLimits`.
+//!
+//! Once any limit is exceeded, the parser skips to the end of the input,
+//! so rules unwind and whatever was parsed up to that point is returned.
+
+use super::prelude::*;
+use crate::settings::ResourceLimits;
+use std::cell::{Cell, RefCell};
+
+/// Which resource is being counted against its limit.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Resource {
+    Steps,
+    Elements,
+    TableCells,
+    Includes,
+    CssSize,
+}
+
+impl Resource {
+    fn limit(self, limits: &ResourceLimits) -> usize {
+        match self {
+            Resource::Steps => limits.max_steps,
+            Resource::Elements => limits.max_elements,
+            Resource::TableCells => limits.max_table_cells,
+            Resource::Includes => limits.max_include_expansions,
+            Resource::CssSize => limits.max_css_size,
+        }
+    }
+
+    pub fn warning_kind(self) -> ParseWarningKind {
+        match self {
+            Resource::Steps => ParseWarningKind::StepLimitExceeded,
+            Resource::Elements => ParseWarningKind::TooManyElements,
+            Resource::TableCells => ParseWarningKind::TooManyTableCells,
+            Resource::Includes => ParseWarningKind::TooManyIncludes,
+            Resource::CssSize => ParseWarningKind::CssTooLarge,
+        }
+    }
+}
+
+/// Counts of the resources used so far.
+///
+/// This is shared between all the parser instances for one parse,
+/// so work done by rules which later failed is still counted.
+#[derive(Debug, Default)]
+pub struct ResourceUsage {
+    steps: Cell<usize>,
+    elements: Cell<usize>,
+    table_cells: Cell<usize>,
+    includes: Cell<usize>,
+    css_size: Cell<usize>,
+    exceeded: RefCell<Option<ParseWarning>>,
+}
+
+impl ResourceUsage {
+    fn counter(&self, resource: Resource) -> &Cell<usize> {
+        match resource {
+            Resource::Steps => &self.steps,
+            Resource::Elements => &self.elements,
+            Resource::TableCells => &self.table_cells,
+            Resource::Includes => &self.includes,
+            Resource::CssSize => &self.css_size,
+        }
+    }
+
+    /// Adds to the count for this resource.
+    ///
+    /// Returns `true` if this puts it over the limit.
+    pub fn add(
+        &self,
+        limits: &ResourceLimits,
+        resource: Resource,
+        amount: usize,
+    ) -> bool {
+        let counter = self.counter(resource);
+        let count = counter.get().saturating_add(amount);
+        counter.set(count);
+        count > resource.limit(limits)
+    }
+
+    #[inline]
+    pub fn is_exceeded(&self) -> bool {
+        self.exceeded.borrow().is_some()
+    }
+
+    /// Records the warning for the limit which was exceeded.
+    ///
+    /// Only the first limit to be hit is recorded.
+    pub fn set_exceeded(&self, warning: ParseWarning) {
+        let mut exceeded = self.exceeded.borrow_mut();
+
+        if exceeded.is_none() {
+            *exceeded = Some(warning);
+        }
+    }
+
+    #[cold]
+    pub fn take_exceeded(&self) -> Option<ParseWarning> {
+        self.exceeded.borrow_mut().take()
+    }
+}
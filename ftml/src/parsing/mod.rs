@@ -21,6 +21,7 @@
 #[macro_use]
 mod macros;
 
+mod bibliography;
 mod boolean;
 mod check_step;
 mod collect;
@@ -29,6 +30,7 @@ mod consume;
 mod depth;
 mod element_condition;
 mod exception;
+mod limits;
 mod outcome;
 mod paragraph;
 mod parser;
@@ -50,6 +52,7 @@ mod prelude {
     pub use crate::tree::{Element, Elements, OwnedElementsIterator};
 }
 
+use self::bibliography::BibliographyState;
 use self::depth::{process_depths, DepthItem, DepthList};
 use self::element_condition::{ElementCondition, ElementConditionType};
 use self::paragraph::{gather_paragraphs, NO_CLOSE_CONDITION};
@@ -64,7 +67,8 @@ use crate::next_index::{NextIndex, TableOfContentsIndex};
 use crate::settings::WikitextSettings;
 use crate::tokenizer::Tokenization;
 use crate::tree::{
-    AttributeMap, Element, LinkLabel, LinkLocation, ListItem, ListType, SyntaxTree,
+    AttributeMap, BibliographyList, Element, LinkLabel, LinkLocation, ListItem, ListType,
+    SyntaxTree,
 };
 use std::borrow::Cow;
 
@@ -92,6 +96,7 @@ where
         table_of_contents_depths,
         footnotes,
         has_footnote_block,
+        bibliographies,
     } = parse_internal(log, page_info, settings, tokenization);
 
     // For producing table of contents indexes
@@ -104,7 +109,11 @@ where
             exceptions,
             ..
         }) => {
-            let (warnings, styles) = extract_exceptions(exceptions);
+            let (mut warnings, styles) = extract_exceptions(exceptions);
+
+            // Match citations to their bibliography entries
+            let (bibliographies, mut bibliography_warnings) = bibliographies.finish();
+            warnings.append(&mut bibliography_warnings);
 
             info!(
                 log,
@@ -141,6 +150,7 @@ where
                 styles,
                 table_of_contents,
                 footnotes,
+                bibliographies,
             )
         }
         Err(warning) => {
@@ -161,6 +171,7 @@ where
             let styles = vec![];
             let table_of_contents = vec![];
             let footnotes = vec![];
+            let bibliographies = BibliographyList::new();
 
             SyntaxTree::from_element_result(
                 elements,
@@ -168,6 +179,7 @@ where
                 styles,
                 table_of_contents,
                 footnotes,
+                bibliographies,
             )
         }
     }
@@ -195,18 +207,29 @@ where
 
     // At the top level, we gather elements into paragraphs
     info!(log, "Running parser on tokens");
-    let result = gather_paragraphs(log, &mut parser, RULE_PAGE, NO_CLOSE_CONDITION);
+    let mut result = gather_paragraphs(log, &mut parser, RULE_PAGE, NO_CLOSE_CONDITION);
+
+    // If a resource limit was hit, add its warning
+    if let Some(warning) = parser.remove_limit_warning() {
+        warn!(log, "Parsing stopped early, resource limit exceeded");
+
+        if let Ok(ref mut success) = result {
+            success.exceptions.push(ParseException::Warning(warning));
+        }
+    }
 
     // Build and return
     let table_of_contents_depths = parser.remove_table_of_contents();
     let footnotes = parser.remove_footnotes();
     let has_footnote_block = parser.has_footnote_block();
+    let bibliographies = parser.remove_bibliographies();
 
     UnstructuredParseResult {
         result,
         table_of_contents_depths,
         footnotes,
         has_footnote_block,
+        bibliographies,
     }
 }
 
@@ -294,4 +317,7 @@ pub struct UnstructuredParseResult<'r, 't> {
 
     /// Whether a footnote block was placed during parsing.
     pub has_footnote_block: bool,
+
+    /// The bibliographies and citations, which have not yet been matched up.
+    pub bibliographies: BibliographyState<'t>,
 }
@@ -18,7 +18,9 @@
  * along with this program. If not, see <http://www.gnu.org/licenses/>.
  */
 
+use super::bibliography::BibliographyState;
 use super::condition::ParseCondition;
+use super::limits::{Resource, ResourceUsage};
 use super::prelude::*;
 use super::rule::Rule;
 use super::RULE_PAGE;
@@ -26,7 +28,8 @@ use crate::data::PageInfo;
 use crate::log::prelude::*;
 use crate::render::text::TextRender;
 use crate::tokenizer::Tokenization;
-use crate::tree::{AcceptsPartial, HeadingLevel};
+use crate::tree::{AcceptsPartial, Bibliography, HeadingLevel};
+use std::borrow::Cow;
 use std::cell::RefCell;
 use std::rc::Rc;
 use std::{mem, ptr};
@@ -66,6 +69,23 @@ pub struct Parser<'r, 't> {
     // Schema: Vec<List of elements in a footnote>
     footnotes: Rc<RefCell<Vec<Vec<Element<'t>>>>>,
 
+    // Bibliographies
+    //
+    // Schema: Bibliography blocks and citations, checked once parsing is done
+    bibliographies: Rc<RefCell<BibliographyState<'t>>>,
+
+    // Definition list keys
+    //
+    // Schema: Vec<Token spanning the key of a definition list item>
+    //
+    // Used to point warnings for bibliography entries at their labels.
+    definition_list_keys: Rc<RefCell<Vec<ExtractedToken<'t>>>>,
+
+    // Resource usage
+    //
+    // Counts checked against the limits in settings
+    usage: Rc<ResourceUsage>,
+
     // Flags
     accepts_partial: AcceptsPartial,
     in_footnote: bool, // Whether we're currently inside [[footnote]] ... [[/footnote]].
@@ -102,6 +122,9 @@ impl<'r, 't> Parser<'r, 't> {
             depth: 0,
             table_of_contents: make_shared_vec(),
             footnotes: make_shared_vec(),
+            bibliographies: Rc::new(RefCell::new(BibliographyState::default())),
+            definition_list_keys: make_shared_vec(),
+            usage: Rc::new(ResourceUsage::default()),
             accepts_partial: AcceptsPartial::None,
             in_footnote: false,
             has_footnote_block: false,
@@ -210,6 +233,47 @@ impl<'r, 't> Parser<'r, 't> {
         }
     }
 
+    // Resource limits
+    /// Adds to the usage of a resource, stopping the parse if it's over the limit.
+    pub fn add_usage(&mut self, resource: Resource, amount: usize) {
+        if self.usage.add(&self.settings.limits, resource, amount) {
+            self.stop_parsing(resource.warning_kind());
+        }
+    }
+
+    /// Records which limit was exceeded, and skips to the end of the input.
+    ///
+    /// The rest of the tokens are not parsed, so that all the rules
+    /// currently running unwind with what they have so far.
+    #[cold]
+    pub fn stop_parsing(&mut self, kind: ParseWarningKind) {
+        warn!(
+            self.log,
+            "Resource limit exceeded, skipping to end of input";
+            "warning" => kind.name(),
+        );
+
+        self.usage.set_exceeded(self.make_warn(kind));
+        self.skip_to_end();
+    }
+
+    fn skip_to_end(&mut self) {
+        if let Some(last) = self.remaining.last() {
+            self.current = last;
+            self.remaining = &self.remaining[self.remaining.len()..];
+        }
+    }
+
+    #[inline]
+    pub fn limit_exceeded(&self) -> bool {
+        self.usage.is_exceeded()
+    }
+
+    #[cold]
+    pub fn remove_limit_warning(&mut self) -> Option<ParseWarning> {
+        self.usage.take_exceeded()
+    }
+
     // Table of Contents
     pub fn push_table_of_contents_entry(
         &mut self,
@@ -245,6 +309,47 @@ impl<'r, 't> Parser<'r, 't> {
         mem::take(&mut self.footnotes.borrow_mut())
     }
 
+    // Bibliographies
+    pub fn push_bibliography(
+        &mut self,
+        bibliography: Bibliography<'t>,
+        warnings: Vec<ParseWarning>,
+    ) -> usize {
+        self.bibliographies
+            .borrow_mut()
+            .push_bibliography(bibliography, warnings)
+    }
+
+    pub fn push_bibliography_citation(&mut self, label: Cow<'t, str>) {
+        let warning = self.make_warn(ParseWarningKind::NoSuchBibliographyLabel);
+
+        self.bibliographies
+            .borrow_mut()
+            .push_citation(label, warning);
+    }
+
+    #[cold]
+    pub fn remove_bibliographies(&mut self) -> BibliographyState<'t> {
+        mem::take(&mut self.bibliographies.borrow_mut())
+    }
+
+    // Definition list keys
+    pub fn push_definition_list_keys(&mut self, keys: Vec<ExtractedToken<'t>>) {
+        self.definition_list_keys.borrow_mut().extend(keys);
+    }
+
+    #[inline]
+    pub fn definition_list_key_count(&self) -> usize {
+        self.definition_list_keys.borrow().len()
+    }
+
+    pub fn remove_definition_list_keys(
+        &mut self,
+        start: usize,
+    ) -> Vec<ExtractedToken<'t>> {
+        self.definition_list_keys.borrow_mut().split_off(start)
+    }
+
     // Special for [[include]], appending a SyntaxTree
     pub fn append_toc_and_footnotes(
         &mut self,
@@ -391,12 +496,25 @@ impl<'r, 't> Parser<'r, 't> {
             Token::InputStart | Token::LineBreak | Token::ParagraphBreak,
         );
 
+        // If a resource limit was hit, then the rest of the input is skipped.
+        if self.usage.is_exceeded() && !self.remaining.is_empty() {
+            self.skip_to_end();
+            return Ok(self.current);
+        }
+
         // Step to the next token.
         match self.remaining.split_first() {
             Some((current, remaining)) => {
                 self.current = current;
                 self.remaining = remaining;
-                Ok(current)
+
+                if current.span.end > self.settings.limits.max_input_length {
+                    self.stop_parsing(ParseWarningKind::InputTooLong);
+                } else {
+                    self.add_usage(Resource::Steps, 1);
+                }
+
+                Ok(self.current)
             }
             None => {
                 warn!(
@@ -0,0 +1,54 @@
+/*
+ * parsing/rule/impls/bibcite.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::prelude::*;
+use regex::Regex;
+
+lazy_static! {
+    static ref BIBCITE_REGEX: Regex =
+        Regex::new(r"^\(\(bibcite\s+(\S+)\s*\)\)$").unwrap();
+}
+
+pub const RULE_BIBCITE: Rule = Rule {
+    name: "bibcite",
+    position: LineRequirement::Any,
+    try_consume_fn,
+};
+
+fn try_consume_fn<'r, 't>(
+    log: &Logger,
+    parser: &mut Parser<'r, 't>,
+) -> ParseResult<'r, 't, Elements<'t>> {
+    info!(log, "Consuming token to create bibliography citation");
+
+    let ExtractedToken { slice, .. } = parser.current();
+
+    let label = BIBCITE_REGEX
+        .captures(slice)
+        .expect("Bibcite regex didn't match")
+        .get(1)
+        .expect("Capture group not found")
+        .as_str();
+
+    // Checked against the bibliographies once parsing is finished
+    parser.push_bibliography_citation(cow!(label));
+
+    ok!(Element::BibliographyCite { label: cow!(label) })
+}
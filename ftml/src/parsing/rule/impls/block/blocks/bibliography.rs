@@ -0,0 +1,131 @@
+/*
+ * parsing/rule/impls/block/blocks/bibliography.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::prelude::*;
+use crate::render::text::TextRender;
+use crate::tree::{Bibliography, BibliographyEntry};
+use std::borrow::Cow;
+
+pub const BLOCK_BIBLIOGRAPHY: BlockRule = BlockRule {
+    name: "block-bibliography",
+    accepts_names: &["bibliography"],
+    accepts_star: false,
+    accepts_score: false,
+    accepts_newlines: true,
+    parse_fn,
+};
+
+fn parse_fn<'r, 't>(
+    log: &Logger,
+    parser: &mut Parser<'r, 't>,
+    name: &'t str,
+    flag_star: bool,
+    flag_score: bool,
+    in_head: bool,
+) -> ParseResult<'r, 't, Elements<'t>> {
+    info!(
+        log,
+        "Parsing bibliography block";
+        "in-head" => in_head,
+    );
+
+    assert!(!flag_star, "Bibliography doesn't allow star flag");
+    assert!(!flag_score, "Bibliography doesn't allow score flag");
+    assert_block_name(&BLOCK_BIBLIOGRAPHY, name);
+
+    // Parse arguments
+    let mut arguments = parser.get_head_map(&BLOCK_BIBLIOGRAPHY, in_head)?;
+
+    let title = arguments.get("title");
+    let hide = arguments.get_bool(parser, "hide")?.unwrap_or(false);
+
+    if !arguments.is_empty() {
+        warn!(
+            log,
+            "Invalid argument keys found";
+            "arguments" => format!("{:#?}", arguments),
+        );
+
+        return Err(parser.make_warn(ParseWarningKind::BlockMalformedArguments));
+    }
+
+    // Entries use definition list syntax, ": label : citation"
+    let key_start = parser.definition_list_key_count();
+    let (elements, exceptions, _) =
+        parser.get_body_elements(&BLOCK_BIBLIOGRAPHY, false)?.into();
+
+    let mut keys = parser.remove_definition_list_keys(key_start).into_iter();
+    let mut entries = Vec::new();
+    let mut warnings = Vec::new();
+
+    for element in elements {
+        match element {
+            // Each item in the list is an entry.
+            Element::DefinitionList(items) => {
+                for item in items {
+                    let label = TextRender.render_partial(
+                        log,
+                        &item.key,
+                        parser.page_info(),
+                        parser.settings(),
+                    );
+
+                    let label = label.trim();
+
+                    // Warning in case this entry is never cited,
+                    // pointing to its label where it was parsed.
+                    let warning = match keys.next() {
+                        Some(token) => ParseWarning::new(
+                            ParseWarningKind::UnusedBibliographyLabel,
+                            parser.rule(),
+                            &token,
+                        ),
+                        None => {
+                            parser.make_warn(ParseWarningKind::UnusedBibliographyLabel)
+                        }
+                    };
+
+                    warnings.push(warning);
+                    entries.push(BibliographyEntry {
+                        label: Cow::Owned(str!(label)),
+                        elements: item.value,
+                        citations: 0,
+                    });
+                }
+            }
+
+            // Ignore internal whitespace.
+            element if element.is_whitespace() => (),
+
+            // Return a warning for anything else.
+            _ => {
+                return Err(
+                    parser.make_warn(ParseWarningKind::BibliographyContainsNonEntry)
+                )
+            }
+        }
+    }
+
+    // Save entries and return
+    let index = parser.push_bibliography(Bibliography { entries }, warnings);
+    let element = Element::BibliographyBlock { index, title, hide };
+
+    ok!(false; element, exceptions)
+}
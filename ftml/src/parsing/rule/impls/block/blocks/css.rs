@@ -46,6 +46,14 @@ fn parse_fn<'r, 't>(
     parser.get_head_none(&BLOCK_CSS, in_head)?;
 
     let css = parser.get_body_text(&BLOCK_CSS)?;
-    let exceptions = vec![ParseException::Style(cow!(css))];
+    parser.add_usage(Resource::CssSize, css.len());
+
+    // Styles past the size limit are dropped
+    let exceptions = if parser.limit_exceeded() {
+        vec![]
+    } else {
+        vec![ParseException::Style(cow!(css))]
+    };
+
     ok!(Elements::None, exceptions)
 }
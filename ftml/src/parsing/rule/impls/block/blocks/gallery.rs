@@ -0,0 +1,123 @@
+/*
+ * parsing/rule/impls/block/blocks/gallery.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::prelude::*;
+use crate::parsing::parse_string;
+use crate::tree::{GalleryImage, GallerySize, ImageSource, LinkLocation};
+use regex::Regex;
+use std::convert::TryFrom;
+
+lazy_static! {
+    static ref GALLERY_ITEM: Regex = Regex::new(
+        r#"^:\s*(\S+)((?:\s+[A-Za-z0-9_\-]+\s*=\s*"(?:[^"\\]|\\["'\\rnt])*")*)\s*$"#
+    )
+    .unwrap();
+    static ref GALLERY_ARGUMENT: Regex =
+        Regex::new(r#"([A-Za-z0-9_\-]+)\s*=\s*("(?:[^"\\]|\\["'\\rnt])*")"#).unwrap();
+}
+
+pub const BLOCK_GALLERY: BlockRule = BlockRule {
+    name: "block-gallery",
+    accepts_names: &["gallery"],
+    accepts_star: false,
+    accepts_score: false,
+    accepts_newlines: true,
+    parse_fn,
+};
+
+fn parse_fn<'r, 't>(
+    log: &Logger,
+    parser: &mut Parser<'r, 't>,
+    name: &'t str,
+    flag_star: bool,
+    flag_score: bool,
+    in_head: bool,
+) -> ParseResult<'r, 't, Elements<'t>> {
+    info!(log, "Parsing gallery block"; "in-head" => in_head);
+
+    assert!(!flag_star, "Gallery doesn't allow star flag");
+    assert!(!flag_score, "Gallery doesn't allow score flag");
+    assert_block_name(&BLOCK_GALLERY, name);
+
+    let mut arguments = parser.get_head_map(&BLOCK_GALLERY, in_head)?;
+    let size = match arguments.get("size") {
+        Some(size) => match GallerySize::try_from(size.as_ref()) {
+            Ok(size) => size,
+            Err(_) => {
+                return Err(parser.make_warn(ParseWarningKind::BlockMalformedArguments))
+            }
+        },
+        None => GallerySize::default(),
+    };
+
+    // Each non-empty line in the body is one image, in the form:
+    // : source key="value" key="value" ...
+    let body = parser.get_body_text(&BLOCK_GALLERY)?;
+    let mut images = Vec::new();
+
+    for line in body.lines() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        match parse_image(line) {
+            Some(image) => images.push(image),
+            None => {
+                return Err(parser.make_warn(ParseWarningKind::BlockMalformedArguments))
+            }
+        }
+    }
+
+    let element = Element::Gallery {
+        images,
+        size,
+        attributes: arguments.to_attribute_map(),
+    };
+
+    ok!(element)
+}
+
+fn parse_image(line: &str) -> Option<GalleryImage<'_>> {
+    let captures = GALLERY_ITEM.captures(line)?;
+    let source = ImageSource::parse(captures.get(1)?.as_str())?;
+
+    let mut arguments = Arguments::new();
+    if let Some(mtch) = captures.get(2) {
+        for captures in GALLERY_ARGUMENT.captures_iter(mtch.as_str()) {
+            let key = captures.get(1)?.as_str();
+            let value = parse_string(captures.get(2)?.as_str());
+
+            arguments.insert(key, value);
+        }
+    }
+
+    let title = arguments.get("title");
+    let caption = arguments.get("caption");
+    let link = arguments.get("link").map(LinkLocation::parse);
+
+    Some(GalleryImage {
+        source,
+        title,
+        caption,
+        link,
+        attributes: arguments.to_attribute_map(),
+    })
+}
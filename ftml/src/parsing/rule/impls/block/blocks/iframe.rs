@@ -44,10 +44,20 @@ fn parse_fn<'r, 't>(
     assert_block_name(&BLOCK_IFRAME, name);
 
     let (url, arguments) = parser.get_head_name_map(&BLOCK_IFRAME, in_head)?;
+
+    // Non-fatal, the renderer replaces the frame with a fallback.
+    let mut exceptions = Vec::new();
+    if !parser.settings().iframe.permits_url(url) {
+        warn!(log, "Iframe URL not permitted by site policy"; "url" => url);
+
+        let warning = parser.make_warn(ParseWarningKind::IframeNotPermitted);
+        exceptions.push(ParseException::Warning(warning));
+    }
+
     let element = Element::Iframe {
         url: cow!(url),
         attributes: arguments.to_attribute_map(),
     };
 
-    ok!(element)
+    ok!(element, exceptions)
 }
@@ -20,6 +20,7 @@
 
 use super::prelude::*;
 use crate::data::PageRef;
+use crate::parsing::bibliography::BibliographyState;
 use crate::parsing::UnstructuredParseResult;
 
 /// Block rule for include (elements).
@@ -59,12 +60,20 @@ fn parse_fn<'r, 't>(
         Err(_) => return Err(parser.make_warn(ParseWarningKind::BlockMalformedArguments)),
     };
 
+    // Count against the include limit
+    parser.add_usage(Resource::Includes, 1);
+
+    if parser.limit_exceeded() {
+        return ok!(Elements::None);
+    }
+
     // Get page to be included
     let UnstructuredParseResult {
         result,
         mut table_of_contents_depths,
         mut footnotes,
         has_footnote_block,
+        bibliographies: _, // TODO merge once include_page() is implemented
     } = include_page(parser, &page_ref)?;
 
     if has_footnote_block {
@@ -112,5 +121,6 @@ fn include_page<'r, 't>(
         table_of_contents_depths: vec![],
         footnotes: vec![],
         has_footnote_block: false,
+        bibliographies: BibliographyState::default(),
     })
 }
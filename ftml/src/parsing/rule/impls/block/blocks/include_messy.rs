@@ -19,6 +19,7 @@
  */
 
 use super::prelude::*;
+use crate::includes::parse_include_block;
 
 /// Psuedo block rule for include (messy).
 ///
@@ -26,9 +27,10 @@ use super::prelude::*;
 /// tokenizing, or any other steps, no `[[include-messy]]` blocks
 /// should actually be present in the wikitext.
 ///
-/// If they are, this indicates that an error occurred parsing
-/// them. As such, we return a particular warning instead of
-/// interpreting the block.
+/// If they are, this indicates either that the include was past the
+/// include limit, in which case the text was cut off right after it,
+/// or that an error occurred parsing them. As such, we return a
+/// particular warning instead of interpreting the block.
 pub const BLOCK_INCLUDE_MESSY: BlockRule = BlockRule {
     name: "block-include-messy",
     accepts_names: &["include-messy"],
@@ -55,6 +57,18 @@ fn parse_fn<'r, 't>(
 
     // Includes are handled specially, so we should never actually be
     // parsing a block here. So, we return a warning.
+    //
+    // If the block is a valid include, then it was left in place
+    // because it was past the include limit.
+    let text = parser.full_text().inner();
+    let end = parser.current().span.start;
+
+    if let Some(start) = text[..end].rfind("[[") {
+        if parse_include_block(log, &text[start..], start).is_ok() {
+            parser.stop_parsing(ParseWarningKind::TooManyIncludes);
+            return ok!(Elements::None);
+        }
+    }
 
     Err(parser.make_warn(ParseWarningKind::InvalidInclude))
 }
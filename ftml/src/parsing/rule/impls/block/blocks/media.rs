@@ -0,0 +1,161 @@
+/*
+ * parsing/rule/impls/block/blocks/media.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::prelude::*;
+use crate::tree::{ImageSource, Media, MediaType};
+use std::borrow::Cow;
+
+// Definitions
+
+pub const BLOCK_AUDIO: BlockRule = BlockRule {
+    name: "block-audio",
+    accepts_names: &["audio"],
+    accepts_star: false,
+    accepts_score: false,
+    accepts_newlines: false,
+    parse_fn: parse_audio,
+};
+
+pub const BLOCK_VIDEO: BlockRule = BlockRule {
+    name: "block-video",
+    accepts_names: &["video"],
+    accepts_star: false,
+    accepts_score: false,
+    accepts_newlines: false,
+    parse_fn: parse_video,
+};
+
+fn parse_audio<'r, 't>(
+    log: &Logger,
+    parser: &mut Parser<'r, 't>,
+    name: &'t str,
+    flag_star: bool,
+    flag_score: bool,
+    in_head: bool,
+) -> ParseResult<'r, 't, Elements<'t>> {
+    parse_media(
+        (&BLOCK_AUDIO, MediaType::Audio),
+        log,
+        parser,
+        name,
+        flag_star,
+        flag_score,
+        in_head,
+    )
+}
+
+fn parse_video<'r, 't>(
+    log: &Logger,
+    parser: &mut Parser<'r, 't>,
+    name: &'t str,
+    flag_star: bool,
+    flag_score: bool,
+    in_head: bool,
+) -> ParseResult<'r, 't, Elements<'t>> {
+    parse_media(
+        (&BLOCK_VIDEO, MediaType::Video),
+        log,
+        parser,
+        name,
+        flag_star,
+        flag_score,
+        in_head,
+    )
+}
+
+// Media block
+
+fn parse_media<'r, 't>(
+    (block_rule, mtype): (&BlockRule, MediaType),
+    log: &Logger,
+    parser: &mut Parser<'r, 't>,
+    name: &'t str,
+    flag_star: bool,
+    flag_score: bool,
+    in_head: bool,
+) -> ParseResult<'r, 't, Elements<'t>> {
+    info!(
+        log,
+        "Parsing media block";
+        "block-rule" => block_rule.name,
+        "media-type" => mtype.name(),
+        "in-head" => in_head,
+        "name" => name,
+    );
+
+    assert!(!flag_star, "Media block doesn't allow star flag");
+    assert!(!flag_score, "Media block doesn't allow score flag");
+    assert_block_name(block_rule, name);
+
+    let (source, mut arguments) = parser.get_head_name_map(block_rule, in_head)?;
+
+    let controls = arguments.get_bool(parser, "controls")?.unwrap_or(true);
+    let looped = arguments.get_bool(parser, "loop")?.unwrap_or(false);
+    let autoplay = arguments.get_bool(parser, "autoplay")?.unwrap_or(false);
+
+    // Parse sources, using the same format as images
+    let parse_source = |source| match parse_source_argument(source) {
+        Some(source) => Ok(source),
+        None => Err(parser.make_warn(ParseWarningKind::BlockMalformedArguments)),
+    };
+
+    let source = parse_source(cow!(source))?;
+
+    // Audio has no frame to show a poster in
+    let poster = match (mtype, arguments.get("poster")) {
+        (_, None) => None,
+        (MediaType::Video, Some(poster)) => Some(parse_source(poster)?),
+        (MediaType::Audio, Some(_)) => {
+            return Err(parser.make_warn(ParseWarningKind::BlockMalformedArguments))
+        }
+    };
+
+    // Captions must be a WebVTT file
+    let captions = match arguments.get("captions") {
+        Some(captions) if captions.to_ascii_lowercase().ends_with(".vtt") => {
+            Some(parse_source(captions)?)
+        }
+        Some(_) => {
+            return Err(parser.make_warn(ParseWarningKind::BlockMalformedArguments))
+        }
+        None => None,
+    };
+
+    // Build media element
+    let element = Element::Media(Box::new(Media {
+        mtype,
+        source,
+        poster,
+        captions,
+        controls,
+        looped,
+        autoplay,
+        attributes: arguments.to_attribute_map(),
+    }));
+
+    ok!(element)
+}
+
+fn parse_source_argument(source: Cow<str>) -> Option<ImageSource> {
+    match source {
+        Cow::Borrowed(source) => ImageSource::parse(source),
+        Cow::Owned(source) => ImageSource::parse(&source).map(|source| source.to_owned()),
+    }
+}
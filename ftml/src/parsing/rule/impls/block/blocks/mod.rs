@@ -23,6 +23,7 @@ mod prelude {
     pub use crate::log::prelude::*;
     pub use crate::parsing::collect::*;
     pub use crate::parsing::condition::ParseCondition;
+    pub use crate::parsing::limits::Resource;
     pub use crate::parsing::parser::Parser;
     pub use crate::parsing::prelude::*;
     pub use crate::parsing::{ParseWarning, Token};
@@ -64,6 +65,7 @@ mod align_justify;
 mod align_left;
 mod align_right;
 mod anchor;
+mod bibliography;
 mod blockquote;
 mod bold;
 mod char;
@@ -77,6 +79,7 @@ mod div;
 mod embed;
 mod equation_ref;
 mod footnote;
+mod gallery;
 mod hidden;
 mod html;
 mod ifcategory;
@@ -93,10 +96,12 @@ mod lines;
 mod list;
 mod mark;
 mod math;
+mod media;
 mod module;
 mod monospace;
 mod paragraph;
 mod radio;
+mod ruby;
 mod size;
 mod span;
 mod strikethrough;
@@ -113,6 +118,7 @@ pub use self::align_justify::BLOCK_ALIGN_JUSTIFY;
 pub use self::align_left::BLOCK_ALIGN_LEFT;
 pub use self::align_right::BLOCK_ALIGN_RIGHT;
 pub use self::anchor::BLOCK_ANCHOR;
+pub use self::bibliography::BLOCK_BIBLIOGRAPHY;
 pub use self::blockquote::BLOCK_BLOCKQUOTE;
 pub use self::bold::BLOCK_BOLD;
 pub use self::char::BLOCK_CHAR;
@@ -126,6 +132,7 @@ pub use self::div::BLOCK_DIV;
 pub use self::embed::BLOCK_EMBED;
 pub use self::equation_ref::BLOCK_EQUATION_REF;
 pub use self::footnote::{BLOCK_FOOTNOTE, BLOCK_FOOTNOTE_BLOCK};
+pub use self::gallery::BLOCK_GALLERY;
 pub use self::hidden::BLOCK_HIDDEN;
 pub use self::html::BLOCK_HTML;
 pub use self::ifcategory::BLOCK_IFCATEGORY;
@@ -142,10 +149,12 @@ pub use self::lines::BLOCK_LINES;
 pub use self::list::{BLOCK_LI, BLOCK_OL, BLOCK_UL};
 pub use self::mark::BLOCK_MARK;
 pub use self::math::BLOCK_MATH;
+pub use self::media::{BLOCK_AUDIO, BLOCK_VIDEO};
 pub use self::module::BLOCK_MODULE;
 pub use self::monospace::BLOCK_MONOSPACE;
 pub use self::paragraph::BLOCK_PARAGRAPH;
 pub use self::radio::BLOCK_RADIO;
+pub use self::ruby::{BLOCK_RB, BLOCK_RT, BLOCK_RUBY, BLOCK_RUBY_SHORT};
 pub use self::size::BLOCK_SIZE;
 pub use self::span::BLOCK_SPAN;
 pub use self::strikethrough::BLOCK_STRIKETHROUGH;
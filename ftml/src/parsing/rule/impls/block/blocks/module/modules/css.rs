@@ -36,7 +36,14 @@ fn parse_fn<'r, 't>(
     assert_module_name(&MODULE_CSS, name);
 
     let css = parser.get_body_text(&BLOCK_MODULE)?;
-    let exceptions = vec![ParseException::Style(cow!(css))];
+    parser.add_usage(Resource::CssSize, css.len());
+
+    // Styles past the size limit are dropped
+    let exceptions = if parser.limit_exceeded() {
+        vec![]
+    } else {
+        vec![ParseException::Style(cow!(css))]
+    };
 
     ok!(true; None, exceptions)
 }
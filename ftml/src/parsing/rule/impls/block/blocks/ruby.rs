@@ -0,0 +1,218 @@
+/*
+ * parsing/rule/impls/block/blocks/ruby.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::prelude::*;
+use crate::tree::AttributeMap;
+
+// Definitions
+
+pub const BLOCK_RUBY: BlockRule = BlockRule {
+    name: "block-ruby",
+    accepts_names: &["ruby"],
+    accepts_star: false,
+    accepts_score: false,
+    accepts_newlines: false,
+    parse_fn: parse_ruby,
+};
+
+pub const BLOCK_RB: BlockRule = BlockRule {
+    name: "block-ruby-base",
+    accepts_names: &["rb", "rubybase"],
+    accepts_star: false,
+    accepts_score: false,
+    accepts_newlines: false,
+    parse_fn: parse_ruby_base,
+};
+
+pub const BLOCK_RT: BlockRule = BlockRule {
+    name: "block-ruby-text",
+    accepts_names: &["rt", "rubytext"],
+    accepts_star: false,
+    accepts_score: false,
+    accepts_newlines: false,
+    parse_fn: parse_ruby_text,
+};
+
+pub const BLOCK_RUBY_SHORT: BlockRule = BlockRule {
+    name: "block-ruby-short",
+    accepts_names: &["rubyshort"],
+    accepts_star: false,
+    accepts_score: false,
+    accepts_newlines: false,
+    parse_fn: parse_ruby_short,
+};
+
+fn parse_ruby<'r, 't>(
+    log: &Logger,
+    parser: &mut Parser<'r, 't>,
+    name: &'t str,
+    flag_star: bool,
+    flag_score: bool,
+    in_head: bool,
+) -> ParseResult<'r, 't, Elements<'t>> {
+    parse_ruby_container(
+        (&BLOCK_RUBY, ContainerType::Ruby),
+        log,
+        parser,
+        name,
+        flag_star,
+        flag_score,
+        in_head,
+    )
+}
+
+fn parse_ruby_base<'r, 't>(
+    log: &Logger,
+    parser: &mut Parser<'r, 't>,
+    name: &'t str,
+    flag_star: bool,
+    flag_score: bool,
+    in_head: bool,
+) -> ParseResult<'r, 't, Elements<'t>> {
+    parse_ruby_container(
+        (&BLOCK_RB, ContainerType::RubyBase),
+        log,
+        parser,
+        name,
+        flag_star,
+        flag_score,
+        in_head,
+    )
+}
+
+fn parse_ruby_text<'r, 't>(
+    log: &Logger,
+    parser: &mut Parser<'r, 't>,
+    name: &'t str,
+    flag_star: bool,
+    flag_score: bool,
+    in_head: bool,
+) -> ParseResult<'r, 't, Elements<'t>> {
+    parse_ruby_container(
+        (&BLOCK_RT, ContainerType::RubyText),
+        log,
+        parser,
+        name,
+        flag_star,
+        flag_score,
+        in_head,
+    )
+}
+
+// Ruby blocks
+
+fn parse_ruby_container<'r, 't>(
+    (block_rule, container_type): (&BlockRule, ContainerType),
+    log: &Logger,
+    parser: &mut Parser<'r, 't>,
+    name: &'t str,
+    flag_star: bool,
+    flag_score: bool,
+    in_head: bool,
+) -> ParseResult<'r, 't, Elements<'t>> {
+    info!(
+        log,
+        "Parsing ruby block";
+        "block-rule" => block_rule.name,
+        "container-type" => container_type.name(),
+        "in-head" => in_head,
+        "name" => name,
+    );
+
+    assert!(!flag_star, "Ruby block doesn't allow star flag");
+    assert!(!flag_score, "Ruby block doesn't allow score flag");
+    assert_block_name(block_rule, name);
+
+    let arguments = parser.get_head_map(block_rule, in_head)?;
+
+    // Get body content, without paragraphs
+    let (elements, exceptions, paragraph_safe) =
+        parser.get_body_elements(block_rule, false)?.into();
+
+    // Build and return element
+    let element = Element::Container(Container::new(
+        container_type,
+        elements,
+        arguments.to_attribute_map(),
+    ));
+
+    ok!(paragraph_safe; element, exceptions)
+}
+
+// Compact form, e.g. [[rubyshort 漢字 | かんじ]]
+
+fn parse_ruby_short<'r, 't>(
+    log: &Logger,
+    parser: &mut Parser<'r, 't>,
+    name: &'t str,
+    flag_star: bool,
+    flag_score: bool,
+    in_head: bool,
+) -> ParseResult<'r, 't, Elements<'t>> {
+    info!(
+        log,
+        "Parsing compact ruby block";
+        "in-head" => in_head,
+        "name" => name,
+    );
+
+    assert!(!flag_star, "Compact ruby doesn't allow star flag");
+    assert!(!flag_score, "Compact ruby doesn't allow score flag");
+    assert_block_name(&BLOCK_RUBY_SHORT, name);
+
+    let (base, text) =
+        parser.get_head_value(&BLOCK_RUBY_SHORT, in_head, |parser, value| {
+            let split = value.and_then(|value| {
+                let (base, text) = value.split_once('|')?;
+                let (base, text) = (base.trim(), text.trim());
+
+                if base.is_empty() || text.is_empty() {
+                    None
+                } else {
+                    Some((base, text))
+                }
+            });
+
+            match split {
+                Some(split) => Ok(split),
+                None => Err(parser.make_warn(ParseWarningKind::BlockMalformedArguments)),
+            }
+        })?;
+
+    // Build ruby element, with the same structure as the block form
+    let element = Element::Container(Container::new(
+        ContainerType::Ruby,
+        vec![
+            Element::Container(Container::new(
+                ContainerType::RubyBase,
+                vec![text!(base)],
+                AttributeMap::new(),
+            )),
+            Element::Container(Container::new(
+                ContainerType::RubyText,
+                vec![text!(text)],
+                AttributeMap::new(),
+            )),
+        ],
+        AttributeMap::new(),
+    ));
+
+    ok!(element)
+}
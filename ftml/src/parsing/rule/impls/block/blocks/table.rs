@@ -238,7 +238,7 @@ fn parse_cell_regular<'r, 't>(
         (&BLOCK_TABLE_CELL_REGULAR, "table cell (regular)"),
     )?;
 
-    parse_cell(elements, attributes, exceptions, false)
+    parse_cell(parser, elements, attributes, exceptions, false)
 }
 
 fn parse_cell_header<'r, 't>(
@@ -266,10 +266,11 @@ fn parse_cell_header<'r, 't>(
         (&BLOCK_TABLE_CELL_HEADER, "table cell (header)"),
     )?;
 
-    parse_cell(elements, attributes, exceptions, true)
+    parse_cell(parser, elements, attributes, exceptions, true)
 }
 
 fn parse_cell<'r, 't>(
+    parser: &mut Parser<'r, 't>,
     mut elements: Vec<Element<'t>>,
     mut attributes: AttributeMap<'t>,
     exceptions: Vec<ParseException<'t>>,
@@ -289,6 +290,8 @@ fn parse_cell<'r, 't>(
         None => *ONE,
     };
 
+    parser.add_usage(Resource::TableCells, 1);
+
     let element = Element::Partial(PartialElement::TableCell(TableCell {
         header,
         column_span,
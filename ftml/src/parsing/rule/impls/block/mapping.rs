@@ -22,12 +22,14 @@ use super::{blocks::*, BlockRule};
 use std::collections::HashMap;
 use unicase::UniCase;
 
-pub const BLOCK_RULES: [BlockRule; 55] = [
+pub const BLOCK_RULES: [BlockRule; 63] = [
     BLOCK_ALIGN_CENTER,
     BLOCK_ALIGN_JUSTIFY,
     BLOCK_ALIGN_LEFT,
     BLOCK_ALIGN_RIGHT,
     BLOCK_ANCHOR,
+    BLOCK_AUDIO,
+    BLOCK_BIBLIOGRAPHY,
     BLOCK_BLOCKQUOTE,
     BLOCK_BOLD,
     BLOCK_CHAR,
@@ -42,6 +44,7 @@ pub const BLOCK_RULES: [BlockRule; 55] = [
     BLOCK_EQUATION_REF,
     BLOCK_FOOTNOTE,
     BLOCK_FOOTNOTE_BLOCK,
+    BLOCK_GALLERY,
     BLOCK_HIDDEN,
     BLOCK_HTML,
     BLOCK_IFCATEGORY,
@@ -63,6 +66,10 @@ pub const BLOCK_RULES: [BlockRule; 55] = [
     BLOCK_OL,
     BLOCK_PARAGRAPH,
     BLOCK_RADIO,
+    BLOCK_RB,
+    BLOCK_RT,
+    BLOCK_RUBY,
+    BLOCK_RUBY_SHORT,
     BLOCK_SIZE,
     BLOCK_SPAN,
     BLOCK_STRIKETHROUGH,
@@ -78,6 +85,7 @@ pub const BLOCK_RULES: [BlockRule; 55] = [
     BLOCK_UL,
     BLOCK_UNDERLINE,
     BLOCK_USER,
+    BLOCK_VIDEO,
 ];
 
 pub type BlockRuleMap = HashMap<UniCase<&'static str>, &'static BlockRule>;
@@ -19,7 +19,7 @@
  */
 
 use super::prelude::*;
-use crate::parsing::{strip_whitespace, Token};
+use crate::parsing::{strip_whitespace, ExtractedToken, Token};
 use crate::tree::DefinitionListItem;
 
 pub const RULE_DEFINITION_LIST: Rule = Rule {
@@ -58,14 +58,16 @@ fn parse_definition_list<'p, 'r, 't>(
     info!(log, "Trying to create a definition list");
 
     let mut items = Vec::new();
+    let mut keys = Vec::new();
     let mut exceptions = Vec::new();
     let mut _paragraph_safe = false;
 
     // Definition list needs at least one item
-    let (item, at_end) =
+    let (item, key, at_end) =
         parse_item(log, parser)?.chain(&mut exceptions, &mut _paragraph_safe);
 
     items.push(item);
+    keys.push(key);
 
     // Collect remainder, halting if there's a failure
     if !at_end {
@@ -76,10 +78,11 @@ fn parse_definition_list<'p, 'r, 't>(
                 Ok(success) => {
                     debug!(log, "Retrieved definition list item");
 
-                    let (item, at_end) =
+                    let (item, key, at_end) =
                         success.chain(&mut exceptions, &mut _paragraph_safe);
 
                     items.push(item);
+                    keys.push(key);
                     parser.update(sub_parser);
 
                     if at_end {
@@ -99,14 +102,15 @@ fn parse_definition_list<'p, 'r, 't>(
         }
     }
 
-    // Build and return element
+    // Record key locations, then build and return element
+    parser.push_definition_list_keys(keys);
     ok!(Element::DefinitionList(items))
 }
 
 fn parse_item<'p, 'r, 't>(
     log: &Logger,
     parser: &'p mut Parser<'r, 't>,
-) -> ParseResult<'r, 't, (DefinitionListItem<'t>, bool)> {
+) -> ParseResult<'r, 't, (DefinitionListItem<'t>, ExtractedToken<'t>, bool)> {
     debug!(log, "Trying to parse a definition list item pair");
 
     let mut exceptions = Vec::new();
@@ -131,6 +135,7 @@ fn parse_item<'p, 'r, 't>(
     parser.step_n(2)?;
 
     // Gather key text until colon
+    let key_start = parser.current();
     let mut key = collect_consume(
         log,
         parser,
@@ -144,6 +149,18 @@ fn parse_item<'p, 'r, 't>(
     )?
     .chain(&mut exceptions, &mut _paragraph_safe);
 
+    // Token spanning the whole key, for warnings
+    let key_slice = parser
+        .full_text()
+        .slice_partial(log, key_start, parser.current())
+        .trim_end();
+
+    let key_token = ExtractedToken {
+        token: key_start.token,
+        slice: key_slice,
+        span: key_start.span.start..key_start.span.start + key_slice.len(),
+    };
+
     strip_whitespace(&mut key);
     parser.step_n(2)?;
 
@@ -174,5 +191,5 @@ fn parse_item<'p, 'r, 't>(
     // Build and return
     let item = DefinitionListItem { key, value };
 
-    ok!(false; (item, should_break), exceptions)
+    ok!(false; (item, key_token, should_break), exceptions)
 }
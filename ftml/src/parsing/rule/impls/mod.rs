@@ -25,6 +25,7 @@ mod prelude {
     pub use crate::parsing::condition::ParseCondition;
     pub use crate::parsing::consume::consume;
     pub use crate::parsing::exception::{ParseException, ParseWarning, ParseWarningKind};
+    pub use crate::parsing::limits::Resource;
     pub use crate::parsing::parser::Parser;
     pub use crate::parsing::result::{ParseResult, ParseSuccess};
     pub use crate::parsing::rule::{LineRequirement, Rule};
@@ -33,6 +34,7 @@ mod prelude {
     pub use crate::tree::{AttributeMap, Container, ContainerType, Element, Elements};
 }
 
+mod bibcite;
 mod block;
 mod blockquote;
 mod bold;
@@ -68,6 +70,7 @@ mod underscore_line_break;
 mod url;
 mod variable;
 
+pub use self::bibcite::RULE_BIBCITE;
 pub use self::block::{RULE_BLOCK, RULE_BLOCK_SKIP_NEWLINE, RULE_BLOCK_STAR};
 pub use self::blockquote::RULE_BLOCKQUOTE;
 pub use self::bold::RULE_BOLD;
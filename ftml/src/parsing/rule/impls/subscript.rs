@@ -32,6 +32,14 @@ fn try_consume_fn<'p, 'r, 't>(
 ) -> ParseResult<'r, 't, Elements<'t>> {
     info!(log, "Trying to create subscript container");
 
+    // ",," also opens a low quote, as in ,,text''.
+    // If that quote is closed before the subscript would be, then leave
+    // it as text, so typography can convert it when rendering.
+    if parser.settings().typography.enabled && closes_low_quote(parser.remaining()) {
+        debug!(log, "Subscript token opens a low quote, leaving as text");
+        return Err(parser.make_warn(ParseWarningKind::RuleFailed));
+    }
+
     check_step(parser, Token::Subscript)?;
 
     collect_container(
@@ -48,3 +56,16 @@ fn try_consume_fn<'p, 'r, 't>(
         None,
     )
 }
+
+/// Determines if `''` appears before the next `,,` in the paragraph.
+fn closes_low_quote(remaining: &[ExtractedToken]) -> bool {
+    for tokens in remaining.windows(2) {
+        match tokens[0].token {
+            Token::Subscript | Token::ParagraphBreak | Token::InputEnd => return false,
+            _ if tokens[0].slice == "'" && tokens[1].slice == "'" => return true,
+            _ => (),
+        }
+    }
+
+    false
+}
@@ -86,6 +86,8 @@ fn try_consume_fn<'p, 'r, 't>(
                 None => finish_table!(),
             };
 
+            parser.add_usage(Resource::TableCells, 1);
+
             macro_rules! build_cell {
                 () => {
                     cells.push(TableCell {
@@ -92,6 +92,7 @@ lazy_static! {
             Token::Email => vec![RULE_EMAIL],
             Token::Url => vec![RULE_URL],
             Token::Variable => vec![RULE_VARIABLE, RULE_TEXT],
+            Token::Bibcite => vec![RULE_BIBCITE, RULE_TEXT],
             Token::String => vec![RULE_TEXT],
 
             // Input boundaries
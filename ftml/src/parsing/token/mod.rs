@@ -63,7 +63,16 @@ impl<'a> ExtractedToken<'a> {
 }
 
 #[derive(
-    Serialize, Deserialize, Enum, IntoStaticStr, Debug, Copy, Clone, PartialEq, Eq,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    Enum,
+    IntoStaticStr,
+    Debug,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
 )]
 #[serde(rename_all = "kebab-case")]
 pub enum Token {
@@ -144,6 +153,7 @@ pub enum Token {
     Email,
     Url,
     Variable,
+    Bibcite,
     String,
 
     //
@@ -289,6 +299,7 @@ impl Token {
             Rule::email => Token::Email,
             Rule::url => Token::Url,
             Rule::variable => Token::Variable,
+            Rule::bibcite => Token::Bibcite,
             Rule::string => Token::String,
 
             // Other
@@ -296,7 +307,7 @@ impl Token {
             Rule::EOI => Token::InputEnd,
 
             // Invalid
-            Rule::char | Rule::document | Rule::token => {
+            Rule::char | Rule::bibcite_space | Rule::document | Rule::token => {
                 panic!("Received invalid pest rule: {:?}", rule)
             }
         }
@@ -0,0 +1,167 @@
+/*
+ * preproc/changes.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use std::cmp;
+use std::ops::Range;
+
+/// Performs replacements on the text, while recording which ranges were changed.
+///
+/// The recorded ranges are always in terms of the current text,
+/// so earlier ranges are shifted or merged as later replacements are made.
+/// A range is empty if text was only removed at that point.
+///
+/// The range of the original text that each change replaced is also kept,
+/// so that indices into the preprocessed text can be mapped back to it.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PreprocessChanges {
+    changes: Vec<Change>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Change {
+    range: Range<usize>,
+    original: Range<usize>,
+}
+
+impl PreprocessChanges {
+    #[inline]
+    pub(crate) fn new() -> Self {
+        PreprocessChanges::default()
+    }
+
+    /// Replaces the given range of the text, recording the change.
+    pub(crate) fn replace(
+        &mut self,
+        text: &mut String,
+        range: Range<usize>,
+        replacement: &str,
+    ) {
+        text.replace_range(range.clone(), replacement);
+
+        let new_end = range.start + replacement.len();
+        let shift = |index: usize| index + new_end - range.end;
+        let mut changed = Change {
+            range: range.start..new_end,
+            original: self.original_index(range.start)..self.original_index(range.end),
+        };
+        let mut changes = Vec::with_capacity(self.changes.len() + 1);
+        let mut after = Vec::new();
+
+        for existing in self.changes.drain(..) {
+            if existing.range.end < range.start {
+                // Entirely before, unaffected
+                changes.push(existing);
+            } else if existing.range.start > range.end {
+                // Entirely after, needs to be moved
+                after.push(Change {
+                    range: shift(existing.range.start)..shift(existing.range.end),
+                    original: existing.original,
+                });
+            } else {
+                // Overlaps or touches, combine into one range
+                changed.range.start = cmp::min(changed.range.start, existing.range.start);
+                changed.original.start =
+                    cmp::min(changed.original.start, existing.original.start);
+                changed.original.end =
+                    cmp::max(changed.original.end, existing.original.end);
+
+                if existing.range.end > range.end {
+                    changed.range.end =
+                        cmp::max(changed.range.end, shift(existing.range.end));
+                }
+            }
+        }
+
+        changes.push(changed);
+        changes.append(&mut after);
+        self.changes = changes;
+    }
+
+    /// Returns the ranges of the preprocessed text which were changed, in order.
+    pub fn ranges(&self) -> Vec<Range<usize>> {
+        self.changes
+            .iter()
+            .map(|change| change.range.clone())
+            .collect()
+    }
+
+    /// Converts an index into the preprocessed text to one in the original text.
+    ///
+    /// Indices within a changed range are mapped to the start of what it replaced.
+    pub fn original_index(&self, index: usize) -> usize {
+        for change in self.changes.iter().rev() {
+            if change.range.end <= index {
+                return change.original.end + (index - change.range.end);
+            }
+
+            if change.range.start < index {
+                return change.original.start;
+            }
+        }
+
+        index
+    }
+
+    /// Converts a span of the preprocessed text to one in the original text.
+    ///
+    /// If either end is within a changed range, the span is widened
+    /// to include everything that range replaced.
+    pub fn original_span(&self, span: &Range<usize>) -> Range<usize> {
+        let end =
+            match self.changes.iter().find(|change| {
+                change.range.start < span.end && span.end < change.range.end
+            }) {
+                Some(change) => change.original.end,
+                None => self.original_index(span.end),
+            };
+
+        self.original_index(span.start)..end
+    }
+}
+
+#[test]
+fn changes() {
+    let mut text = str!("apple banana cherry");
+    let mut changes = PreprocessChanges::new();
+
+    // "apple BANANA cherry"
+    changes.replace(&mut text, 6..12, "BANANA");
+
+    // "apple BANANA ch"
+    changes.replace(&mut text, 15..19, "");
+
+    // "a BANANA ch"
+    changes.replace(&mut text, 1..6, " ");
+
+    // "a BANANA!! ch"
+    changes.replace(&mut text, 8..8, "!!");
+
+    assert_eq!(text, "a BANANA!! ch");
+    assert_eq!(changes.ranges(), vec![1..10, 13..13]);
+
+    // "apple banana cherry" -> "a BANANA!! ch"
+    assert_eq!(changes.original_index(0), 0);
+    assert_eq!(changes.original_index(1), 1);
+    assert_eq!(changes.original_index(5), 1);
+    assert_eq!(changes.original_index(10), 12);
+    assert_eq!(changes.original_index(13), 19);
+    assert_eq!(changes.original_span(&(3..12)), 1..14);
+    assert_eq!(changes.original_span(&(10..13)), 12..19);
+}
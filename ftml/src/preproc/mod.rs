@@ -18,13 +18,16 @@
  * along with this program. If not, see <http://www.gnu.org/licenses/>.
  */
 
-mod typography;
+mod changes;
 mod whitespace;
 
 #[cfg(test)]
 mod test;
 
+pub use self::changes::PreprocessChanges;
 use crate::log::prelude::*;
+use crate::settings::{PreprocessPass, WikitextSettings};
+use regex::Regex;
 
 /// Run the preprocessor on the given wikitext, which is modified in-place.
 ///
@@ -33,11 +36,22 @@ use crate::log::prelude::*;
 /// * Trimming whitespace lines
 /// * Concatenating lines that end with backslashes
 /// * Convert tabs to four spaces
-/// * Wikidot typography transformations
+/// * Any additional passes configured in the settings
+/// * Cutting off text past the input length limit
 ///
-/// This call always succeeds. The return value designates where issues occurred
-/// to allow programmatic determination of where things were not as expected.
-pub fn preprocess(log: &Logger, text: &mut String) {
+/// Wikidot's typography substitutions (fancy quotes and ellipses) are not
+/// performed here, but when rendering, so that they only apply to text.
+///
+/// This call always succeeds. The return value designates which ranges of
+/// the modified text were changed, in order, to allow programmatic determination
+/// of where the text differs from the input. A range is empty if text was only
+/// removed at that point. It can also map indices in the modified text back
+/// to the input, see [`PreprocessChanges::original_span()`].
+pub fn preprocess(
+    log: &Logger,
+    text: &mut String,
+    settings: &WikitextSettings,
+) -> PreprocessChanges {
     let log = &log.new(slog_o!(
         "filename" => slog_filename!(),
         "lineno" => slog_lineno!(),
@@ -45,16 +59,101 @@ pub fn preprocess(log: &Logger, text: &mut String) {
         "text" => str!(text),
     ));
 
-    whitespace::substitute(log, text);
-    typography::substitute(log, text);
+    let max_input_length = settings.limits.max_input_length;
+    let settings = &settings.preprocess;
+    let mut changes = PreprocessChanges::new();
+
+    whitespace::substitute(log, text, &mut changes);
+
+    for pass in &settings.passes {
+        substitute_pass(log, text, pass, &mut changes);
+    }
+
+    truncate_input(log, text, max_input_length, &mut changes);
 
     info!(log, "Finished preprocessing of text"; "text" => &*text);
+
+    changes
+}
+
+/// Cuts off text past the input length limit, so it isn't tokenized.
+///
+/// One character past the limit is kept, so that the parser
+/// still produces a warning when it reaches that point.
+fn truncate_input(
+    log: &Logger,
+    text: &mut String,
+    max_input_length: usize,
+    changes: &mut PreprocessChanges,
+) {
+    let end = match (max_input_length + 1..text.len())
+        .find(|&index| text.is_char_boundary(index))
+    {
+        Some(end) => end,
+        None => return,
+    };
+
+    warn!(
+        log,
+        "Input is too long, cutting off text";
+        "text-len" => text.len(),
+        "max-input-length" => max_input_length,
+    );
+
+    changes.replace(text, end..text.len(), "");
+}
+
+fn substitute_pass(
+    log: &Logger,
+    text: &mut String,
+    pass: &PreprocessPass,
+    changes: &mut PreprocessChanges,
+) {
+    debug!(
+        log,
+        "Running additional preprocessor pass";
+        "pattern" => &pass.pattern,
+        "replacement" => &pass.replacement,
+    );
+
+    let regex = match Regex::new(&pass.pattern) {
+        Ok(regex) => regex,
+        Err(error) => {
+            warn!(
+                log,
+                "Invalid regular expression in preprocessor pass, skipping";
+                "pattern" => &pass.pattern,
+                "error" => str!(error),
+            );
+
+            return;
+        }
+    };
+
+    // Unlike the built-in substitutions, this only makes one pass over the text,
+    // since an arbitrary replacement may match its own pattern again.
+    let replacements: Vec<_> = regex
+        .captures_iter(text)
+        .map(|captures| {
+            let mtch = captures
+                .get(0)
+                .expect("Regular expression lacks a full match");
+            let mut replacement = String::new();
+            captures.expand(&pass.replacement, &mut replacement);
+
+            (mtch.start()..mtch.end(), replacement)
+        })
+        .collect();
+
+    // Replace from the end, so earlier ranges remain valid
+    for (range, replacement) in replacements.into_iter().rev() {
+        changes.replace(text, range, &replacement);
+    }
 }
 
 #[test]
 fn fn_type() {
-    type SubstituteFn = fn(&Logger, &mut String);
+    type SubstituteFn = fn(&Logger, &mut String, &mut PreprocessChanges);
 
     let _: SubstituteFn = whitespace::substitute;
-    let _: SubstituteFn = typography::substitute;
 }
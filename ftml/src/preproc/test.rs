@@ -19,7 +19,10 @@
  */
 
 use super::preprocess;
+use crate::data::PageInfo;
 use crate::log::prelude::*;
+use crate::render::{text::TextRender, Render};
+use crate::settings::{PreprocessPass, WikitextMode, WikitextSettings};
 use proptest::prelude::*;
 
 pub fn test_substitution<F>(filter_name: &str, mut substitute: F, tests: &[(&str, &str)])
@@ -45,7 +48,7 @@ where
     }
 }
 
-const PREFILTER_TEST_CASES: [(&str, &str); 10] = [
+const PREFILTER_TEST_CASES: [(&str, &str); 7] = [
     ("", ""),
     ("tab\ttest", "tab    test"),
     (
@@ -62,6 +65,11 @@ const PREFILTER_TEST_CASES: [(&str, &str); 10] = [
         "concat:\nApple Banana CherryPineapple \\ Grape\nBlueberry",
     ),
     ("[\n  \n    \n       \n  \n      \n \n   \n]", "[\n\n]"),
+];
+
+/// Typography used to be performed by the preprocessor, but is now done
+/// when rendering. These check the rendered text still matches.
+const TYPOGRAPHY_TEST_CASES: [(&str, &str); 6] = [
     (
         "SCP-4455-Ω said, ``It was a dark and stormy night. I looked down on my arch-nemesis, the Streamliner.''",
 
@@ -75,27 +83,112 @@ const PREFILTER_TEST_CASES: [(&str, &str); 10] = [
         " . . . I'm not sure about this,",
         " … I'm not sure about this,",
     ),
+    (
+        "``It was **very** dark...''",
+        "“It was very dark…”",
+    ),
+    (
+        "[[code]]\n``Not quoted'' ...\n[[/code]]\n@@``Raw''@@ ``[[[some-page|Link...]]]''",
+        "```\n``Not quoted'' ...\n```\n``Raw'' “Link... [/some-page]”",
+    ),
+    (
+        "[!-- ``Comment'' --]``Text''",
+        "“Text”",
+    ),
 ];
 
 #[test]
 fn prefilter() {
     test_substitution(
         "prefilter",
-        |log, text| preprocess(log, text),
+        |log, text| {
+            let settings = WikitextSettings::from_mode(WikitextMode::Page);
+            preprocess(log, text, &settings);
+        },
         &PREFILTER_TEST_CASES,
     );
 }
 
+#[test]
+fn typography() {
+    let log = crate::build_logger();
+    let page_info = PageInfo::dummy();
+    let mut settings = WikitextSettings::from_mode(WikitextMode::Page);
+
+    for (input, expected) in &TYPOGRAPHY_TEST_CASES {
+        let mut text = str!(input);
+        preprocess(&log, &mut text, &settings);
+
+        let tokens = crate::tokenize(&log, &text);
+        let (tree, _warnings) = crate::parse(&log, &tokens, &page_info, &settings).into();
+        let output = TextRender.render(&log, &tree, &page_info, &settings);
+
+        assert_eq!(&output, expected, "Output of typography test didn't match",);
+    }
+
+    // Disabled typography
+    settings.typography.enabled = false;
+
+    let text = "``Quote'' ...";
+    let tokens = crate::tokenize(&log, text);
+    let (tree, _warnings) = crate::parse(&log, &tokens, &page_info, &settings).into();
+    let output = TextRender.render(&log, &tree, &page_info, &settings);
+    assert_eq!(output, text, "Typography performed despite being disabled");
+}
+
+#[test]
+fn settings() {
+    let log = crate::build_logger();
+    let mut settings = WikitextSettings::from_mode(WikitextMode::Page);
+
+    macro_rules! check {
+        ($input:expr, $expected:expr, $changes:expr $(,)?) => {{
+            let mut text = str!($input);
+            let changes = preprocess(&log, &mut text, &settings);
+
+            assert_eq!(text, $expected, "Preprocessed text doesn't match expected");
+            assert_eq!(
+                changes.ranges(),
+                $changes,
+                "Changed ranges don't match expected",
+            );
+        }};
+    }
+
+    check!("``Quote'' code...\r\n", "``Quote'' code...", vec![17..17]);
+    check!("A\tB\r\nC", "A    B\nC", vec![1..5, 6..7]);
+
+    // Additional passes
+    settings.preprocess.passes = vec![
+        PreprocessPass {
+            pattern: str!(r"(\d+)C"),
+            replacement: str!("$1 °C"),
+        },
+        PreprocessPass {
+            pattern: str!("(invalid"),
+            replacement: str!("x"),
+        },
+    ];
+    check!("Heat to 90C.", "Heat to 90 °C.", vec![8..14]);
+
+    // Input length limit
+    settings.preprocess.passes.clear();
+    settings.limits.max_input_length = 4;
+    check!("abcd", "abcd", vec![]);
+    check!("abcdefgh", "abcde", vec![5..5]);
+    check!("abcdéfgh", "abcdé", vec![6..6]);
+    check!("abcéfgh", "abcé", vec![5..5]);
+}
+
 proptest! {
     #[test]
     fn prefilter_prop(mut s in ".*") {
         let log = crate::build_logger();
 
-        crate::preprocess(&log, &mut s);
+        let settings = WikitextSettings::from_mode(WikitextMode::Page);
+        crate::preprocess(&log, &mut s, &settings);
 
-        const INVALID_SUBSTRINGS: [&str; 7] = [
-            "...",
-            ". . .",
+        const INVALID_SUBSTRINGS: [&str; 5] = [
             "\r\n",
             "\r",
             "\\\n",
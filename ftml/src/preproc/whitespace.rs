@@ -27,6 +27,7 @@
 //! * Convert null characters to regular spaces
 //! * Compress groups of 3+ newlines into 2 newlines
 
+use super::PreprocessChanges;
 use crate::log::prelude::*;
 use regex::{Regex, RegexBuilder};
 
@@ -41,30 +42,36 @@ lazy_static! {
     static ref TRAILING_NEWLINES: Regex = Regex::new(r"\n+$").unwrap();
 }
 
-pub fn substitute(log: &Logger, text: &mut String) {
+pub fn substitute(log: &Logger, text: &mut String, changes: &mut PreprocessChanges) {
     // Replace DOS and Mac newlines
-    str_replace(log, text, "\r\n", "\n");
-    str_replace(log, text, "\r", "\n");
+    str_replace(log, text, changes, "\r\n", "\n");
+    str_replace(log, text, changes, "\r", "\n");
 
     // Strip lines with only whitespace
-    regex_replace(log, text, &*WHITESPACE, "");
+    regex_replace(log, text, changes, &*WHITESPACE, "");
 
     // Join concatenated lines (ending with '\')
-    str_replace(log, text, "\\\n", "");
+    str_replace(log, text, changes, "\\\n", "");
 
     // Tabs to spaces
-    str_replace(log, text, "\t", "    ");
+    str_replace(log, text, changes, "\t", "    ");
 
     // Null characters to spaces
-    str_replace(log, text, "\0", " ");
+    str_replace(log, text, changes, "\0", " ");
 
     // Remove leading and trailing newlines,
     // save one at the end
-    regex_replace(log, text, &*LEADING_NEWLINES, "");
-    regex_replace(log, text, &*TRAILING_NEWLINES, "");
+    regex_replace(log, text, changes, &*LEADING_NEWLINES, "");
+    regex_replace(log, text, changes, &*TRAILING_NEWLINES, "");
 }
 
-fn str_replace(log: &Logger, text: &mut String, pattern: &str, replacement: &str) {
+fn str_replace(
+    log: &Logger,
+    text: &mut String,
+    changes: &mut PreprocessChanges,
+    pattern: &str,
+    replacement: &str,
+) {
     debug!(
         log,
         "Replacing miscellaneous static string";
@@ -76,11 +83,17 @@ fn str_replace(log: &Logger, text: &mut String, pattern: &str, replacement: &str
 
     while let Some(idx) = text.find(pattern) {
         let range = idx..idx + pattern.len();
-        text.replace_range(range, replacement);
+        changes.replace(text, range, replacement);
     }
 }
 
-fn regex_replace(log: &Logger, text: &mut String, regex: &Regex, replacement: &str) {
+fn regex_replace(
+    log: &Logger,
+    text: &mut String,
+    changes: &mut PreprocessChanges,
+    regex: &Regex,
+    replacement: &str,
+) {
     debug!(
         log,
         "Replacing miscellaneous regular expression";
@@ -92,7 +105,7 @@ fn regex_replace(log: &Logger, text: &mut String, regex: &Regex, replacement: &s
 
     while let Some(mtch) = regex.find(text) {
         let range = mtch.start()..mtch.end();
-        text.replace_range(range, replacement);
+        changes.replace(text, range, replacement);
     }
 }
 
@@ -132,5 +145,9 @@ fn regexes() {
 fn test_substitute() {
     use super::test::test_substitution;
 
-    test_substitution("miscellaneous", substitute, &TEST_CASES);
+    test_substitution(
+        "miscellaneous",
+        |log, text| substitute(log, text, &mut PreprocessChanges::new()),
+        &TEST_CASES,
+    );
 }
@@ -0,0 +1,196 @@
+/*
+ * render/bidi.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Helpers for bidirectional text, such as Hebrew or Arabic mixed with English.
+
+use std::borrow::Cow;
+use unic_langid::{CharacterDirection, LanguageIdentifier};
+
+/// The base direction of text, as used for the HTML `dir` attribute.
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum TextDirection {
+    LeftToRight,
+    RightToLeft,
+}
+
+impl TextDirection {
+    /// Gets the direction text in the given language is written in.
+    ///
+    /// Returns `None` if the language is not a valid language identifier
+    /// with an ISO 639 language code, such as the placeholder `default`.
+    pub fn from_language(language: &str) -> Option<Self> {
+        let language: LanguageIdentifier = language.parse().ok()?;
+
+        // Undetermined languages have no direction, and while
+        // longer language subtags are valid, none are registered.
+        if language.language.is_empty()
+            || !matches!(language.language.as_str().len(), 2 | 3)
+        {
+            return None;
+        }
+
+        match language.character_direction() {
+            CharacterDirection::RTL => Some(TextDirection::RightToLeft),
+            _ => Some(TextDirection::LeftToRight),
+        }
+    }
+
+    #[inline]
+    pub fn html_value(self) -> &'static str {
+        match self {
+            TextDirection::LeftToRight => "ltr",
+            TextDirection::RightToLeft => "rtl",
+        }
+    }
+}
+
+/// Ensures every bidirectional embedding, override, or isolate in the text is closed.
+///
+/// Each line is treated separately, as the Unicode bidirectional algorithm does
+/// with paragraphs. Any which are left open are closed at the end of the line,
+/// and any terminators without a matching initiator are removed. This way one
+/// piece of text cannot change the direction of whatever is placed after it.
+pub fn balance_controls(text: &str) -> Cow<'_, str> {
+    if !text.chars().any(is_control) {
+        return Cow::Borrowed(text);
+    }
+
+    let mut output = String::with_capacity(text.len());
+    let mut stack = Vec::new();
+
+    for ch in text.chars() {
+        match ch {
+            // Embeddings and overrides
+            '\u{202a}' | '\u{202b}' | '\u{202d}' | '\u{202e}' => stack.push(ch),
+
+            // Isolates
+            '\u{2066}' | '\u{2067}' | '\u{2068}' => stack.push(ch),
+
+            // Pop directional formatting, only closes an embedding or override
+            '\u{202c}' => match stack.last() {
+                Some(&last) if !is_isolate(last) => {
+                    stack.pop();
+                }
+                _ => continue,
+            },
+
+            // Pop directional isolate, also closes any embeddings within it
+            '\u{2069}' => {
+                if !stack.iter().copied().any(is_isolate) {
+                    continue;
+                }
+
+                while let Some(last) = stack.pop() {
+                    if is_isolate(last) {
+                        break;
+                    }
+
+                    output.push('\u{202c}');
+                }
+            }
+
+            '\n' => close_all(&mut output, &mut stack),
+            _ => (),
+        }
+
+        output.push(ch);
+    }
+
+    close_all(&mut output, &mut stack);
+    Cow::Owned(output)
+}
+
+fn close_all(output: &mut String, stack: &mut Vec<char>) {
+    while let Some(last) = stack.pop() {
+        let terminator = if is_isolate(last) {
+            '\u{2069}'
+        } else {
+            '\u{202c}'
+        };
+        output.push(terminator);
+    }
+}
+
+#[inline]
+fn is_isolate(ch: char) -> bool {
+    matches!(ch, '\u{2066}' | '\u{2067}' | '\u{2068}')
+}
+
+#[inline]
+fn is_control(ch: char) -> bool {
+    matches!(ch, '\u{202a}'..='\u{202e}' | '\u{2066}'..='\u{2069}')
+}
+
+#[test]
+fn from_language() {
+    macro_rules! check {
+        ($language:expr, $expected:expr $(,)?) => {
+            assert_eq!(
+                TextDirection::from_language($language),
+                $expected,
+                "Text direction for language {:?} doesn't match",
+                $language,
+            );
+        };
+    }
+
+    check!("en", Some(TextDirection::LeftToRight));
+    check!("en-US", Some(TextDirection::LeftToRight));
+    check!("he", Some(TextDirection::RightToLeft));
+    check!("ar-EG", Some(TextDirection::RightToLeft));
+    check!("default", None);
+    check!("und", None);
+    check!("", None);
+}
+
+#[test]
+fn balance() {
+    macro_rules! check {
+        ($input:expr, $expected:expr $(,)?) => {
+            assert_eq!(
+                balance_controls($input),
+                $expected,
+                "Balanced bidi controls don't match",
+            );
+        };
+    }
+
+    // Nothing to do
+    check!("apple banana", "apple banana");
+    check!("\u{2067}שלום\u{2069} world", "\u{2067}שלום\u{2069} world");
+    check!("mark\u{200f} only", "mark\u{200f} only");
+
+    // Unclosed, at the end of the text or line
+    check!("\u{202e}reversed", "\u{202e}reversed\u{202c}");
+    check!(
+        "\u{2068}a\u{202b}b\nc",
+        "\u{2068}a\u{202b}b\u{202c}\u{2069}\nc"
+    );
+
+    // Unmatched terminators
+    check!("a\u{202c}b\u{2069}c", "abc");
+    check!("\u{2066}a\u{202c}b\u{2069}", "\u{2066}ab\u{2069}");
+
+    // Isolate terminator closing embeddings within it
+    check!(
+        "\u{2067}a\u{202a}b\u{2069}",
+        "\u{2067}a\u{202a}b\u{202c}\u{2069}"
+    );
+}
@@ -50,6 +50,42 @@ fn debug() {
     enable_page_syntax: true,
     use_true_ids: true,
     allow_local_paths: true,
+    enable_section_edit: false,
+    enable_json_ld: true,
+    html_syntax: Html,
+    preprocess: PreprocessSettings {
+        passes: [],
+    },
+    typography: TypographySettings {
+        enabled: true,
+        quotes: English,
+    },
+    html_block: HtmlBlockSettings {
+        hosting: Inline,
+        sandbox: [
+            "allow-scripts",
+        ],
+        auto_height: false,
+        stylesheet: None,
+    },
+    iframe: IframeSettings {
+        allowed_domains: [],
+        denied_domains: [],
+        sandbox: [
+            "allow-scripts",
+            "allow-popups",
+            "allow-forms",
+        ],
+        referrer_policy: "strict-origin-when-cross-origin",
+    },
+    limits: ResourceLimits {
+        max_input_length: 1048576,
+        max_elements: 200000,
+        max_table_cells: 20000,
+        max_include_expansions: 100,
+        max_css_size: 262144,
+        max_steps: 20000000,
+    },
 }
 PageInfo {
     page: "some-page",
@@ -89,6 +125,9 @@ SyntaxTree {
     ],
     table_of_contents: [],
     footnotes: [],
+    bibliographies: BibliographyList(
+        [],
+    ),
 }"#;
 
     let log = crate::build_logger();
@@ -116,6 +155,7 @@ SyntaxTree {
         styles,
         table_of_contents,
         footnotes,
+        crate::tree::BibliographyList::new(),
     );
     let (tree, _) = result.into();
 
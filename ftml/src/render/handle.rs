@@ -18,11 +18,11 @@
  * along with this program. If not, see <http://www.gnu.org/licenses/>.
  */
 
-use crate::data::{PageInfo, UserInfo};
+use crate::data::{PageInfo, PageRef, UserInfo};
 use crate::log::prelude::*;
 use crate::settings::WikitextSettings;
 use crate::tree::{ImageSource, LinkLabel, LinkLocation, Module};
-use crate::url::BuildSiteUrl;
+use crate::url::{normalize_href, BuildSiteUrl};
 use std::borrow::Cow;
 use std::num::NonZeroUsize;
 use strum_macros::IntoStaticStr;
@@ -63,6 +63,45 @@ impl Handle {
         format!("TODO: actual title ({:?})", link)
     }
 
+    /// Determines which of the given pages exist.
+    ///
+    /// This is called once per render with every page linked to,
+    /// so implementations can look them up in a single query.
+    /// Returns one value per page, in the same order.
+    pub fn get_pages_exist(&self, log: &Logger, pages: &[&PageRef]) -> Vec<bool> {
+        info!(log, "Checking if pages exist"; "pages-len" => pages.len());
+
+        // TODO: look up the pages, until then every page is assumed to exist
+        vec![true; pages.len()]
+    }
+
+    /// Gets the full URL of the page being rendered.
+    pub fn get_page_url(&self, log: &Logger, info: &PageInfo) -> String {
+        info!(log, "Getting page URL"; "page" => info.page.as_ref());
+
+        match info.category {
+            Some(ref category) => {
+                self.build_url(&info.site, &format!("{}:{}", category, info.page))
+            }
+            None => self.build_url(&info.site, &info.page),
+        }
+    }
+
+    /// Gets the URL for the form to create the given page.
+    pub fn get_page_create_url(&self, log: &Logger, page_ref: &PageRef) -> String {
+        info!(log, "Getting page creation URL"; "page" => page_ref.page());
+
+        let (site, page) = page_ref.fields();
+        let mut url = match site {
+            Some(site) => self.build_url(site, page),
+            None => normalize_href(page).into_owned(),
+        };
+
+        // TODO: use the site's real page creation route, this is Wikidot's
+        url.push_str("/edit/true");
+        url
+    }
+
     pub fn get_user_info<'a>(&self, log: &Logger, name: &'a str) -> Option<UserInfo<'a>> {
         info!(log, "Fetching user info"; "name" => name);
 
@@ -72,6 +111,7 @@ impl Handle {
         Some(info)
     }
 
+    #[inline]
     pub fn get_image_link<'a>(
         &self,
         log: &Logger,
@@ -81,6 +121,19 @@ impl Handle {
     ) -> Option<Cow<'a, str>> {
         info!(log, "Getting file link for image");
 
+        self.get_file_link(log, source, info, settings)
+    }
+
+    /// Resolves a file source to a URL, as used for images and other media.
+    pub fn get_file_link<'a>(
+        &self,
+        log: &Logger,
+        source: &ImageSource<'a>,
+        info: &PageInfo,
+        settings: &WikitextSettings,
+    ) -> Option<Cow<'a, str>> {
+        info!(log, "Getting file link"; "source" => source.name());
+
         let (site, page, file): (&str, &str, &str) = match source {
             ImageSource::Url(url) => return Some(Cow::clone(url)),
             ImageSource::File1 { .. }
@@ -90,7 +143,7 @@ impl Handle {
             {
                 warn!(
                     log,
-                    "Specified path image source when local paths  are disabled",
+                    "Specified path file source when local paths are disabled",
                 );
 
                 return None;
@@ -156,7 +209,14 @@ impl Handle {
             "table-of-contents" => "Table of Contents",
             "footnote" => "Footnote",
             "footnote-block-title" => "Footnotes",
+            "footnote-back-link" => "Back to footnote",
+            "bibliography-block-title" => "Bibliography",
+            "bibliography-back-link" => "Back to citation",
+            "iframe-blocked" => "Content blocked",
+            "iframe-open-new-tab" => "Open in new tab",
             "image-context-bad" => "No images in this context",
+            "media-context-bad" => "No media in this context",
+            "section-edit" => "Edit",
             _ => {
                 info!(
                     log,
@@ -22,11 +22,12 @@ use super::attributes::AddedAttributes;
 use super::context::HtmlContext;
 use super::render::ItemRender;
 use crate::log::prelude::*;
+use crate::settings::HtmlSyntax;
 use std::collections::HashSet;
 
 macro_rules! tag_method {
     ($tag:tt) => {
-        pub fn $tag(self) -> HtmlBuilderTag<'c, 'i, 'h, 'e, 't> {
+        pub fn $tag(self) -> HtmlBuilderTag<'c, 'i, 'h, 's, 'e, 't> {
             self.tag(stringify!($tag))
         }
     };
@@ -41,25 +42,25 @@ const SOLO_HTML_TAGS: [&str; 14] = [
 // Main struct
 
 #[derive(Debug)]
-pub struct HtmlBuilder<'c, 'i, 'h, 'e, 't>
+pub struct HtmlBuilder<'c, 'i, 'h, 's, 'e, 't>
 where
     'e: 't,
 {
-    ctx: &'c mut HtmlContext<'i, 'h, 'e, 't>,
+    ctx: &'c mut HtmlContext<'i, 'h, 's, 'e, 't>,
 }
 
-impl<'c, 'i, 'h, 'e, 't> HtmlBuilder<'c, 'i, 'h, 'e, 't>
+impl<'c, 'i, 'h, 's, 'e, 't> HtmlBuilder<'c, 'i, 'h, 's, 'e, 't>
 where
     'e: 't,
 {
     #[inline]
-    pub fn new(ctx: &'c mut HtmlContext<'i, 'h, 'e, 't>) -> Self {
+    pub fn new(ctx: &'c mut HtmlContext<'i, 'h, 's, 'e, 't>) -> Self {
         HtmlBuilder { ctx }
     }
 
     /// Create a new HTML element with the given tag type.
     #[inline]
-    pub fn tag(self, tag: &'t str) -> HtmlBuilderTag<'c, 'i, 'h, 'e, 't> {
+    pub fn tag(self, tag: &'t str) -> HtmlBuilderTag<'c, 'i, 'h, 's, 'e, 't> {
         debug_assert!(is_alphanumeric(tag));
 
         let HtmlBuilder { ctx } = self;
@@ -68,14 +69,14 @@ where
 
     /// Create a new custom element. Tag must start with `wj-`.
     #[inline]
-    pub fn element(self, tag: &'t str) -> HtmlBuilderTag<'c, 'i, 'h, 'e, 't> {
+    pub fn element(self, tag: &'t str) -> HtmlBuilderTag<'c, 'i, 'h, 's, 'e, 't> {
         debug_assert!(tag.starts_with("wj-"));
 
         self.tag(tag)
     }
 
     #[inline]
-    pub fn table_cell(self, header: bool) -> HtmlBuilderTag<'c, 'i, 'h, 'e, 't> {
+    pub fn table_cell(self, header: bool) -> HtmlBuilderTag<'c, 'i, 'h, 's, 'e, 't> {
         if header {
             self.tag("th")
         } else {
@@ -84,7 +85,13 @@ where
     }
 
     /// Creates an inline `<svg>` using the `ui.svg` spritesheet.
+    ///
+    /// The spritesheet isn't available within an EPUB, so nothing is added there.
     pub fn sprite(self, id: &'t str) {
+        if self.ctx.settings().html_syntax == HtmlSyntax::Epub {
+            return;
+        }
+
         let viewbox = match id {
             "wj-karma" => "0 0 64 114",
             _ => "0 0 24 24",
@@ -92,9 +99,11 @@ where
 
         let class = format!("wj-sprite sprite-{}", id);
         let href = format!("/files--static/media/ui.svg#{}", id);
+        let xml = self.ctx.settings().html_syntax.is_xml();
 
         self.tag("svg")
             .attr(attr!(
+                "xmlns" => "http://www.w3.org/2000/svg"; if xml,
                 "class" => &class,
                 "viewBox" => viewbox,
             ))
@@ -137,31 +146,36 @@ where
 // Helper structs
 
 #[derive(Debug)]
-pub struct HtmlBuilderTag<'c, 'i, 'h, 'e, 't>
+pub struct HtmlBuilderTag<'c, 'i, 'h, 's, 'e, 't>
 where
     'e: 't,
 {
-    ctx: &'c mut HtmlContext<'i, 'h, 'e, 't>,
+    ctx: &'c mut HtmlContext<'i, 'h, 's, 'e, 't>,
     tag: &'t str,
+    xml: bool,
     in_tag: bool,
     in_contents: bool,
 }
 
-impl<'c, 'i, 'h, 'e, 't> HtmlBuilderTag<'c, 'i, 'h, 'e, 't> {
-    pub fn new(ctx: &'c mut HtmlContext<'i, 'h, 'e, 't>, tag: &'t str) -> Self {
+impl<'c, 'i, 'h, 's, 'e, 't> HtmlBuilderTag<'c, 'i, 'h, 's, 'e, 't> {
+    pub fn new(ctx: &'c mut HtmlContext<'i, 'h, 's, 'e, 't>, tag: &'t str) -> Self {
+        let xml = ctx.settings().html_syntax.is_xml();
+
         ctx.push_raw('<');
         ctx.push_raw_str(tag);
 
         HtmlBuilderTag {
             ctx,
             tag,
+            xml,
             in_tag: true,
             in_contents: false,
         }
     }
 
     fn attr_key(&mut self, key: &str, has_value: bool) {
-        debug_assert!(is_alphanumeric(key));
+        // Namespaced attributes, such as "epub:type", are permitted.
+        debug_assert!(key.split(':').all(is_alphanumeric));
         debug_assert!(self.in_tag);
 
         self.ctx.push_raw(' ');
@@ -241,9 +255,13 @@ impl<'c, 'i, 'h, 'e, 't> HtmlBuilderTag<'c, 'i, 'h, 'e, 't> {
         // Because .all() is true for empty slices, this expression
         // checks both:
 
+        //
+        // XML doesn't permit attributes without values, so there
+        // they're given an empty one instead, which means the same.
+
         let has_value = !value_parts.iter().all(|s| s.is_empty());
 
-        self.attr_key(key, has_value);
+        self.attr_key(key, has_value || self.xml);
 
         if has_value {
             self.ctx.push_raw('"');
@@ -251,6 +269,8 @@ impl<'c, 'i, 'h, 'e, 't> HtmlBuilderTag<'c, 'i, 'h, 'e, 't> {
                 self.ctx.push_escaped(part);
             }
             self.ctx.push_raw('"');
+        } else if self.xml {
+            self.ctx.push_raw_str("\"\"");
         }
 
         self
@@ -285,13 +305,20 @@ impl<'c, 'i, 'h, 'e, 't> HtmlBuilderTag<'c, 'i, 'h, 'e, 't> {
     }
 }
 
-impl<'c, 'i, 'h, 'e, 't> Drop for HtmlBuilderTag<'c, 'i, 'h, 'e, 't> {
+impl<'c, 'i, 'h, 's, 'e, 't> Drop for HtmlBuilderTag<'c, 'i, 'h, 's, 'e, 't> {
     fn drop(&mut self) {
+        let close_tag = should_close_tag(self.tag);
+
         if self.in_tag && !self.in_contents {
-            self.ctx.push_raw('>');
+            // In XML, void elements must be self-closing.
+            if self.xml && !close_tag {
+                self.ctx.push_raw_str(" />");
+            } else {
+                self.ctx.push_raw('>');
+            }
         }
 
-        if should_close_tag(self.tag) {
+        if close_tag {
             self.ctx.push_raw_str("</");
             self.ctx.push_raw_str(self.tag);
             self.ctx.push_raw('>');
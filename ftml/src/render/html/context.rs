@@ -27,27 +27,41 @@ use crate::data::PageRef;
 use crate::data::{Backlinks, PageInfo};
 use crate::info;
 use crate::next_index::{NextIndex, TableOfContentsIndex};
+use crate::render::bidi::TextDirection;
+use crate::render::typography::Typography;
 use crate::render::Handle;
 use crate::settings::WikitextSettings;
-use crate::tree::{Element, LinkLocation, VariableScopes};
+use crate::tree::{
+    AttributeMap, BibliographyList, Element, LinkLocation, VariableScopes,
+};
 use crate::url::is_url;
 use std::borrow::Cow;
-use std::fmt::{self, Write};
+use std::collections::{HashMap, HashSet};
+use std::fmt::{self, Debug, Write};
 use std::num::NonZeroUsize;
 
+/// How much of the body to hold before passing it on to a sink, in bytes.
+const SINK_BUFFER_SIZE: usize = 8192;
+
 #[derive(Debug)]
-pub struct HtmlContext<'i, 'h, 'e, 't>
+pub struct HtmlContext<'i, 'h, 's, 'e, 't>
 where
     'e: 't,
 {
     body: String,
+    sink: Option<HtmlSink<'s>>,
     styles: Vec<String>,
     meta: Vec<HtmlMeta>,
+    json_ld: Option<String>,
     backlinks: Backlinks<'static>,
+    missing_pages: HashSet<PageRef<'static>>,
     info: &'i PageInfo<'i>,
     handle: &'h Handle,
     settings: &'e WikitextSettings,
     random: Random,
+    direction: Option<TextDirection>,
+    explicit_direction_depth: usize,
+    include_depth: usize,
 
     //
     // Included page scopes
@@ -59,17 +73,21 @@ where
     //
     table_of_contents: &'e [Element<'t>],
     footnotes: &'e [Vec<Element<'t>>],
+    bibliographies: &'e BibliographyList<'t>,
+    typography: &'e Typography,
 
     //
     // Other fields to track
     //
     code_snippet_index: NonZeroUsize,
     table_of_contents_index: usize,
+    section_index: usize,
     equation_index: NonZeroUsize,
     footnote_index: NonZeroUsize,
+    bibliography_citations: HashMap<usize, usize>,
 }
 
-impl<'i, 'h, 'e, 't> HtmlContext<'i, 'h, 'e, 't> {
+impl<'i, 'h, 's, 'e, 't> HtmlContext<'i, 'h, 's, 'e, 't> {
     #[inline]
     pub fn new(
         info: &'i PageInfo<'i>,
@@ -77,26 +95,50 @@ impl<'i, 'h, 'e, 't> HtmlContext<'i, 'h, 'e, 't> {
         settings: &'e WikitextSettings,
         table_of_contents: &'e [Element<'t>],
         footnotes: &'e [Vec<Element<'t>>],
+        bibliographies: &'e BibliographyList<'t>,
+        typography: &'e Typography,
     ) -> Self {
         HtmlContext {
             body: String::new(),
+            sink: None,
             styles: Vec::new(),
             meta: Self::initial_metadata(info),
+            json_ld: None,
             backlinks: Backlinks::new(),
+            missing_pages: HashSet::new(),
             info,
             handle,
             settings,
             random: Random::default(),
+            direction: TextDirection::from_language(&info.language),
+            explicit_direction_depth: 0,
+            include_depth: 0,
             variables: VariableScopes::new(),
             table_of_contents,
             footnotes,
+            bibliographies,
+            typography,
             code_snippet_index: NonZeroUsize::new(1).unwrap(),
             table_of_contents_index: 0,
+            section_index: 0,
             equation_index: NonZeroUsize::new(1).unwrap(),
             footnote_index: NonZeroUsize::new(1).unwrap(),
+            bibliography_citations: HashMap::new(),
         }
     }
 
+    /// Sends the body to the given writer as it is rendered, instead of collecting it.
+    ///
+    /// The body is buffered and written out in chunks, and whatever remains
+    /// is written by [`finish()`](Self::finish).
+    #[inline]
+    pub fn set_sink(&mut self, writer: &'s mut dyn Write) {
+        self.sink = Some(HtmlSink {
+            writer,
+            result: Ok(()),
+        });
+    }
+
     fn initial_metadata(info: &PageInfo<'i>) -> Vec<HtmlMeta> {
         // Initial version, we can tune how the metadata is generated later.
 
@@ -158,6 +200,48 @@ impl<'i, 'h, 'e, 't> HtmlContext<'i, 'h, 'e, 't> {
         &self.info.language
     }
 
+    /// The direction of the page's language, if it is known.
+    #[inline]
+    pub fn direction(&self) -> Option<TextDirection> {
+        self.direction
+    }
+
+    /// Whether a block of user content should have its direction chosen from its text.
+    ///
+    /// This is only done if the page's direction is known, and neither the block
+    /// nor any container it is within already sets a direction of its own.
+    pub fn auto_direction(&self, attributes: &AttributeMap) -> bool {
+        self.direction.is_some()
+            && self.explicit_direction_depth == 0
+            && !attributes.get().contains_key("dir")
+    }
+
+    #[inline]
+    pub fn enter_explicit_direction(&mut self) {
+        self.explicit_direction_depth += 1;
+    }
+
+    #[inline]
+    pub fn exit_explicit_direction(&mut self) {
+        self.explicit_direction_depth -= 1;
+    }
+
+    /// Whether the elements being rendered come from an included page.
+    #[inline]
+    pub fn in_include(&self) -> bool {
+        self.include_depth > 0
+    }
+
+    #[inline]
+    pub fn enter_include(&mut self) {
+        self.include_depth += 1;
+    }
+
+    #[inline]
+    pub fn exit_include(&mut self) {
+        self.include_depth -= 1;
+    }
+
     #[inline]
     pub fn variables(&self) -> &VariableScopes {
         &self.variables
@@ -178,6 +262,16 @@ impl<'i, 'h, 'e, 't> HtmlContext<'i, 'h, 'e, 't> {
         self.footnotes
     }
 
+    #[inline]
+    pub fn bibliographies(&self) -> &'e BibliographyList<'t> {
+        self.bibliographies
+    }
+
+    #[inline]
+    pub fn typography(&self) -> &'e Typography {
+        self.typography
+    }
+
     pub fn next_code_snippet_index(&mut self) -> NonZeroUsize {
         let index = self.code_snippet_index;
         self.code_snippet_index = NonZeroUsize::new(index.get() + 1).unwrap();
@@ -190,6 +284,12 @@ impl<'i, 'h, 'e, 't> HtmlContext<'i, 'h, 'e, 't> {
         index
     }
 
+    pub fn next_section_index(&mut self) -> usize {
+        let index = self.section_index;
+        self.section_index += 1;
+        index
+    }
+
     pub fn next_equation_index(&mut self) -> NonZeroUsize {
         let index = self.equation_index;
         self.equation_index = NonZeroUsize::new(index.get() + 1).unwrap();
@@ -202,6 +302,15 @@ impl<'i, 'h, 'e, 't> HtmlContext<'i, 'h, 'e, 't> {
         index
     }
 
+    /// Gets the index of the next citation of the given bibliography entry.
+    ///
+    /// Each entry counts its citations separately, starting from 1.
+    pub fn next_bibliography_citation(&mut self, number: usize) -> usize {
+        let index = self.bibliography_citations.entry(number).or_insert(0);
+        *index += 1;
+        *index
+    }
+
     #[inline]
     pub fn get_footnote(&self, index_one: NonZeroUsize) -> Option<&'e [Element<'t>]> {
         self.footnotes
@@ -209,6 +318,17 @@ impl<'i, 'h, 'e, 't> HtmlContext<'i, 'h, 'e, 't> {
             .map(|elements| elements.as_slice())
     }
 
+    // Page existence
+    #[inline]
+    pub fn set_missing_pages(&mut self, missing_pages: HashSet<PageRef<'static>>) {
+        self.missing_pages = missing_pages;
+    }
+
+    #[inline]
+    pub fn page_exists(&self, page: &PageRef) -> bool {
+        !self.missing_pages.contains(page)
+    }
+
     // Backlinks
     #[inline]
     pub fn add_link(&mut self, link: &LinkLocation) {
@@ -217,7 +337,13 @@ impl<'i, 'h, 'e, 't> HtmlContext<'i, 'h, 'e, 't> {
 
         match link {
             LinkLocation::Page(page) => {
-                self.backlinks.internal_links.push(page.to_owned());
+                let page_ref = page.to_owned();
+
+                if self.page_exists(page) {
+                    self.backlinks.internal_links.push(page_ref);
+                } else {
+                    self.backlinks.missing_links.push(page_ref);
+                }
             }
             LinkLocation::Url(link) => {
                 let mut link: &str = link;
@@ -261,34 +387,83 @@ impl<'i, 'h, 'e, 't> HtmlContext<'i, 'h, 'e, 't> {
         self.styles.push(style);
     }
 
+    #[inline]
+    pub fn add_meta(&mut self, meta: &mut Vec<HtmlMeta>) {
+        self.meta.append(meta);
+    }
+
+    #[inline]
+    pub fn set_json_ld(&mut self, json_ld: Option<String>) {
+        self.json_ld = json_ld;
+    }
+
     #[inline]
     pub fn push_raw(&mut self, ch: char) {
         self.buffer().push(ch);
+        self.flush_if_full();
     }
 
     #[inline]
     pub fn push_raw_str(&mut self, s: &str) {
         self.buffer().push_str(s);
+        self.flush_if_full();
     }
 
     #[inline]
     pub fn push_escaped(&mut self, s: &str) {
         escape(self.buffer(), s);
+        self.flush_if_full();
+    }
+
+    #[inline]
+    fn flush_if_full(&mut self) {
+        if self.body.len() >= SINK_BUFFER_SIZE {
+            self.flush();
+        }
+    }
+
+    /// Passes the buffered body on to the sink, if there is one.
+    ///
+    /// After the sink fails, nothing more is written to it,
+    /// and the error is returned by [`finish()`](Self::finish).
+    fn flush(&mut self) {
+        if let Some(ref mut sink) = self.sink {
+            if sink.result.is_ok() {
+                sink.result = sink.writer.write_str(&self.body);
+            }
+
+            self.body.clear();
+        }
+    }
+
+    /// Completes rendering, writing out any remaining body to the sink.
+    ///
+    /// If a sink was set, the body of the returned output is empty.
+    pub fn finish(mut self) -> Result<HtmlOutput, fmt::Error> {
+        self.flush();
+
+        match self.sink.take() {
+            Some(HtmlSink {
+                result: Err(error), ..
+            }) => Err(error),
+            _ => Ok(self.into()),
+        }
     }
 
     #[inline]
-    pub fn html(&mut self) -> HtmlBuilder<'_, 'i, 'h, 'e, 't> {
+    pub fn html(&mut self) -> HtmlBuilder<'_, 'i, 'h, 's, 'e, 't> {
         HtmlBuilder::new(self)
     }
 }
 
-impl<'i, 'h, 'e, 't> From<HtmlContext<'i, 'h, 'e, 't>> for HtmlOutput {
+impl<'i, 'h, 's, 'e, 't> From<HtmlContext<'i, 'h, 's, 'e, 't>> for HtmlOutput {
     #[inline]
-    fn from(ctx: HtmlContext<'i, 'h, 'e, 't>) -> HtmlOutput {
+    fn from(ctx: HtmlContext<'i, 'h, 's, 'e, 't>) -> HtmlOutput {
         let HtmlContext {
             body,
             styles,
             meta,
+            json_ld,
             backlinks,
             ..
         } = ctx;
@@ -297,21 +472,40 @@ impl<'i, 'h, 'e, 't> From<HtmlContext<'i, 'h, 'e, 't>> for HtmlOutput {
             body,
             styles,
             meta,
+            json_ld,
             backlinks,
         }
     }
 }
 
-impl<'i, 'h, 'e, 't> Write for HtmlContext<'i, 'h, 'e, 't> {
+impl<'i, 'h, 's, 'e, 't> Write for HtmlContext<'i, 'h, 's, 'e, 't> {
     #[inline]
     fn write_str(&mut self, s: &str) -> fmt::Result {
-        self.buffer().write_str(s)
+        self.push_raw_str(s);
+        Ok(())
     }
 }
 
-impl<'i, 'h, 'e, 't> NextIndex<TableOfContentsIndex> for HtmlContext<'i, 'h, 'e, 't> {
+impl<'i, 'h, 's, 'e, 't> NextIndex<TableOfContentsIndex>
+    for HtmlContext<'i, 'h, 's, 'e, 't>
+{
     #[inline]
     fn next(&mut self) -> usize {
         self.next_table_of_contents_index()
     }
 }
+
+/// A writer which receives the body while it is being rendered.
+struct HtmlSink<'s> {
+    writer: &'s mut dyn Write,
+    result: fmt::Result,
+}
+
+impl Debug for HtmlSink<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("HtmlSink")
+            .field("writer", &"<dyn Write>")
+            .field("result", &self.result)
+            .finish()
+    }
+}
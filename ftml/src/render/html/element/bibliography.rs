@@ -0,0 +1,195 @@
+/*
+ * render/html/element/bibliography.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::prelude::*;
+
+pub fn render_bibcite(log: &Logger, ctx: &mut HtmlContext, label: &str) {
+    info!(
+        log,
+        "Rendering bibliography citation";
+        "label" => label,
+    );
+
+    let number = match ctx.bibliographies().get_reference(label) {
+        Some((number, _)) => number,
+        None => {
+            debug!(log, "No such bibliography label found");
+
+            ctx.html()
+                .span()
+                .attr(attr!("class" => "wj-error-inline"))
+                .inner(log, format!("[{}]", label));
+
+            return;
+        }
+    };
+
+    let citation = ctx.next_bibliography_citation(number);
+    let use_true_ids = ctx.settings().use_true_ids;
+    let href = format!("#wj-bibitem-{}", number);
+    let id = format!("wj-bibcite-{}-{}", number, citation);
+    let marker = format!("[{}]", number);
+
+    ctx.html()
+        .sup()
+        .attr(attr!("class" => "wj-bibcite"))
+        .contents(|ctx| {
+            ctx.html()
+                .a()
+                .attr(attr!(
+                    "class" => "wj-bibcite-link",
+                    "href" => &href,
+                    "id" => &id; if use_true_ids,
+                    "data-label" => label,
+                ))
+                .inner(log, &marker);
+        });
+}
+
+pub fn render_bibliography(
+    log: &Logger,
+    ctx: &mut HtmlContext,
+    index: usize,
+    title: Option<&str>,
+) {
+    info!(
+        log,
+        "Rendering bibliography block";
+        "index" => index,
+        "title" => title.unwrap_or("<default>"),
+    );
+
+    let (first, bibliography) = match ctx.bibliographies().get_bibliography(index) {
+        Some(result) => result,
+        None => {
+            warn!(log, "Bibliography index out of bounds, skipping");
+            return;
+        }
+    };
+
+    if bibliography.entries.is_empty() {
+        debug!(log, "Bibliography has no entries, skipping");
+        return;
+    }
+
+    let title_default;
+    let title: &str = match title {
+        Some(title) => title,
+        None => {
+            title_default =
+                ctx.handle()
+                    .get_message(log, ctx.language(), "bibliography-block-title");
+            title_default
+        }
+    };
+
+    let use_true_ids = ctx.settings().use_true_ids;
+    let back_label =
+        ctx.handle()
+            .get_message(log, ctx.language(), "bibliography-back-link");
+    let start = str!(first);
+
+    ctx.html()
+        .div()
+        .attr(attr!("class" => "wj-bibliography"))
+        .contents(|ctx| {
+            ctx.html()
+                .div()
+                .attr(attr!("class" => "wj-title"))
+                .inner(log, title);
+
+            ctx.html()
+                .ol()
+                .attr(attr!("start" => &start; if first > 1))
+                .contents(|ctx| {
+                    for (offset, entry) in bibliography.entries.iter().enumerate() {
+                        let number = first + offset;
+                        let id = format!("wj-bibitem-{}", number);
+
+                        ctx.html()
+                            .li()
+                            .attr(attr!(
+                                "class" => "wj-bibliography-item",
+                                "id" => &id; if use_true_ids,
+                                "data-label" => entry.label.as_ref(),
+                            ))
+                            .contents(|ctx| {
+                                // Citation contents
+                                ctx.html()
+                                    .span()
+                                    .attr(
+                                        attr!("class" => "wj-bibliography-item-contents"),
+                                    )
+                                    .inner(log, &entry.elements);
+
+                                // Links back to each place it was cited,
+                                // which only have ids to link to with true ids.
+                                if use_true_ids {
+                                    render_back_links(
+                                        log,
+                                        ctx,
+                                        number,
+                                        entry.citations,
+                                        back_label,
+                                    );
+                                }
+                            });
+                    }
+                });
+        });
+}
+
+fn render_back_links(
+    log: &Logger,
+    ctx: &mut HtmlContext,
+    number: usize,
+    citations: usize,
+    back_label: &str,
+) {
+    if citations == 0 {
+        return;
+    }
+
+    ctx.html()
+        .span()
+        .attr(attr!("class" => "wj-bibliography-back-links"))
+        .contents(|ctx| {
+            for citation in 1..=citations {
+                let href = format!("#wj-bibcite-{}-{}", number, citation);
+
+                // With only one citation an arrow is enough,
+                // otherwise each link gets its own number.
+                let text = if citations == 1 {
+                    str!("↑")
+                } else {
+                    str!(citation)
+                };
+
+                ctx.html()
+                    .a()
+                    .attr(attr!(
+                        "class" => "wj-bibliography-back-link",
+                        "href" => &href,
+                        "aria-label" => back_label,
+                    ))
+                    .inner(log, &text);
+            }
+        });
+}
@@ -87,6 +87,9 @@ pub fn render_collapsible(log: &Logger, ctx: &mut HtmlContext, collapsible: Coll
             .get_message(log, ctx.language(), "collapsible-hide")
     });
 
+    // ID of the content block, for the buttons to refer to
+    let content_id = ctx.random().generate_html_id();
+
     ctx.html()
         .details()
         .attr(attr!(
@@ -102,6 +105,8 @@ pub fn render_collapsible(log: &Logger, ctx: &mut HtmlContext, collapsible: Coll
                 .summary()
                 .attr(attr!(
                     "class" => "wj-collapsible-button wj-collapsible-button-top",
+                    "aria-expanded" => if start_open { "true" } else { "false" },
+                    "aria-controls" => &content_id,
                 ))
                 .contents(|ctx| {
                     // Block is folded text
@@ -120,7 +125,10 @@ pub fn render_collapsible(log: &Logger, ctx: &mut HtmlContext, collapsible: Coll
             // Content block
             ctx.html()
                 .div()
-                .attr(attr!("class" => "wj-collapsible-content"))
+                .attr(attr!(
+                    "class" => "wj-collapsible-content",
+                    "id" => &content_id,
+                ))
                 .inner(log, elements);
 
             // Bottom open/close button
@@ -129,6 +137,9 @@ pub fn render_collapsible(log: &Logger, ctx: &mut HtmlContext, collapsible: Coll
                     .element("wj-collapsible-button-bottom")
                     .attr(attr!(
                         "class" => "wj-collapsible-button wj-collapsible-button-bottom",
+                        "role" => "button",
+                        "tabindex" => "0",
+                        "aria-controls" => &content_id,
                     ))
                     .contents(|ctx| {
                         // Block is unfolded text
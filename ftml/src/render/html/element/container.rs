@@ -19,38 +19,121 @@
  */
 
 use super::prelude::*;
-use crate::tree::{Container, HtmlTag};
+use crate::tree::{Container, ContainerType, HtmlTag};
 
 pub fn render_container(log: &Logger, ctx: &mut HtmlContext, container: &Container) {
     info!(log, "Rendering container"; "container" => container.ctype().name());
 
+    // Inline code is isolated, so text in the other direction around it displays correctly
+    if container.ctype() == ContainerType::Monospace {
+        ctx.html()
+            .tag("bdi")
+            .contents(|ctx| render_container_tag(log, ctx, container));
+    } else {
+        render_container_tag(log, ctx, container);
+    }
+}
+
+fn render_container_tag(log: &Logger, ctx: &mut HtmlContext, container: &Container) {
+    // Ruby text has fallback parentheses, for browsers without ruby support
+    let ruby_text = container.ctype() == ContainerType::RubyText;
+    if ruby_text {
+        ctx.html().tag("rp").inner(log, "(");
+    }
+
+    // Headings get a button to edit their section, if enabled.
+    //
+    // Included headings aren't in this page's source, and so aren't counted,
+    // the same as in crate::sections::get_sections().
+    let section_index = match container.ctype() {
+        ContainerType::Header(_)
+            if ctx.settings().enable_section_edit && !ctx.in_include() =>
+        {
+            Some(ctx.next_section_index())
+        }
+        _ => None,
+    };
+
     // Get HTML tag type for this type of container
     let tag_spec = container.ctype().html_tag(ctx);
 
     // Get correct ID, based on the render setting
     let random_id = choose_id(ctx, &tag_spec);
 
+    // Blocks of text have their direction chosen by their contents
+    let auto_direction = matches!(
+        container.ctype(),
+        ContainerType::Paragraph | ContainerType::Blockquote | ContainerType::Header(_),
+    ) && ctx.auto_direction(container.attributes());
+
     // Build the tag
     let mut tag = ctx.html().tag(tag_spec.tag());
 
     // Merge the class attribute with the container's class, if it conflicts
     match tag_spec {
-        HtmlTag::Tag(_) => tag.attr(attr!(;; container.attributes())),
+        HtmlTag::Tag(_) => tag.attr(attr!(
+            "dir" => "auto"; if auto_direction;;
+            container.attributes(),
+        )),
         HtmlTag::TagAndClass { class, .. } => tag.attr(attr!(
-            "class" => class;;
+            "class" => class,
+            "dir" => "auto"; if auto_direction;;
             container.attributes(),
         )),
         HtmlTag::TagAndId { id, .. } => tag.attr(attr!(
             "id" => match random_id {
                 Some(ref id) => id,
                 None => &id,
-            };;
+            },
+            "dir" => "auto"; if auto_direction;;
             container.attributes(),
         )),
     };
 
     // Add container internals
-    tag.inner(log, container.elements());
+    //
+    // If the container sets its own direction, blocks inside shouldn't override it.
+    let explicit_direction = container.attributes().get().contains_key("dir");
+
+    tag.contents(|ctx| {
+        if explicit_direction {
+            ctx.enter_explicit_direction();
+        }
+
+        render_elements(log, ctx, container.elements());
+
+        if let Some(index) = section_index {
+            render_section_edit(log, ctx, index);
+        }
+
+        if explicit_direction {
+            ctx.exit_explicit_direction();
+        }
+    });
+    drop(tag);
+
+    if ruby_text {
+        ctx.html().tag("rp").inner(log, ")");
+    }
+}
+
+fn render_section_edit(log: &Logger, ctx: &mut HtmlContext, index: usize) {
+    debug!(log, "Rendering section edit button"; "index" => index);
+
+    let message = ctx
+        .handle()
+        .get_message(log, ctx.language(), "section-edit");
+
+    let index = str!(index);
+
+    ctx.html()
+        .tag("button")
+        .attr(attr!(
+            "type" => "button",
+            "class" => "wj-section-edit",
+            "data-section" => &index,
+        ))
+        .inner(log, message);
 }
 
 pub fn render_color(
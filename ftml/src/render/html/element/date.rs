@@ -41,12 +41,18 @@ pub fn render_date(
     let formatted_datetime = str!(date.format(date_format));
 
     // Build HTML elements
+    //
+    // Uses a time element, so the exact date is available to assistive technologies
+    // regardless of how it is formatted.
+    let iso_datetime = date.to_rfc3339();
+
     ctx.html()
-        .span()
+        .tag("time")
         .attr(attr!(
             "class" => "wj-date" space hover_class,
+            "datetime" => &iso_datetime,
             "data-format" => date_format.unwrap_or_else(|| date.default_format_string()),
-            "data-iso" => &date.to_rfc3339(),
+            "data-iso" => &iso_datetime,
             "data-timestamp" => &timestamp,
             "data-delta" => &delta,
         ))
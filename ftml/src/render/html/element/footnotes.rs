@@ -19,6 +19,7 @@
  */
 
 use super::prelude::*;
+use crate::settings::HtmlSyntax;
 
 pub fn render_footnote(log: &Logger, ctx: &mut HtmlContext) {
     info!(log, "Rendering footnote reference");
@@ -34,6 +35,26 @@ pub fn render_footnote(log: &Logger, ctx: &mut HtmlContext) {
         .get_footnote(index)
         .expect("Footnote index out of bounds from gathered footnote list");
 
+    // EPUB reading systems show the note as a popup instead.
+    if ctx.settings().html_syntax == HtmlSyntax::Epub {
+        let ref_id = format!("wj-footnote-ref-{}", index);
+        let href = format!("#wj-footnote-{}", index);
+
+        ctx.html()
+            .a()
+            .attr(attr!(
+                "class" => "wj-footnote-ref-marker",
+                "id" => &ref_id,
+                "href" => &href,
+                "epub:type" => "noteref",
+                "role" => "doc-noteref",
+                "aria-label" => &label,
+            ))
+            .inner(log, &id);
+
+        return;
+    }
+
     ctx.html()
         .span()
         .attr(attr!("class" => "wj-footnote-ref"))
@@ -44,6 +65,7 @@ pub fn render_footnote(log: &Logger, ctx: &mut HtmlContext) {
                 .attr(attr!(
                     "class" => "wj-footnote-ref-marker",
                     "role" => "link",
+                    "tabindex" => "0",
                     "aria-label" => &label,
                     "data-id" => &id,
                 ))
@@ -102,17 +124,30 @@ pub fn render_footnote_block(log: &Logger, ctx: &mut HtmlContext, title: Option<
                 .attr(attr!("class" => "wj-title"))
                 .inner(log, title);
 
+            if ctx.settings().html_syntax == HtmlSyntax::Epub {
+                render_footnote_notes(log, ctx);
+                return;
+            }
+
+            let back_string =
+                ctx.handle()
+                    .get_message(log, ctx.language(), "footnote-back-link");
+
             ctx.html().ol().contents(|ctx| {
                 // TODO make this into a footnote helper method
                 for (index, contents) in ctx.footnotes().iter().enumerate() {
                     let index = index + 1;
                     let id = &format!("{}", index);
+                    let back_label = &format!("{} {}", back_string, index);
 
                     // Build actual footnote item
+                    //
+                    // Can be focused by script, when moving here from the reference.
                     ctx.html()
                         .li()
                         .attr(attr!(
                             "class" => "wj-footnote-list-item",
+                            "tabindex" => "-1",
                             "data-id" => id,
                         ))
                         .contents(|ctx| {
@@ -123,6 +158,8 @@ pub fn render_footnote_block(log: &Logger, ctx: &mut HtmlContext, title: Option<
                                     "class" => "wj-footnote-list-item-marker",
                                     "type" => "button",
                                     "role" => "link",
+                                    "tabindex" => "0",
+                                    "aria-label" => back_label,
                                 ))
                                 .contents(|ctx| {
                                     str_write!(ctx, "{}", index);
@@ -144,3 +181,39 @@ pub fn render_footnote_block(log: &Logger, ctx: &mut HtmlContext, title: Option<
             });
         });
 }
+
+/// Renders each footnote as an EPUB note, for popups.
+fn render_footnote_notes(log: &Logger, ctx: &mut HtmlContext) {
+    for (index, contents) in ctx.footnotes().iter().enumerate() {
+        let index = index + 1;
+        let id = format!("wj-footnote-{}", index);
+        let href = format!("#wj-footnote-ref-{}", index);
+
+        ctx.html()
+            .tag("aside")
+            .attr(attr!(
+                "class" => "wj-footnote-list-item",
+                "id" => &id,
+                "epub:type" => "footnote",
+                "role" => "doc-footnote",
+            ))
+            .contents(|ctx| {
+                // Number, linking back to the reference
+                ctx.html()
+                    .a()
+                    .attr(attr!(
+                        "class" => "wj-footnote-list-item-marker",
+                        "href" => &href,
+                    ))
+                    .contents(|ctx| str_write!(ctx, "{}.", index));
+
+                ctx.push_raw(' ');
+
+                // Footnote contents
+                ctx.html()
+                    .span()
+                    .attr(attr!("class" => "wj-footnote-list-item-contents"))
+                    .inner(log, contents);
+            });
+    }
+}
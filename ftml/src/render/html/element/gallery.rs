@@ -0,0 +1,153 @@
+/*
+ * render/html/element/gallery.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::prelude::*;
+use crate::tree::{AttributeMap, GalleryImage, GallerySize};
+use crate::url::normalize_link;
+use std::borrow::Cow;
+
+pub fn render_gallery(
+    log: &Logger,
+    ctx: &mut HtmlContext,
+    images: &[GalleryImage],
+    size: GallerySize,
+    attributes: &AttributeMap,
+) {
+    info!(
+        log,
+        "Rendering gallery";
+        "images" => images.len(),
+        "size" => size.name(),
+        "allow-local-paths" => ctx.settings().allow_local_paths,
+    );
+
+    // Without local paths, most gallery images cannot be shown,
+    // so instead we produce a plain list of links to each image.
+    if !ctx.settings().allow_local_paths {
+        render_gallery_links(log, ctx, images, attributes);
+        return;
+    }
+
+    ctx.html()
+        .div()
+        .attr(attr!(
+            "class" => "wj-gallery " size.html_class();;
+            attributes
+        ))
+        .contents(|ctx| {
+            for image in images {
+                render_gallery_item(log, ctx, image);
+            }
+        });
+}
+
+fn render_gallery_item(log: &Logger, ctx: &mut HtmlContext, image: &GalleryImage) {
+    let source_url =
+        ctx.handle()
+            .get_image_link(log, &image.source, ctx.info(), ctx.settings());
+
+    ctx.html()
+        .tag("figure")
+        .attr(attr!("class" => "wj-gallery-item"))
+        .contents(|ctx| {
+            let url = match &source_url {
+                Some(url) => url,
+                None => {
+                    debug!(log, "Gallery image URL unresolved, missing or error");
+
+                    let message = ctx.handle().get_message(
+                        log,
+                        ctx.language(),
+                        "image-context-bad",
+                    );
+
+                    ctx.html()
+                        .span()
+                        .attr(attr!("class" => "wj-error-inline"))
+                        .inner(log, message);
+
+                    return;
+                }
+            };
+
+            // Images without an explicit link point to the full-size image
+            let href = match &image.link {
+                Some(link) => normalize_link(link, ctx.handle()),
+                None => Cow::clone(url),
+            };
+
+            ctx.html().a().attr(attr!("href" => &href)).contents(|ctx| {
+                ctx.html().img().attr(attr!(
+                    "class" => "wj-gallery-image",
+                    "src" => url,
+                    "title" => image.title.as_deref().unwrap_or("");
+                        if image.title.is_some(),
+                    "loading" => "lazy",
+                    "crossorigin";;
+                    &image.attributes
+                ));
+            });
+
+            if let Some(caption) = &image.caption {
+                ctx.html()
+                    .tag("figcaption")
+                    .attr(attr!("class" => "wj-gallery-caption"))
+                    .inner(log, caption);
+            }
+        });
+}
+
+fn render_gallery_links(
+    log: &Logger,
+    ctx: &mut HtmlContext,
+    images: &[GalleryImage],
+    attributes: &AttributeMap,
+) {
+    debug!(log, "Local paths disabled, rendering gallery as links");
+
+    ctx.html()
+        .ul()
+        .attr(attr!("class" => "wj-gallery-links";; attributes))
+        .contents(|ctx| {
+            for image in images {
+                let source_url = ctx.handle().get_image_link(
+                    log,
+                    &image.source,
+                    ctx.info(),
+                    ctx.settings(),
+                );
+
+                ctx.html().li().contents(|ctx| match &source_url {
+                    Some(url) => {
+                        ctx.html()
+                            .a()
+                            .attr(attr!("href" => &url))
+                            .inner(log, image.label());
+                    }
+                    None => {
+                        ctx.html()
+                            .span()
+                            .attr(attr!("class" => "wj-error-inline"))
+                            .inner(log, image.label());
+                    }
+                });
+            }
+        });
+}
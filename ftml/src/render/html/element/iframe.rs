@@ -19,7 +19,27 @@
  */
 
 use super::prelude::*;
+use crate::settings::{HtmlBlockHosting, HtmlBlockSettings};
 use crate::tree::AttributeMap;
+use crate::url::{get_url_host, is_url};
+use regex::Regex;
+
+lazy_static! {
+    static ref SANDBOX_FLAG: Regex = Regex::new(r"^allow-[a-z\-]+$").unwrap();
+}
+
+/// The content security policy for inline HTML.
+///
+/// Scripts and styles may only be inline, and the snippet
+/// may not make any requests other than for images and media.
+const CONTENT_SECURITY_POLICY: &str = "default-src 'none'; script-src 'unsafe-inline'; \
+img-src https: data:; media-src https: data:; font-src https: data:";
+
+/// Script which reports the height of the document to the page, for resizing the iframe.
+const AUTO_HEIGHT_SCRIPT: &str = "(function(){function post(){parent.postMessage(\
+{type:'wj-html-height',height:document.documentElement.scrollHeight},'*')}\
+addEventListener('load',post);if(window.ResizeObserver){\
+new ResizeObserver(post).observe(document.documentElement)}})()";
 
 pub fn render_iframe(
     log: &Logger,
@@ -33,14 +53,88 @@ pub fn render_iframe(
         "url" => url,
     );
 
+    let settings = &ctx.settings().iframe;
+    if !settings.permits_url(url) {
+        render_iframe_blocked(log, ctx, url);
+        return;
+    }
+
+    let sandbox = settings.sandbox.join(" ");
+    let referrer_policy = settings.referrer_policy.clone();
+
     ctx.html().iframe().attr(attr!(
         "src" => url,
+        "sandbox" => &sandbox,
+        "referrerpolicy" => &referrer_policy,
+        "loading" => "lazy",
         "crossorigin";;
         attributes
     ));
 }
 
+fn render_iframe_blocked(log: &Logger, ctx: &mut HtmlContext, url: &str) {
+    debug!(log, "Iframe URL not permitted, rendering fallback"; "url" => url);
+
+    let blocked_message = ctx
+        .handle()
+        .get_message(log, ctx.language(), "iframe-blocked");
+
+    let link_message =
+        ctx.handle()
+            .get_message(log, ctx.language(), "iframe-open-new-tab");
+
+    ctx.html()
+        .div()
+        .attr(attr!("class" => "wj-iframe-blocked"))
+        .contents(|ctx| {
+            ctx.html()
+                .span()
+                .attr(attr!("class" => "wj-iframe-blocked-message"))
+                .inner(log, blocked_message);
+
+            // Only offer a link for web pages, never javascript: and the like.
+            if get_url_host(url).is_some() {
+                ctx.push_raw_str(" \u{2014} ");
+                ctx.html()
+                    .a()
+                    .attr(attr!(
+                        "href" => url,
+                        "target" => "_blank",
+                        "rel" => "noopener noreferrer",
+                    ))
+                    .inner(log, link_message);
+            }
+        });
+}
+
 pub fn render_html(log: &Logger, ctx: &mut HtmlContext, contents: &str) {
+    match ctx.settings().html_block.hosting {
+        HtmlBlockHosting::Inline => render_html_inline(log, ctx, contents),
+        HtmlBlockHosting::Remote => render_html_remote(log, ctx, contents),
+    }
+}
+
+fn render_html_inline(log: &Logger, ctx: &mut HtmlContext, contents: &str) {
+    info!(
+        log,
+        "Rendering html block (inline in sandboxed iframe)";
+        "contents" => contents,
+    );
+
+    let settings = &ctx.settings().html_block;
+    let sandbox = get_sandbox_flags(log, &settings.sandbox);
+    let document = build_html_document(log, settings, contents);
+    let auto_height = settings.auto_height;
+
+    ctx.html().iframe().attr(attr!(
+        "class" => "wj-html-block",
+        "sandbox" => &sandbox,
+        "srcdoc" => &document,
+        "data-auto-height"; if auto_height,
+    ));
+}
+
+fn render_html_remote(log: &Logger, ctx: &mut HtmlContext, contents: &str) {
     info!(
         log,
         "Rendering html block (submitting to remote for iframe)";
@@ -49,8 +143,143 @@ pub fn render_html(log: &Logger, ctx: &mut HtmlContext, contents: &str) {
 
     // Submit HTML to be hosted on wjfiles, then get back its URL for the iframe.
     let iframe_url = ctx.handle().post_html(log, ctx.info(), contents);
+    let sandbox = ctx.settings().html_block.sandbox.join(" ");
+
     ctx.html().iframe().attr(attr!(
+        "class" => "wj-html-block",
         "src" => &iframe_url,
+        "sandbox" => &sandbox,
         "crossorigin",
     ));
 }
+
+/// Produces the value of the `sandbox` attribute for inline HTML.
+///
+/// Since a `srcdoc` iframe otherwise shares the page's origin,
+/// `allow-same-origin` is never permitted here.
+fn get_sandbox_flags(log: &Logger, flags: &[String]) -> String {
+    let mut sandbox = String::new();
+
+    for flag in flags {
+        if flag == "allow-same-origin" || !SANDBOX_FLAG.is_match(flag) {
+            warn!(log, "Ignoring sandbox flag for inline HTML"; "flag" => flag);
+            continue;
+        }
+
+        if !sandbox.is_empty() {
+            sandbox.push(' ');
+        }
+
+        sandbox.push_str(flag);
+    }
+
+    sandbox
+}
+
+/// Builds the full document placed in the iframe's `srcdoc`.
+fn build_html_document(
+    log: &Logger,
+    settings: &HtmlBlockSettings,
+    contents: &str,
+) -> String {
+    // Only permit stylesheet URLs which cannot break out of the CSP or attribute
+    let stylesheet = settings.stylesheet.as_deref().filter(|url| {
+        let valid = is_url(url)
+            && !url.contains(|c: char| {
+                c.is_whitespace() || matches!(c, ';' | ',' | '"' | '\'' | '<' | '>')
+            });
+
+        if !valid {
+            warn!(log, "Ignoring invalid stylesheet URL for inline HTML"; "url" => url);
+        }
+
+        valid
+    });
+
+    let mut document = str!("<!DOCTYPE html><html><head><meta charset=\"utf-8\">");
+
+    // Content security policy
+    document.push_str("<meta http-equiv=\"Content-Security-Policy\" content=\"");
+    document.push_str(CONTENT_SECURITY_POLICY);
+    document.push_str("; style-src 'unsafe-inline'");
+
+    if let Some(url) = stylesheet {
+        document.push(' ');
+        document.push_str(url);
+    }
+
+    document.push_str("\">");
+
+    // Site stylesheet
+    if let Some(url) = stylesheet {
+        document.push_str("<link rel=\"stylesheet\" href=\"");
+        document.push_str(url);
+        document.push_str("\">");
+    }
+
+    // Body, with the user's HTML
+    document.push_str("</head><body>");
+    document.push_str(contents);
+
+    if settings.auto_height {
+        document.push_str("<script>");
+        document.push_str(AUTO_HEIGHT_SCRIPT);
+        document.push_str("</script>");
+    }
+
+    document.push_str("</body></html>");
+    document
+}
+
+#[test]
+fn inline_html() {
+    let log = crate::build_logger();
+
+    // Sandbox flags
+    let flags = vec![
+        str!("allow-scripts"),
+        str!("allow-same-origin"),
+        str!("allow-popups"),
+        str!("javascript:alert(1)"),
+    ];
+    assert_eq!(
+        get_sandbox_flags(&log, &flags),
+        "allow-scripts allow-popups",
+        "Unsafe or invalid sandbox flags weren't removed",
+    );
+
+    // Document
+    let mut settings = HtmlBlockSettings {
+        stylesheet: Some(str!("https://example.com/site.css")),
+        auto_height: true,
+        ..HtmlBlockSettings::default()
+    };
+
+    let document = build_html_document(&log, &settings, "<p>Test</p>");
+    assert!(
+        document.contains("style-src 'unsafe-inline' https://example.com/site.css\""),
+        "Stylesheet not permitted in content security policy",
+    );
+    assert!(
+        document
+            .contains("<link rel=\"stylesheet\" href=\"https://example.com/site.css\">"),
+        "Stylesheet not included in document",
+    );
+    assert!(
+        document.contains("<body><p>Test</p><script>"),
+        "Auto-height script not included after contents",
+    );
+
+    settings.stylesheet = Some(str!("https://example.com/x.css; script-src *"));
+    settings.auto_height = false;
+
+    let document = build_html_document(&log, &settings, "<p>Test</p>");
+    assert!(
+        !document.contains("example.com"),
+        "Invalid stylesheet URL was included",
+    );
+    assert!(
+        !document.contains("<script>"),
+        "Auto-height script included when disabled",
+    );
+}
@@ -53,7 +53,9 @@ pub fn render_image(
         .get_image_link(log, source, ctx.info(), ctx.settings());
     match source_url {
         // Found URL
-        Some(url) => render_image_element(log, ctx, &url, link, alignment, attributes),
+        Some(url) => {
+            render_image_element(log, ctx, source, &url, link, alignment, attributes)
+        }
 
         // Missing or error
         None => render_image_missing(log, ctx),
@@ -63,6 +65,7 @@ pub fn render_image(
 fn render_image_element(
     log: &Logger,
     ctx: &mut HtmlContext,
+    source: &ImageSource,
     url: &str,
     link: &Option<LinkLocation>,
     alignment: Option<FloatAlignment>,
@@ -79,6 +82,11 @@ fn render_image_element(
         None => ("", ""),
     };
 
+    // Images must have alternative text, so use the file name if none was given.
+    // This is what Wikidot does, and is better than nothing for screen readers.
+    let has_alt = attributes.get().contains_key("alt");
+    let default_alt = image_file_name(source);
+
     ctx.html()
         .div()
         .attr(attr!(
@@ -89,6 +97,7 @@ fn render_image_element(
                 ctx.html().img().attr(attr!(
                     "class" => "wj-image",
                     "src" => url,
+                    "alt" => default_alt; if !has_alt,
                     "crossorigin";;
                     attributes
                 ));
@@ -107,6 +116,24 @@ fn render_image_element(
         });
 }
 
+/// Gets the file name of an image, for use as its default alt text.
+fn image_file_name<'a>(source: &'a ImageSource) -> &'a str {
+    match source {
+        ImageSource::File1 { file }
+        | ImageSource::File2 { file, .. }
+        | ImageSource::File3 { file, .. } => file,
+        ImageSource::Url(url) => {
+            // Last path segment, without any query or fragment
+            let path = match url.find(|c| c == '?' || c == '#') {
+                Some(index) => &url[..index],
+                None => url,
+            };
+
+            path.rsplit('/').next().unwrap_or(path)
+        }
+    }
+}
+
 fn render_image_missing(log: &Logger, ctx: &mut HtmlContext) {
     debug!(log, "Image URL unresolved, missing or error");
 
@@ -38,9 +38,11 @@ pub fn render_include(
     );
 
     ctx.variables_mut().push_scope(variables);
+    ctx.enter_include();
 
     render_elements(log, ctx, elements);
 
+    ctx.exit_include();
     ctx.variables_mut().pop_scope();
 }
 
@@ -21,6 +21,7 @@
 use super::prelude::*;
 use crate::tree::{AnchorTarget, AttributeMap, Element, LinkLabel, LinkLocation};
 use crate::url::normalize_link;
+use std::borrow::Cow;
 
 pub fn render_anchor(
     log: &Logger,
@@ -69,7 +70,19 @@ pub fn render_link(
     // Add to backlinks
     ctx.add_link(link);
 
-    let url = normalize_link(link, ctx.handle());
+    // Links to missing pages go to the page creation form instead
+    let missing = match link {
+        LinkLocation::Page(page_ref) if !ctx.page_exists(page_ref) => {
+            Some(handle.get_page_create_url(log, page_ref))
+        }
+        _ => None,
+    };
+
+    let url = match missing {
+        Some(ref create_url) => Cow::Borrowed(create_url.as_str()),
+        None => normalize_link(link, handle),
+    };
+
     let target_value = match target {
         Some(target) => target.html_attr(),
         None => "",
@@ -78,6 +91,7 @@ pub fn render_link(
     let mut tag = ctx.html().a();
     tag.attr(attr!(
         "href" => &url,
+        "class" => "wj-link-missing"; if missing.is_some(),
         "target" => target_value; if target.is_some(),
     ));
 
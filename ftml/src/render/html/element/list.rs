@@ -45,9 +45,14 @@ pub fn render_list(
                     elements,
                     attributes,
                 } => {
+                    let auto_direction = ctx.auto_direction(attributes);
+
                     ctx.html()
                         .li()
-                        .attr(attr!(;; attributes))
+                        .attr(attr!(
+                            "dir" => "auto"; if auto_direction;;
+                            attributes,
+                        ))
                         .inner(log, elements);
                 }
                 ListItem::SubList { element } => {
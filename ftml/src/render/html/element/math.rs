@@ -24,6 +24,7 @@ use std::num::NonZeroUsize;
 
 cfg_if! {
     if #[cfg(feature = "mathml")] {
+        use crate::render::html::escape::escape;
         use latex2mathml::{latex_to_mathml, DisplayStyle};
     } else {
         /// Mocked version of the enum from `latex2mathml`.
@@ -84,12 +85,22 @@ fn render_latex(
         DisplayStyle::Inline => ("span", "wj-math-inline", "wj-error-inline"),
     };
 
+    // Generate MathML, if enabled
+    let mathml = convert_mathml(log, latex_source, display);
+
     // Outer container
+    //
+    // MathML is read by screen readers, using the LaTeX source as its alternative text.
+    // Otherwise the container itself is labelled with the source.
+    let has_mathml = matches!(mathml, Some(Ok(_)));
+
     ctx.html()
         .tag(html_tag)
         .attr(attr!(
             "class" => "wj-math " wj_type,
             "data-name" => name.unwrap_or(""); if name.is_some(),
+            "role" => "math"; if !has_mathml,
+            "aria-label" => latex_source; if !has_mathml,
         ))
         .contents(|ctx| {
             // Add equation index
@@ -129,46 +140,88 @@ fn render_latex(
                 .inner(log, latex_source);
 
             // Add generated MathML
-            cfg_if! {
-                if #[cfg(feature = "mathml")] {
-                    match latex_to_mathml(latex_source, display) {
-                        Ok(mathml) => {
-                            info!(
-                                log,
-                                "Processed LaTeX -> MathML";
-                                "display" => str!(display),
-                                "mathml" => &mathml,
-                            );
-
-                            // Inject MathML elements
-                            ctx.html()
-                                .element("wj-math-ml")
-                                .attr(attr!(
-                                    "class" => "wj-math-ml",
-                                ))
-                                .contents(|ctx| ctx.push_raw_str(&mathml));
-                        }
-                        Err(error) => {
-                            warn!(
-                                log,
-                                "Error processing LaTeX -> MathML";
-                                "display" => str!(display),
-                                "error" => str!(error),
-                            );
-
-                            let error = str!(error);
-
-                            ctx.html()
-                                .span()
-                                .attr(attr!("class" => _error_type))
-                                .inner(log, error);
-                        }
-                    }
+            match &mathml {
+                Some(Ok(mathml)) => {
+                    // Inject MathML elements
+                    ctx.html()
+                        .element("wj-math-ml")
+                        .attr(attr!(
+                            "class" => "wj-math-ml",
+                        ))
+                        .contents(|ctx| ctx.push_raw_str(mathml));
+                }
+                Some(Err(error)) => {
+                    ctx.html()
+                        .span()
+                        .attr(attr!("class" => _error_type))
+                        .inner(log, error);
                 }
+                None => (),
             }
         });
 }
 
+/// Converts the LaTeX source into MathML.
+///
+/// Returns `None` if MathML support is disabled,
+/// or the error message if conversion failed.
+fn convert_mathml(
+    log: &Logger,
+    latex_source: &str,
+    display: DisplayStyle,
+) -> Option<Result<String, String>> {
+    cfg_if! {
+        if #[cfg(feature = "mathml")] {
+            match latex_to_mathml(latex_source, display) {
+                Ok(mathml) => {
+                    info!(
+                        log,
+                        "Processed LaTeX -> MathML";
+                        "display" => str!(display),
+                        "mathml" => &mathml,
+                    );
+
+                    Some(Ok(add_alt_text(&mathml, latex_source)))
+                }
+                Err(error) => {
+                    warn!(
+                        log,
+                        "Error processing LaTeX -> MathML";
+                        "display" => str!(display),
+                        "error" => str!(error),
+                    );
+
+                    Some(Err(str!(error)))
+                }
+            }
+        } else {
+            let _ = (log, latex_source, display);
+
+            None
+        }
+    }
+}
+
+/// Adds the LaTeX source as the `alttext` of the root `<math>` element.
+#[cfg(feature = "mathml")]
+fn add_alt_text(mathml: &str, latex_source: &str) -> String {
+    const MATH_TAG: &str = "<math";
+
+    match mathml.strip_prefix(MATH_TAG) {
+        Some(rest) => {
+            let mut output =
+                String::with_capacity(mathml.len() + latex_source.len() + 12);
+            output.push_str(MATH_TAG);
+            output.push_str(" alttext=\"");
+            escape(&mut output, latex_source);
+            output.push('"');
+            output.push_str(rest);
+            output
+        }
+        None => str!(mathml),
+    }
+}
+
 pub fn render_equation_reference(log: &Logger, ctx: &mut HtmlContext, name: &str) {
     info!(
         log,
@@ -0,0 +1,90 @@
+/*
+ * render/html/element/media.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::prelude::*;
+use crate::tree::Media;
+
+pub fn render_media(log: &Logger, ctx: &mut HtmlContext, media: &Media) {
+    info!(
+        log,
+        "Rendering media element";
+        "media-type" => media.mtype.name(),
+        "source" => media.source.name(),
+        "controls" => media.controls,
+        "loop" => media.looped,
+        "autoplay" => media.autoplay,
+    );
+
+    let handle = ctx.handle();
+    let source_url = handle.get_file_link(log, &media.source, ctx.info(), ctx.settings());
+    let source_url = match source_url {
+        Some(url) => url,
+        None => return render_media_missing(log, ctx),
+    };
+
+    // The poster and captions are optional, so they are omitted if unresolved
+    let poster_url = media
+        .poster
+        .as_ref()
+        .and_then(|source| handle.get_file_link(log, source, ctx.info(), ctx.settings()));
+
+    let captions_url = media
+        .captions
+        .as_ref()
+        .and_then(|source| handle.get_file_link(log, source, ctx.info(), ctx.settings()));
+
+    ctx.html()
+        .tag(media.mtype.html_tag())
+        .attr(attr!(
+            "class" => media.mtype.html_class(),
+            "src" => &source_url,
+            "poster" => poster_url.as_deref().unwrap_or("");
+                if poster_url.is_some(),
+            "controls"; if media.controls,
+            "loop"; if media.looped,
+            "autoplay"; if media.autoplay,
+            "muted"; if media.autoplay,
+            "preload" => "metadata",
+            "crossorigin";;
+            &media.attributes
+        ))
+        .contents(|ctx| {
+            if let Some(url) = &captions_url {
+                ctx.html().tag("track").attr(attr!(
+                    "kind" => "captions",
+                    "src" => url,
+                    "default",
+                ));
+            }
+        });
+}
+
+fn render_media_missing(log: &Logger, ctx: &mut HtmlContext) {
+    debug!(log, "Media URL unresolved, missing or error");
+
+    let message = ctx
+        .handle()
+        .get_message(log, ctx.language(), "media-context-bad");
+
+    ctx.html()
+        .span()
+        .attr(attr!("class" => "wj-error-inline"))
+        .inner(log, message);
+}
@@ -20,12 +20,14 @@
 
 //! Module that implements HTML rendering for `Element` and its children.
 
+mod bibliography;
 mod collapsible;
 mod container;
 mod date;
 mod definition_list;
 mod embed;
 mod footnotes;
+mod gallery;
 mod iframe;
 mod image;
 mod include;
@@ -33,6 +35,7 @@ mod input;
 mod link;
 mod list;
 mod math;
+mod media;
 mod table;
 mod tabs;
 mod text;
@@ -48,12 +51,14 @@ mod prelude {
     pub use crate::tree::{Element, SyntaxTree};
 }
 
+use self::bibliography::{render_bibcite, render_bibliography};
 use self::collapsible::{render_collapsible, Collapsible};
 use self::container::{render_color, render_container};
 use self::date::render_date;
 use self::definition_list::render_definition_list;
 use self::embed::render_embed;
 use self::footnotes::{render_footnote, render_footnote_block};
+use self::gallery::render_gallery;
 use self::iframe::{render_html, render_iframe};
 use self::image::render_image;
 use self::include::{render_include, render_variable};
@@ -61,6 +66,7 @@ use self::input::{render_checkbox, render_radio_button};
 use self::link::{render_anchor, render_link};
 use self::list::render_list;
 use self::math::{render_equation_reference, render_math_block, render_math_inline};
+use self::media::render_media;
 use self::table::render_table;
 use self::tabs::render_tabview;
 use self::text::{render_code, render_email, render_wikitext_raw};
@@ -96,7 +102,10 @@ pub fn render_element(log: &Logger, ctx: &mut HtmlContext, element: &Element) {
             ctx.handle()
                 .render_module(log, ctx.buffer(), module, ModuleRenderMode::Html);
         }
-        Element::Text(text) => ctx.push_escaped(text),
+        Element::Text(text) => {
+            let text = ctx.typography().text(element, text);
+            ctx.push_escaped(text);
+        }
         Element::Raw(text) => render_wikitext_raw(log, ctx, text),
         Element::Variable(name) => render_variable(log, ctx, name),
         Element::Email(email) => render_email(log, ctx, email),
@@ -118,6 +127,12 @@ pub fn render_element(log: &Logger, ctx: &mut HtmlContext, element: &Element) {
             alignment,
             attributes,
         } => render_image(log, ctx, source, link, *alignment, attributes),
+        Element::Gallery {
+            images,
+            size,
+            attributes,
+        } => render_gallery(log, ctx, images, *size, attributes),
+        Element::Media(media) => render_media(log, ctx, media),
         Element::List {
             ltype,
             items,
@@ -163,6 +178,12 @@ pub fn render_element(log: &Logger, ctx: &mut HtmlContext, element: &Element) {
                 render_footnote_block(log, ctx, ref_cow!(title));
             }
         }
+        Element::BibliographyCite { label } => render_bibcite(log, ctx, label),
+        Element::BibliographyBlock { index, title, hide } => {
+            if !*hide {
+                render_bibliography(log, ctx, *index, ref_cow!(title));
+            }
+        }
         Element::User { name, show_avatar } => render_user(log, ctx, name, *show_avatar),
         Element::Date {
             value,
@@ -53,6 +53,8 @@ pub fn render_table(log: &Logger, ctx: &mut HtmlContext, table: &Table) {
                                     str_write!(column_span_buf, "{}", cell.column_span);
                                 }
 
+                                let auto_direction = ctx.auto_direction(&cell.attributes);
+
                                 ctx.html()
                                     .table_cell(cell.header)
                                     .attr(attr!(
@@ -62,7 +64,10 @@ pub fn render_table(log: &Logger, ctx: &mut HtmlContext, table: &Table) {
 
                                         // Add alignment if specified
                                         "class" => align_class;
-                                            if cell.align.is_some();;
+                                            if cell.align.is_some(),
+
+                                        // Let cell contents choose their direction
+                                        "dir" => "auto"; if auto_direction;;
 
                                         &cell.attributes,
                                     ))
@@ -58,7 +58,6 @@ pub fn render_tabview(log: &Logger, ctx: &mut HtmlContext, tabs: &[Tab]) {
                                 "class" => "wj-tabs-button",
                                 "id" => &button_ids[i],
                                 "role" => "tab",
-                                "aria-label" => &tab.label,
                                 "aria-selected" => tab_selected,
                                 "aria-controls" => &tab_ids[i],
                                 "tabindex" => tab_index,
@@ -19,6 +19,7 @@
  */
 
 use super::prelude::*;
+use crate::settings::HtmlSyntax;
 use crate::tree::{Alignment, AttributeMap, FloatAlignment};
 
 pub fn render_table_of_contents(
@@ -52,16 +53,19 @@ pub fn render_table_of_contents(
         ))
         .contents(|ctx| {
             // TOC buttons
-            ctx.html()
-                .div()
-                .attr(attr!("id" => "wj-toc-action-bar"; if use_true_ids))
-                .contents(|ctx| {
-                    // TODO button
-                    ctx.html().a().attr(attr!(
-                        "href" => "javascript:;",
-                        "onclick" => "WIKIJUMP.page.listeners.foldToc(event)",
-                    ));
-                });
+            // Scripts aren't permitted in EPUBs, so there is nothing to fold with.
+            if ctx.settings().html_syntax != HtmlSyntax::Epub {
+                ctx.html()
+                    .div()
+                    .attr(attr!("id" => "wj-toc-action-bar"; if use_true_ids))
+                    .contents(|ctx| {
+                        // TODO button
+                        ctx.html().a().attr(attr!(
+                            "href" => "javascript:;",
+                            "onclick" => "WIKIJUMP.page.listeners.foldToc(event)",
+                        ));
+                    });
+            }
 
             // TOC Heading
             let table_of_contents_title =
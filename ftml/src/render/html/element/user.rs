@@ -64,8 +64,9 @@ pub fn render_user(log: &Logger, ctx: &mut HtmlContext, name: &str, show_avatar:
                             ));
                         }
 
+                        // Isolated, since user names may be in either direction
                         ctx.html()
-                            .span()
+                            .tag("bdi")
                             .attr(attr!("class" => "wj-user-info-name"))
                             .inner(log, &info.user_name);
                     });
@@ -96,7 +97,7 @@ pub fn render_user(log: &Logger, ctx: &mut HtmlContext, name: &str, show_avatar:
                         }
 
                         ctx.html()
-                            .span()
+                            .tag("bdi")
                             .attr(attr!("class" => "wj-user-info-name"))
                             .inner(log, name);
                     });
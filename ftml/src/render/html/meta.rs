@@ -20,7 +20,7 @@
 
 use super::escape as html;
 
-#[derive(Serialize, Deserialize, Debug, Copy, Clone, Hash, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Copy, Clone, Hash, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
 pub enum HtmlMetaType {
     Name,
@@ -40,7 +40,7 @@ impl HtmlMetaType {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
 pub struct HtmlMeta {
     pub tag_type: HtmlMetaType,
     pub name: String,
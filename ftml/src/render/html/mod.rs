@@ -26,32 +26,85 @@ mod attributes;
 mod builder;
 mod context;
 mod element;
-mod escape;
+pub(crate) mod escape;
 mod meta;
 mod output;
+mod page_links;
 mod random;
 mod render;
+mod social;
 
 pub use self::meta::{HtmlMeta, HtmlMetaType};
-pub use self::output::HtmlOutput;
+pub use self::output::{HtmlOutput, HtmlStreamOutput};
 
 #[cfg(test)]
 use super::prelude;
 
 use self::attributes::AddedAttributes;
 use self::context::HtmlContext;
+use self::social::{get_social_metadata, SocialMetadata};
 use crate::data::PageInfo;
 use crate::log::prelude::*;
-use crate::render::{Handle, Render};
+use crate::render::bidi::TextDirection;
+use crate::render::{typography, Handle, Render};
 use crate::settings::WikitextSettings;
 use crate::tree::SyntaxTree;
+use std::fmt;
+use std::io;
 
 #[derive(Debug)]
 pub struct HtmlRender;
 
+impl HtmlRender {
+    /// Renders the syntax tree, writing the body out as it is generated.
+    ///
+    /// This avoids holding the entire body in memory, which is useful for
+    /// large pages being sent straight to a file or socket. The output is
+    /// the same as the body from [`render()`](Render::render), and
+    /// everything else is returned once rendering has finished.
+    pub fn render_to(
+        &self,
+        log: &Logger,
+        tree: &SyntaxTree,
+        page_info: &PageInfo,
+        settings: &WikitextSettings,
+        writer: &mut dyn fmt::Write,
+    ) -> Result<HtmlStreamOutput, fmt::Error> {
+        let output = render(log, tree, page_info, settings, Some(writer))?;
+        Ok(output.into())
+    }
+
+    /// Renders the syntax tree, writing the body out as UTF-8 as it is generated.
+    ///
+    /// See [`render_to()`](Self::render_to).
+    /// The writer is not flushed, and no buffering is added beyond that
+    /// done during rendering.
+    pub fn render_to_io(
+        &self,
+        log: &Logger,
+        tree: &SyntaxTree,
+        page_info: &PageInfo,
+        settings: &WikitextSettings,
+        writer: &mut dyn io::Write,
+    ) -> io::Result<HtmlStreamOutput> {
+        let mut adapter = IoWriter {
+            inner: writer,
+            error: None,
+        };
+
+        match self.render_to(log, tree, page_info, settings, &mut adapter) {
+            Ok(output) => Ok(output),
+            Err(_) => Err(adapter.error.unwrap_or_else(|| {
+                io::Error::new(io::ErrorKind::Other, "formatter error")
+            })),
+        }
+    }
+}
+
 impl Render for HtmlRender {
     type Output = HtmlOutput;
 
+    #[inline]
     fn render(
         &self,
         log: &Logger,
@@ -59,38 +112,91 @@ impl Render for HtmlRender {
         page_info: &PageInfo,
         settings: &WikitextSettings,
     ) -> HtmlOutput {
-        info!(
-            log,
-            "Rendering syntax tree";
-            "target" => "html",
-            "site" => page_info.site.as_ref(),
-            "page" => page_info.page.as_ref(),
-            "category" => match &page_info.category {
-                Some(category) => category.as_ref(),
-                None => "_default",
-            },
-        );
-
-        let mut ctx = HtmlContext::new(
-            page_info,
-            &Handle,
-            settings,
-            &tree.table_of_contents,
-            &tree.footnotes,
-        );
-
-        // Add styles
-        for style in &tree.styles {
-            ctx.add_style(str!(style));
-        }
+        render(log, tree, page_info, settings, None)
+            .expect("Rendering without a sink cannot fail")
+    }
+}
+
+fn render(
+    log: &Logger,
+    tree: &SyntaxTree,
+    page_info: &PageInfo,
+    settings: &WikitextSettings,
+    sink: Option<&mut dyn fmt::Write>,
+) -> Result<HtmlOutput, fmt::Error> {
+    info!(
+        log,
+        "Rendering syntax tree";
+        "target" => "html",
+        "streaming" => sink.is_some(),
+        "site" => page_info.site.as_ref(),
+        "page" => page_info.page.as_ref(),
+        "category" => match &page_info.category {
+            Some(category) => category.as_ref(),
+            None => "_default",
+        },
+    );
+
+    let typography = typography::get_substitutions(log, tree, &settings.typography);
+    let missing_pages = page_links::get_missing_pages(log, &Handle, tree);
+    let mut ctx = HtmlContext::new(
+        page_info,
+        &Handle,
+        settings,
+        &tree.table_of_contents,
+        &tree.footnotes,
+        &tree.bibliographies,
+        &typography,
+    );
+
+    if let Some(writer) = sink {
+        ctx.set_sink(writer);
+    }
+
+    ctx.set_missing_pages(missing_pages);
+
+    // Add metadata for previews
+    let SocialMetadata { mut meta, json_ld } =
+        get_social_metadata(log, &Handle, tree, &typography, page_info, settings);
 
-        // Crawl through elements and generate HTML
-        ctx.html()
-            .element("wj-body")
-            .attr(attr!("class" => "wj-body"))
-            .inner(log, &tree.elements);
+    ctx.add_meta(&mut meta);
+    ctx.set_json_ld(json_ld);
+
+    // Add styles
+    for style in &tree.styles {
+        ctx.add_style(str!(style));
+    }
+
+    // Crawl through elements and generate HTML
+    //
+    // The language and its direction are only set if the language is known.
+    let language = str!(ctx.language());
+    let direction = ctx.direction().map(TextDirection::html_value);
+
+    ctx.html()
+        .element("wj-body")
+        .attr(attr!(
+            "class" => "wj-body",
+            "lang" => &language; if direction.is_some(),
+            "dir" => direction.unwrap_or_default(); if direction.is_some(),
+        ))
+        .inner(log, &tree.elements);
+
+    // Build and return HtmlOutput, writing out any remaining body
+    ctx.finish()
+}
+
+/// Adapts an [`io::Write`] for rendering, keeping the actual error if one occurs.
+struct IoWriter<'w> {
+    inner: &'w mut dyn io::Write,
+    error: Option<io::Error>,
+}
 
-        // Build and return HtmlOutput
-        ctx.into()
+impl fmt::Write for IoWriter<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.inner.write_all(s.as_bytes()).map_err(|error| {
+            self.error = Some(error);
+            fmt::Error
+        })
     }
 }
@@ -21,10 +21,43 @@
 use super::meta::HtmlMeta;
 use crate::data::Backlinks;
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
 pub struct HtmlOutput {
     pub body: String,
     pub styles: Vec<String>,
     pub meta: Vec<HtmlMeta>,
+    pub json_ld: Option<String>,
     pub backlinks: Backlinks<'static>,
 }
+
+/// The results of rendering HTML to a writer.
+///
+/// This is everything in [`HtmlOutput`] except the body,
+/// which has already been written out.
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+pub struct HtmlStreamOutput {
+    pub styles: Vec<String>,
+    pub meta: Vec<HtmlMeta>,
+    pub json_ld: Option<String>,
+    pub backlinks: Backlinks<'static>,
+}
+
+impl From<HtmlOutput> for HtmlStreamOutput {
+    #[inline]
+    fn from(output: HtmlOutput) -> HtmlStreamOutput {
+        let HtmlOutput {
+            styles,
+            meta,
+            json_ld,
+            backlinks,
+            ..
+        } = output;
+
+        HtmlStreamOutput {
+            styles,
+            meta,
+            json_ld,
+            backlinks,
+        }
+    }
+}
@@ -0,0 +1,83 @@
+/*
+ * render/html/page_links.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Looks up which pages linked to from a syntax tree exist.
+//!
+//! Links to missing pages are rendered differently, pointing to the page
+//! creation form. All page links are gathered up front, so the handle
+//! can answer for them in a single batch rather than per-link.
+
+use crate::data::PageRef;
+use crate::log::prelude::*;
+use crate::render::Handle;
+use crate::tree::visit::{walk_element, Visitor};
+use crate::tree::{Element, LinkLocation, SyntaxTree};
+use std::collections::HashSet;
+
+/// Gets the set of pages linked to in this tree which do not exist.
+pub fn get_missing_pages(
+    log: &Logger,
+    handle: &Handle,
+    tree: &SyntaxTree,
+) -> HashSet<PageRef<'static>> {
+    let pages = collect_page_links(tree);
+    if pages.is_empty() {
+        return HashSet::new();
+    }
+
+    let exists = handle.get_pages_exist(log, &pages);
+    debug_assert_eq!(pages.len(), exists.len(), "Existence results don't match");
+
+    pages
+        .into_iter()
+        .zip(exists)
+        .filter(|(_, exists)| !exists)
+        .map(|(page, _)| page.to_owned())
+        .collect()
+}
+
+/// Gathers all unique `LinkLocation::Page` targets in the tree, in order of appearance.
+pub fn collect_page_links<'a>(tree: &'a SyntaxTree<'a>) -> Vec<&'a PageRef<'a>> {
+    let mut collector = PageLinkCollector::default();
+    collector.visit_syntax_tree(tree);
+    collector.pages
+}
+
+#[derive(Debug, Default)]
+struct PageLinkCollector<'a> {
+    pages: Vec<&'a PageRef<'a>>,
+    seen: HashSet<&'a PageRef<'a>>,
+}
+
+impl<'a> Visitor<'a> for PageLinkCollector<'a> {
+    fn visit_element(&mut self, element: &'a Element<'a>) {
+        if let Element::Link {
+            link: LinkLocation::Page(page_ref),
+            ..
+        } = element
+        {
+            if self.seen.insert(page_ref) {
+                self.pages.push(page_ref);
+            }
+        }
+
+        walk_element(self, element);
+    }
+}
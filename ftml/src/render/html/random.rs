@@ -42,7 +42,10 @@ impl Default for Random {
             if #[cfg(test)] {
                 let rng = SmallRng::from_seed(TEST_RANDOM_SEED);
             } else {
-                let rng = SmallRng::from_entropy();
+                // Seeding from the thread's generator avoids a system call
+                // every time, which adds up when rendering many small inputs.
+                let rng = SmallRng::from_rng(thread_rng())
+                    .expect("Thread random number generator failed");
             }
         }
 
@@ -0,0 +1,300 @@
+/*
+ * render/html/social.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Metadata used for previews when a page is shared, such as on social media.
+//!
+//! This produces Open Graph and Twitter Card tags, and optionally a
+//! schema.org `Article` in JSON-LD. Besides the page information, it uses
+//! the first paragraph of the page as the description, and the first image
+//! as the preview image.
+
+use super::meta::{HtmlMeta, HtmlMetaType};
+use crate::data::PageInfo;
+use crate::log::prelude::*;
+use crate::render::typography::Typography;
+use crate::render::{text::TextRender, Handle};
+use crate::settings::WikitextSettings;
+use crate::tree::visit::{walk_container, walk_element, Visitor};
+use crate::tree::{Container, ContainerType, Element, ImageSource, SyntaxTree};
+use crate::url::is_url;
+use serde_json::json;
+
+/// The maximum length of the description, in characters.
+const DESCRIPTION_LENGTH: usize = 200;
+
+/// How far back to look for a word boundary when shortening the description,
+/// in characters.
+const DESCRIPTION_BACKOFF: usize = 20;
+
+#[derive(Debug, Clone)]
+pub struct SocialMetadata {
+    pub meta: Vec<HtmlMeta>,
+    pub json_ld: Option<String>,
+}
+
+pub fn get_social_metadata(
+    log: &Logger,
+    handle: &Handle,
+    tree: &SyntaxTree,
+    typography: &Typography,
+    info: &PageInfo,
+    settings: &WikitextSettings,
+) -> SocialMetadata {
+    info!(log, "Building social metadata for page");
+
+    let mut collector = PreviewCollector::default();
+    collector.visit_elements(&tree.elements);
+
+    let title = match info.alt_title {
+        Some(ref alt_title) => format!("{} - {}", info.title, alt_title),
+        None => str!(info.title),
+    };
+
+    let description = collector
+        .paragraph
+        .map(|elements| {
+            let text = TextRender
+                .render_partial_typography(log, elements, info, settings, typography);
+            build_description(&text)
+        })
+        .filter(|description| !description.is_empty());
+
+    // Only absolute URLs are of use to other sites
+    let image = collector
+        .image
+        .and_then(|source| handle.get_image_link(log, source, info, settings))
+        .filter(|url| is_url(url))
+        .map(|url| url.into_owned());
+
+    let url = handle.get_page_url(log, info);
+
+    // Build tags
+    let mut meta = Vec::new();
+    let mut add_meta = |tag_type, name: &str, value: &str| {
+        meta.push(HtmlMeta {
+            tag_type,
+            name: str!(name),
+            value: str!(value),
+        });
+    };
+
+    add_meta(HtmlMetaType::Property, "og:type", "article");
+    add_meta(HtmlMetaType::Property, "og:title", &title);
+    add_meta(HtmlMetaType::Property, "og:url", &url);
+
+    if let Some(ref description) = description {
+        add_meta(HtmlMetaType::Property, "og:description", description);
+    }
+
+    if let Some(ref image) = image {
+        add_meta(HtmlMetaType::Property, "og:image", image);
+    }
+
+    let card = if image.is_some() {
+        "summary_large_image"
+    } else {
+        "summary"
+    };
+
+    add_meta(HtmlMetaType::Name, "twitter:card", card);
+    add_meta(HtmlMetaType::Name, "twitter:title", &title);
+
+    if let Some(ref description) = description {
+        add_meta(HtmlMetaType::Name, "twitter:description", description);
+    }
+
+    if let Some(ref image) = image {
+        add_meta(HtmlMetaType::Name, "twitter:image", image);
+    }
+
+    // Build JSON-LD
+    let json_ld = if settings.enable_json_ld {
+        let mut article = json!({
+            "@context": "https://schema.org",
+            "@type": "Article",
+            "headline": info.title,
+            "url": url,
+            "keywords": info.tags,
+        });
+
+        if let Some(ref alt_title) = info.alt_title {
+            article["alternativeHeadline"] = json!(alt_title);
+        }
+
+        if let Some(description) = description {
+            article["description"] = json!(description);
+        }
+
+        if let Some(image) = image {
+            article["image"] = json!(image);
+        }
+
+        Some(escape_json_ld(&article.to_string()))
+    } else {
+        None
+    };
+
+    SocialMetadata { meta, json_ld }
+}
+
+/// Collapses whitespace and shortens the text to a reasonable length for a preview.
+///
+/// The text is cut off by characters, backing off to the end of the previous word
+/// if there is one nearby. Text without spaces, such as Chinese or Japanese, is
+/// cut off in the middle instead.
+fn build_description(text: &str) -> String {
+    let collapsed = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    let end = match collapsed.char_indices().nth(DESCRIPTION_LENGTH) {
+        Some((end, _)) => end,
+        None => return collapsed,
+    };
+
+    let mut description = &collapsed[..end];
+
+    if !collapsed[end..].starts_with(' ') {
+        let backoff_start = description
+            .char_indices()
+            .rev()
+            .nth(DESCRIPTION_BACKOFF - 1)
+            .map_or(0, |(index, _)| index);
+
+        if let Some(index) = description[backoff_start..].rfind(' ') {
+            description = &description[..backoff_start + index];
+        }
+    }
+
+    format!("{}\u{2026}", description.trim_end())
+}
+
+/// Escapes characters which could close the `<script>` tag the JSON-LD is placed in.
+///
+/// The escapes are still valid JSON, so the data is unchanged.
+fn escape_json_ld(json: &str) -> String {
+    let mut escaped = String::with_capacity(json.len());
+
+    for ch in json.chars() {
+        match ch {
+            '<' => escaped.push_str("\\u003c"),
+            '>' => escaped.push_str("\\u003e"),
+            '&' => escaped.push_str("\\u0026"),
+            _ => escaped.push(ch),
+        }
+    }
+
+    escaped
+}
+
+/// Finds the first paragraph with text, and the first image, in the tree.
+#[derive(Debug, Default)]
+struct PreviewCollector<'a> {
+    paragraph: Option<&'a [Element<'a>]>,
+    image: Option<&'a ImageSource<'a>>,
+}
+
+impl<'a> PreviewCollector<'a> {
+    fn done(&self) -> bool {
+        self.paragraph.is_some() && self.image.is_some()
+    }
+
+    fn add_image(&mut self, source: &'a ImageSource<'a>) {
+        if self.image.is_none() {
+            self.image = Some(source);
+        }
+    }
+}
+
+impl<'a> Visitor<'a> for PreviewCollector<'a> {
+    fn visit_elements(&mut self, elements: &'a [Element<'a>]) {
+        for element in elements {
+            if self.done() {
+                return;
+            }
+
+            self.visit_element(element);
+        }
+    }
+
+    fn visit_element(&mut self, element: &'a Element<'a>) {
+        match element {
+            Element::Image { source, .. } => self.add_image(source),
+            Element::Gallery { images, .. } => {
+                if let Some(image) = images.first() {
+                    self.add_image(&image.source);
+                }
+            }
+            _ => walk_element(self, element),
+        }
+    }
+
+    fn visit_container(&mut self, container: &'a Container<'a>) {
+        if container.ctype() == ContainerType::Paragraph
+            && self.paragraph.is_none()
+            && has_text(container.elements())
+        {
+            self.paragraph = Some(container.elements());
+        }
+
+        // Paragraphs can still contain images
+        walk_container(self, container);
+    }
+}
+
+/// Determines if there is any non-whitespace text directly within these elements.
+fn has_text(elements: &[Element]) -> bool {
+    elements.iter().any(|element| match element {
+        Element::Text(text) => !text.trim().is_empty(),
+        Element::Container(container) => has_text(container.elements()),
+        _ => false,
+    })
+}
+
+#[test]
+fn description() {
+    assert_eq!(
+        build_description("  Apple\n banana\tcherry "),
+        "Apple banana cherry"
+    );
+    assert_eq!(build_description(""), "");
+
+    let long_text = "word ".repeat(100);
+    let description = build_description(&long_text);
+    assert!(description.ends_with("word\u{2026}"));
+    assert!(description.chars().count() <= DESCRIPTION_LENGTH + 1);
+
+    let description = build_description(&"abcdef ".repeat(100));
+    assert!(description.ends_with(" abcdef\u{2026}"));
+    assert!(description.chars().count() <= DESCRIPTION_LENGTH + 1);
+
+    let long_word = format!("Start {}", "a".repeat(300));
+    let description = build_description(&long_word);
+    assert_eq!(description.chars().count(), DESCRIPTION_LENGTH + 1);
+    assert!(description.starts_with("Start aaa"));
+
+    let cjk_text = "漢字".repeat(150);
+    let description = build_description(&cjk_text);
+    assert_eq!(description.chars().count(), DESCRIPTION_LENGTH + 1);
+    assert!(description.starts_with("漢字漢字"));
+    assert!(description.ends_with("字\u{2026}"));
+
+    assert_eq!(
+        escape_json_ld(r#"{"a":"</script>&"}"#),
+        r#"{"a":"\u003c/script\u003e\u0026"}"#,
+    );
+}
@@ -18,8 +18,17 @@
  * along with this program. If not, see <http://www.gnu.org/licenses/>.
  */
 
+use super::context::HtmlContext;
+use super::element::render_elements;
+use super::page_links::collect_page_links;
 use super::prelude::*;
-use super::HtmlRender;
+use super::{HtmlOutput, HtmlRender};
+use crate::data::PageRef;
+use crate::render::typography::Typography;
+use crate::render::Handle;
+use crate::settings::HtmlSyntax;
+use crate::tree::{BibliographyList, LinkLabel, LinkLocation};
+use std::collections::HashSet;
 
 #[test]
 fn html() {
@@ -27,9 +36,305 @@ fn html() {
     let page_info = PageInfo::dummy();
     let settings = WikitextSettings::from_mode(WikitextMode::Page);
 
-    let result = SyntaxTree::from_element_result(vec![], vec![], vec![], vec![], vec![]);
+    let result = SyntaxTree::from_element_result(
+        vec![],
+        vec![],
+        vec![],
+        vec![],
+        vec![],
+        BibliographyList::new(),
+    );
     let (tree, _) = result.into();
     if false {
         let _output = HtmlRender.render(&log, &tree, &page_info, &settings);
     }
 }
+
+#[test]
+fn missing_page_links() {
+    let log = crate::build_logger();
+    let page_info = PageInfo::dummy();
+    let settings = WikitextSettings::from_mode(WikitextMode::Page);
+    let bibliographies = BibliographyList::new();
+
+    macro_rules! link {
+        ($page:expr) => {
+            Element::Link {
+                link: LinkLocation::Page(PageRef::page_only(cow!($page))),
+                label: LinkLabel::Url(None),
+                target: None,
+            }
+        };
+    }
+
+    let elements = vec![
+        link!("present"),
+        Element::Container(Container::new(
+            ContainerType::Bold,
+            vec![link!("missing"), link!("present")],
+            AttributeMap::new(),
+        )),
+        link!("missing"),
+    ];
+
+    // Check links are gathered, without duplicates
+    let result = SyntaxTree::from_element_result(
+        elements.clone(),
+        vec![],
+        vec![],
+        vec![],
+        vec![],
+        BibliographyList::new(),
+    );
+    let (tree, _) = result.into();
+    let pages = collect_page_links(&tree);
+
+    assert_eq!(
+        pages,
+        vec![
+            &PageRef::page_only(cow!("present")),
+            &PageRef::page_only(cow!("missing")),
+        ],
+    );
+
+    // Check missing links are rendered and tracked separately
+    let mut missing_pages = HashSet::new();
+    missing_pages.insert(PageRef::page_only(cow!("missing")));
+
+    let typography = Typography::default();
+    let mut ctx = HtmlContext::new(
+        &page_info,
+        &Handle,
+        &settings,
+        &[],
+        &[],
+        &bibliographies,
+        &typography,
+    );
+    ctx.set_missing_pages(missing_pages);
+    render_elements(&log, &mut ctx, &elements);
+
+    let output: HtmlOutput = ctx.into();
+    assert_eq!(
+        output.body,
+        "<a href=\"/present\">present</a>\
+         <strong><a href=\"/missing/edit/true\" class=\"wj-link-missing\">missing</a>\
+         <a href=\"/present\">present</a></strong>\
+         <a href=\"/missing/edit/true\" class=\"wj-link-missing\">missing</a>",
+    );
+    assert_eq!(
+        output.backlinks.internal_links,
+        vec![PageRef::page_only(cow!("present")); 2],
+    );
+    assert_eq!(
+        output.backlinks.missing_links,
+        vec![PageRef::page_only(cow!("missing")); 2],
+    );
+}
+
+#[test]
+fn section_edit_links() {
+    let log = crate::build_logger();
+    let page_info = PageInfo::dummy();
+    let settings = WikitextSettings {
+        enable_section_edit: true,
+        ..WikitextSettings::from_mode(WikitextMode::Page)
+    };
+
+    let text = "+ Apple\n\n++* Banana";
+    let tokenization = crate::tokenize(&log, text);
+    let result = crate::parse(&log, &tokenization, &page_info, &settings);
+    let (mut tree, _warnings) = result.into();
+
+    // Headings from included pages don't get a button, or count towards the index
+    let included = tree.elements[0].to_owned();
+    tree.elements.insert(
+        1,
+        Element::Include {
+            paragraph_safe: false,
+            variables: Default::default(),
+            location: PageRef::page_only(cow!("other-page")),
+            elements: vec![included],
+        },
+    );
+
+    let output = HtmlRender.render(&log, &tree, &page_info, &settings);
+
+    assert_eq!(
+        output.body,
+        "<wj-body class=\"wj-body\">\
+         <h1 id=\"toc0\">Apple<button type=\"button\" class=\"wj-section-edit\" data-section=\"0\">Edit</button></h1>\
+         <h1 id=\"toc1\">Apple</h1>\
+         <h2>Banana<button type=\"button\" class=\"wj-section-edit\" data-section=\"1\">Edit</button></h2>\
+         </wj-body>",
+    );
+}
+
+#[test]
+fn bibliography_back_links() {
+    let log = crate::build_logger();
+    let page_info = PageInfo::dummy();
+    let text = "Cited((bibcite apple)).\n\n[[bibliography]]\n: apple : The apple.\n[[/bibliography]]";
+
+    let render = |mode| {
+        let settings = WikitextSettings::from_mode(mode);
+        let tokenization = crate::tokenize(&log, text);
+        let result = crate::parse(&log, &tokenization, &page_info, &settings);
+        let (tree, _warnings) = result.into();
+
+        HtmlRender.render(&log, &tree, &page_info, &settings).body
+    };
+
+    // Back links need the citation ids, which are only present with true ids
+    let body = render(WikitextMode::Page);
+    assert!(body.contains("id=\"wj-bibcite-1-1\""));
+    assert!(body.contains("href=\"#wj-bibcite-1-1\""));
+
+    let body = render(WikitextMode::ForumPost);
+    assert!(!body.contains("wj-bibcite-1-1"));
+    assert!(!body.contains("wj-bibliography-back-link"));
+}
+
+#[test]
+fn social_metadata() {
+    let log = crate::build_logger();
+    let page_info = PageInfo::dummy();
+    let settings = WikitextSettings::from_mode(WikitextMode::Page);
+
+    let text = "[[image https://example.com/picture.png]]\n\n\
+                The first   paragraph\nof the page.\n\nThe second paragraph.";
+    let tokenization = crate::tokenize(&log, text);
+    let result = crate::parse(&log, &tokenization, &page_info, &settings);
+    let (tree, _warnings) = result.into();
+    let output = HtmlRender.render(&log, &tree, &page_info, &settings);
+
+    let get_meta = |name: &str| {
+        output
+            .meta
+            .iter()
+            .find(|meta| meta.name == name)
+            .map(|meta| meta.value.as_str())
+    };
+
+    assert_eq!(get_meta("og:title"), Some("A page for the age"));
+    assert_eq!(
+        get_meta("og:description"),
+        Some("The first paragraph of the page."),
+    );
+    assert_eq!(
+        get_meta("og:image"),
+        Some("https://example.com/picture.png"),
+    );
+    assert_eq!(
+        get_meta("og:url"),
+        Some("https://sandbox.wikijump.com/some-page"),
+    );
+    assert_eq!(get_meta("twitter:card"), Some("summary_large_image"));
+
+    let json_ld = output.json_ld.expect("No JSON-LD produced");
+    assert!(json_ld.contains(r#""@type":"Article""#));
+    assert!(json_ld.contains(r#""headline":"A page for the age""#));
+    assert!(json_ld.contains(r#""description":"The first paragraph of the page.""#));
+
+    // Without an image or JSON-LD
+    let settings = WikitextSettings::from_mode(WikitextMode::ForumPost);
+    let tokenization = crate::tokenize(&log, "Just text.");
+    let result = crate::parse(&log, &tokenization, &page_info, &settings);
+    let (tree, _warnings) = result.into();
+    let output = HtmlRender.render(&log, &tree, &page_info, &settings);
+
+    assert!(output.meta.iter().all(|meta| meta.name != "og:image"));
+    assert!(output
+        .meta
+        .iter()
+        .any(|meta| meta.name == "twitter:card" && meta.value == "summary"));
+    assert_eq!(output.json_ld, None);
+
+    // Images within definition lists
+    let text = ": Picture : [[image https://example.com/listed.png]]";
+    let tokenization = crate::tokenize(&log, text);
+    let result = crate::parse(&log, &tokenization, &page_info, &settings);
+    let (tree, _warnings) = result.into();
+    let output = HtmlRender.render(&log, &tree, &page_info, &settings);
+
+    assert!(output.meta.iter().any(|meta| {
+        meta.name == "og:image" && meta.value == "https://example.com/listed.png"
+    }));
+}
+
+#[test]
+fn xhtml() {
+    let log = crate::build_logger();
+    let page_info = PageInfo::dummy();
+    let settings = WikitextSettings {
+        html_syntax: HtmlSyntax::Xhtml,
+        ..WikitextSettings::from_mode(WikitextMode::Page)
+    };
+
+    let text = "Apple _\nBanana\n\n[[*checkbox]] Cherry\n\n----";
+    let tokenization = crate::tokenize(&log, text);
+    let result = crate::parse(&log, &tokenization, &page_info, &settings);
+    let (tree, _warnings) = result.into();
+    let output = HtmlRender.render(&log, &tree, &page_info, &settings);
+
+    assert_eq!(
+        output.body,
+        "<wj-body class=\"wj-body\">\
+         <p>Apple<br />Banana</p>\
+         <p><input type=\"checkbox\" checked=\"\" />Cherry</p>\
+         <hr />\
+         </wj-body>",
+    );
+}
+
+#[test]
+fn streaming() {
+    let log = crate::build_logger();
+    let page_info = PageInfo::dummy();
+    let settings = WikitextSettings::from_mode(WikitextMode::Page);
+
+    // Long enough to be written out in several chunks
+    let mut text = String::new();
+    for i in 0..500 {
+        str_write!(
+            text,
+            "+ Section {}\n\n**Bold** text & <more> [[[page-{}]]]\n\n",
+            i,
+            i
+        );
+    }
+
+    let tokenization = crate::tokenize(&log, &text);
+    let result = crate::parse(&log, &tokenization, &page_info, &settings);
+    let (tree, _warnings) = result.into();
+    let expected = HtmlRender.render(&log, &tree, &page_info, &settings);
+
+    // Into a fmt::Write
+    let mut body = String::new();
+    let output = HtmlRender
+        .render_to(&log, &tree, &page_info, &settings, &mut body)
+        .expect("Rendering to string failed");
+
+    assert!(body.len() > 8192, "Test body too short to be chunked");
+    assert_eq!(body, expected.body);
+    assert_eq!(output.styles, expected.styles);
+    assert_eq!(output.json_ld, expected.json_ld);
+    assert_eq!(output.backlinks, expected.backlinks);
+
+    // Into an io::Write
+    let mut bytes = Vec::new();
+    HtmlRender
+        .render_to_io(&log, &tree, &page_info, &settings, &mut bytes)
+        .expect("Rendering to bytes failed");
+
+    assert_eq!(bytes, expected.body.as_bytes());
+
+    // Errors from the writer are returned
+    let mut buffer = [0; 100];
+    let mut writer = &mut buffer[..];
+    let error = HtmlRender
+        .render_to_io(&log, &tree, &page_info, &settings, &mut writer)
+        .expect_err("Rendering to a full buffer succeeded");
+
+    assert_eq!(error.kind(), std::io::ErrorKind::WriteZero);
+}
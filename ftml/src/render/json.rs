@@ -24,6 +24,7 @@
 //! output as is used in the AST tests at `src/test.rs`.
 
 use super::prelude::*;
+use crate::schema::SCHEMA_VERSION;
 
 #[derive(Debug)]
 pub struct JsonRender {
@@ -63,15 +64,20 @@ impl Render for JsonRender {
         };
 
         // Wrapper struct to provide both page info and the AST in the JSON.
+        //
+        // The version of the schema is included, so consumers can tell
+        // whether they are able to read the rest.
         #[derive(Serialize, Debug)]
         #[serde(rename_all = "kebab-case")]
         struct JsonWrapper<'a> {
+            version: u32,
             settings: &'a WikitextSettings,
             page_info: &'a PageInfo<'a>,
             syntax_tree: &'a SyntaxTree<'a>,
         }
 
         let output = JsonWrapper {
+            version: SCHEMA_VERSION,
             settings,
             page_info,
             syntax_tree,
@@ -85,11 +91,48 @@ impl Render for JsonRender {
 fn json() {
     // Expected outputs
     const PRETTY_OUTPUT: &str = r#"{
+  "version": 1,
   "settings": {
     "mode": "page",
     "enable-page-syntax": true,
     "use-true-ids": true,
-    "allow-local-paths": true
+    "allow-local-paths": true,
+    "enable-section-edit": false,
+    "enable-json-ld": true,
+    "html-syntax": "html",
+    "preprocess": {
+      "passes": []
+    },
+    "typography": {
+      "enabled": true,
+      "quotes": "english"
+    },
+    "html-block": {
+      "hosting": "inline",
+      "sandbox": [
+        "allow-scripts"
+      ],
+      "auto-height": false,
+      "stylesheet": null
+    },
+    "iframe": {
+      "allowed-domains": [],
+      "denied-domains": [],
+      "sandbox": [
+        "allow-scripts",
+        "allow-popups",
+        "allow-forms"
+      ],
+      "referrer-policy": "strict-origin-when-cross-origin"
+    },
+    "limits": {
+      "max-input-length": 1048576,
+      "max-elements": 200000,
+      "max-table-cells": 20000,
+      "max-include-expansions": 100,
+      "max-css-size": 262144,
+      "max-steps": 20000000
+    }
   },
   "page-info": {
     "page": "some-page",
@@ -132,11 +175,12 @@ fn json() {
       "span.hidden-text { display: none; }"
     ],
     "table-of-contents": [],
-    "footnotes": []
+    "footnotes": [],
+    "bibliographies": []
   }
 }"#;
 
-    const COMPACT_OUTPUT: &str = r#"{"settings":{"mode":"page","enable-page-syntax":true,"use-true-ids":true,"allow-local-paths":true},"page-info":{"page":"some-page","category":null,"site":"sandbox","title":"A page for the age","alt-title":null,"rating":69.0,"tags":["tale","_cc"],"language":"default"},"syntax-tree":{"elements":[{"element":"text","data":"apple"},{"element":"text","data":" "},{"element":"container","data":{"type":"bold","attributes":{},"elements":[{"element":"text","data":"banana"}]}}],"styles":["span.hidden-text { display: none; }"],"table-of-contents":[],"footnotes":[]}}"#;
+    const COMPACT_OUTPUT: &str = r#"{"version":1,"settings":{"mode":"page","enable-page-syntax":true,"use-true-ids":true,"allow-local-paths":true,"enable-section-edit":false,"enable-json-ld":true,"html-syntax":"html","preprocess":{"passes":[]},"typography":{"enabled":true,"quotes":"english"},"html-block":{"hosting":"inline","sandbox":["allow-scripts"],"auto-height":false,"stylesheet":null},"iframe":{"allowed-domains":[],"denied-domains":[],"sandbox":["allow-scripts","allow-popups","allow-forms"],"referrer-policy":"strict-origin-when-cross-origin"},"limits":{"max-input-length":1048576,"max-elements":200000,"max-table-cells":20000,"max-include-expansions":100,"max-css-size":262144,"max-steps":20000000}},"page-info":{"page":"some-page","category":null,"site":"sandbox","title":"A page for the age","alt-title":null,"rating":69.0,"tags":["tale","_cc"],"language":"default"},"syntax-tree":{"elements":[{"element":"text","data":"apple"},{"element":"text","data":" "},{"element":"container","data":{"type":"bold","attributes":{},"elements":[{"element":"text","data":"banana"}]}}],"styles":["span.hidden-text { display: none; }"],"table-of-contents":[],"footnotes":[],"bibliographies":[]}}"#;
 
     let log = crate::build_logger();
     let page_info = PageInfo::dummy();
@@ -163,6 +207,7 @@ fn json() {
         styles,
         table_of_contents,
         footnotes,
+        crate::tree::BibliographyList::new(),
     );
     let (tree, _) = result.into();
 
@@ -0,0 +1,253 @@
+/*
+ * render/latex/context.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::escape::{escape, escape_graphics_path, escape_url, sanitize_math};
+use crate::data::PageInfo;
+use crate::render::typography::Typography;
+use crate::render::Handle;
+use crate::settings::WikitextSettings;
+use crate::tree::{BibliographyList, Element, VariableScopes};
+use std::fmt::{self, Write};
+use std::num::NonZeroUsize;
+
+#[derive(Debug)]
+pub struct LatexContext<'i, 'h, 'e, 't>
+where
+    'e: 't,
+{
+    output: String,
+    info: &'i PageInfo<'i>,
+    handle: &'h Handle,
+    settings: &'e WikitextSettings,
+
+    //
+    // Included page scopes
+    //
+    variables: VariableScopes,
+
+    //
+    // Elements from the syntax tree
+    //
+    footnotes: &'e [Vec<Element<'t>>],
+    bibliographies: &'e BibliographyList<'t>,
+    typography: &'e Typography,
+
+    //
+    // Other fields to track
+    //
+    /// How many tables we're currently inside of.
+    /// Paragraphs can't be started within a cell, so they're rendered inline.
+    table_depth: usize,
+
+    /// The current footnote index, for rendering.
+    footnote_index: NonZeroUsize,
+}
+
+impl<'i, 'h, 'e, 't> LatexContext<'i, 'h, 'e, 't>
+where
+    'e: 't,
+{
+    #[inline]
+    pub fn new(
+        info: &'i PageInfo<'i>,
+        handle: &'h Handle,
+        settings: &'e WikitextSettings,
+        footnotes: &'e [Vec<Element<'t>>],
+        bibliographies: &'e BibliographyList<'t>,
+        typography: &'e Typography,
+    ) -> Self {
+        LatexContext {
+            output: String::new(),
+            info,
+            handle,
+            settings,
+            variables: VariableScopes::new(),
+            footnotes,
+            bibliographies,
+            typography,
+            table_depth: 0,
+            footnote_index: NonZeroUsize::new(1).unwrap(),
+        }
+    }
+
+    // Getters
+    #[inline]
+    pub fn buffer(&mut self) -> &mut String {
+        &mut self.output
+    }
+
+    #[inline]
+    pub fn info(&self) -> &'i PageInfo<'i> {
+        self.info
+    }
+
+    #[inline]
+    pub fn settings(&self) -> &WikitextSettings {
+        self.settings
+    }
+
+    #[inline]
+    pub fn language(&self) -> &str {
+        &self.info.language
+    }
+
+    #[inline]
+    pub fn handle(&self) -> &'h Handle {
+        self.handle
+    }
+
+    #[inline]
+    pub fn variables(&self) -> &VariableScopes {
+        &self.variables
+    }
+
+    #[inline]
+    pub fn variables_mut(&mut self) -> &mut VariableScopes {
+        &mut self.variables
+    }
+
+    #[inline]
+    pub fn footnotes(&self) -> &'e [Vec<Element<'t>>] {
+        self.footnotes
+    }
+
+    #[inline]
+    pub fn bibliographies(&self) -> &'e BibliographyList<'t> {
+        self.bibliographies
+    }
+
+    #[inline]
+    pub fn typography(&self) -> &'e Typography {
+        self.typography
+    }
+
+    pub fn next_footnote_index(&mut self) -> NonZeroUsize {
+        let index = self.footnote_index;
+        self.footnote_index = NonZeroUsize::new(index.get() + 1).unwrap();
+        index
+    }
+
+    // Tables
+    #[inline]
+    pub fn in_table(&self) -> bool {
+        self.table_depth > 0
+    }
+
+    #[inline]
+    pub fn enter_table(&mut self) {
+        self.table_depth += 1;
+    }
+
+    #[inline]
+    pub fn exit_table(&mut self) {
+        self.table_depth -= 1;
+    }
+
+    // Buffer management
+    #[inline]
+    pub fn push_raw(&mut self, ch: char) {
+        self.output.push(ch);
+    }
+
+    #[inline]
+    pub fn push_raw_str(&mut self, s: &str) {
+        self.output.push_str(s);
+    }
+
+    #[inline]
+    pub fn push_escaped(&mut self, s: &str) {
+        escape(&mut self.output, s);
+    }
+
+    #[inline]
+    pub fn push_url(&mut self, url: &str) {
+        escape_url(&mut self.output, url);
+    }
+
+    #[inline]
+    pub fn push_graphics_path(&mut self, path: &str) {
+        escape_graphics_path(&mut self.output, path);
+    }
+
+    #[inline]
+    pub fn push_math(&mut self, latex_source: &str) {
+        sanitize_math(&mut self.output, latex_source);
+    }
+
+    /// Whether nothing has been written since the start of the last paragraph.
+    pub fn at_paragraph_start(&self) -> bool {
+        self.output.is_empty() || self.output.ends_with("\n\n")
+    }
+
+    /// Whether a line break here would have a line to end.
+    ///
+    /// Breaking at the start of a paragraph or table cell is an error in LaTeX.
+    pub fn can_break_line(&self) -> bool {
+        let trimmed = self.output.trim_end();
+
+        !self.at_paragraph_start()
+            && !trimmed.ends_with('&')
+            && !trimmed.ends_with("\\hline")
+            && !trimmed.ends_with('{')
+    }
+
+    /// Ensures the following content starts on its own line.
+    pub fn start_line(&mut self) {
+        if !self.output.is_empty() && !self.output.ends_with('\n') {
+            self.output.push('\n');
+        }
+    }
+
+    /// Ends the current paragraph, if there is one.
+    ///
+    /// Within tables, this only separates the contents with a space.
+    pub fn end_paragraph(&mut self) {
+        if self.in_table() {
+            if !self.output.ends_with(' ') {
+                self.output.push(' ');
+            }
+
+            return;
+        }
+
+        self.start_line();
+
+        if !self.at_paragraph_start() {
+            self.output.push('\n');
+        }
+    }
+}
+
+impl<'i, 'h, 'e, 't> From<LatexContext<'i, 'h, 'e, 't>> for String {
+    #[inline]
+    fn from(ctx: LatexContext<'i, 'h, 'e, 't>) -> String {
+        ctx.output
+    }
+}
+
+impl<'i, 'h, 'e, 't> Write for LatexContext<'i, 'h, 'e, 't>
+where
+    'e: 't,
+{
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.buffer().write_str(s)
+    }
+}
@@ -0,0 +1,746 @@
+/*
+ * render/latex/elements.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Module that implements LaTeX rendering for `Element` and its children.
+
+use super::escape::label_name;
+use super::LatexContext;
+use crate::log::prelude::*;
+use crate::render::ModuleRenderMode;
+use crate::tree::{
+    Alignment, ContainerType, DefinitionListItem, Element, ImageSource, LinkLocation,
+    ListItem, ListType, Tab, Table,
+};
+use crate::url::normalize_link;
+use std::borrow::Cow;
+
+/// Colors which `xcolor` knows without any options.
+const XCOLOR_NAMES: &[&str] = &[
+    "black",
+    "blue",
+    "brown",
+    "cyan",
+    "darkgray",
+    "gray",
+    "green",
+    "lightgray",
+    "lime",
+    "magenta",
+    "olive",
+    "orange",
+    "pink",
+    "purple",
+    "red",
+    "teal",
+    "violet",
+    "white",
+    "yellow",
+];
+
+pub fn render_elements(log: &Logger, ctx: &mut LatexContext, elements: &[Element]) {
+    info!(log, "Rendering elements"; "elements-len" => elements.len());
+
+    for element in elements {
+        render_element(log, ctx, element);
+    }
+}
+
+pub fn render_element(log: &Logger, ctx: &mut LatexContext, element: &Element) {
+    info!(log, "Rendering element"; "element" => element.name());
+
+    match element {
+        Element::Container(container) => {
+            let elements = container.elements();
+
+            match container.ctype() {
+                // Don't render this at all.
+                ContainerType::Hidden => (),
+
+                // Block containers
+                ContainerType::Paragraph => {
+                    render_elements(log, ctx, elements);
+                    ctx.end_paragraph();
+                }
+                ContainerType::Div => {
+                    ctx.end_paragraph();
+                    render_elements(log, ctx, elements);
+                    ctx.end_paragraph();
+                }
+                ContainerType::Blockquote => {
+                    render_environment(log, ctx, "quote", elements)
+                }
+                ContainerType::Align(alignment) => {
+                    let environment = match alignment {
+                        Alignment::Left => "flushleft",
+                        Alignment::Right => "flushright",
+                        Alignment::Center => "center",
+                        Alignment::Justify => {
+                            ctx.end_paragraph();
+                            render_elements(log, ctx, elements);
+                            ctx.end_paragraph();
+                            return;
+                        }
+                    };
+
+                    render_environment(log, ctx, environment, elements);
+                }
+                ContainerType::Header(heading) => {
+                    // Sectioning commands aren't permitted within table cells.
+                    if ctx.in_table() {
+                        render_command(log, ctx, "textbf", elements);
+                        ctx.end_paragraph();
+                        return;
+                    }
+
+                    let command = match heading.level.value() {
+                        1 => "section",
+                        2 => "subsection",
+                        3 => "subsubsection",
+                        4 => "paragraph",
+                        _ => "subparagraph",
+                    };
+
+                    // Headings excluded from the table of contents aren't numbered.
+                    let star = if heading.has_toc { "" } else { "*" };
+
+                    ctx.end_paragraph();
+                    str_write!(ctx, "\\{}{}{{", command, star);
+                    render_elements(log, ctx, elements);
+                    ctx.push_raw_str("}\n\n");
+                }
+
+                // Inline containers
+                ContainerType::Bold => render_command(log, ctx, "textbf", elements),
+                ContainerType::Italics => render_command(log, ctx, "emph", elements),
+                ContainerType::Underline | ContainerType::Insertion => {
+                    render_command(log, ctx, "uline", elements)
+                }
+                ContainerType::Strikethrough | ContainerType::Deletion => {
+                    render_command(log, ctx, "sout", elements)
+                }
+                ContainerType::Superscript => {
+                    render_command(log, ctx, "textsuperscript", elements)
+                }
+                ContainerType::Subscript => {
+                    render_command(log, ctx, "textsubscript", elements)
+                }
+                ContainerType::Monospace => render_command(log, ctx, "texttt", elements),
+                ContainerType::Invisible => render_command(log, ctx, "phantom", elements),
+
+                // Ruby text is rendered in parentheses after its base,
+                // as in "base (reading)".
+                ContainerType::RubyText => {
+                    ctx.push_raw_str(" (");
+                    render_elements(log, ctx, elements);
+                    ctx.push_raw(')');
+                }
+
+                // Containers which only affect styling on the web.
+                ContainerType::Span
+                | ContainerType::Mark
+                | ContainerType::Size
+                | ContainerType::Ruby
+                | ContainerType::RubyBase => render_elements(log, ctx, elements),
+            }
+        }
+        Element::Module(module) => {
+            let mut buffer = String::new();
+
+            ctx.handle()
+                .render_module(log, &mut buffer, module, ModuleRenderMode::Text);
+
+            ctx.push_escaped(&buffer);
+        }
+        Element::Text(text) => {
+            let text = ctx.typography().text(element, text);
+            ctx.push_escaped(text);
+        }
+        Element::Raw(text) | Element::Email(text) => ctx.push_escaped(text),
+        Element::Variable(name) => {
+            let value = ctx.variables().get(name);
+
+            info!(
+                log,
+                "Rendering variable";
+                "name" => name.as_ref(),
+                "value" => value,
+            );
+
+            let value = match value {
+                Some(value) => str!(value),
+                None => format!("{{${}}}", name),
+            };
+
+            ctx.push_escaped(&value);
+        }
+        Element::Table(table) => render_table(log, ctx, table),
+        Element::TabView(tabs) => {
+            // There's nothing to switch between on paper, so show each tab in turn.
+            for Tab { label, elements } in tabs {
+                ctx.end_paragraph();
+                ctx.push_raw_str("\\textbf{");
+                ctx.push_escaped(label);
+                ctx.push_raw('}');
+                ctx.end_paragraph();
+
+                render_elements(log, ctx, elements);
+            }
+        }
+        Element::Anchor {
+            elements,
+            attributes,
+            ..
+        } => {
+            let url = attributes.get().get("href").map(|href| {
+                let link = LinkLocation::parse(cow!(href));
+                get_url_from_link(ctx, &link).into_owned()
+            });
+
+            match url {
+                Some(url) if !url.starts_with('#') => {
+                    ctx.push_raw_str("\\href{");
+                    ctx.push_url(&url);
+                    ctx.push_raw_str("}{");
+                    render_elements(log, ctx, elements);
+                    ctx.push_raw('}');
+                }
+                _ => render_elements(log, ctx, elements),
+            }
+        }
+        Element::Link { link, label, .. } => {
+            let url = get_url_from_link(ctx, link);
+
+            ctx.handle().get_link_label(log, link, label, |label| {
+                // Anchors within the page have nowhere to go.
+                if url.starts_with('#') {
+                    ctx.push_escaped(label);
+                    return;
+                }
+
+                ctx.push_raw_str("\\href{");
+                ctx.push_url(&url);
+                ctx.push_raw_str("}{");
+                ctx.push_escaped(label);
+                ctx.push_raw('}');
+            });
+        }
+        Element::Image {
+            source,
+            link,
+            alignment,
+            ..
+        } => {
+            let centered =
+                matches!(alignment, Some(image) if image.align == Alignment::Center);
+
+            if centered {
+                ctx.start_line();
+                ctx.push_raw_str("\\begin{center}\n");
+            }
+
+            match link {
+                Some(link) => {
+                    let url = get_url_from_link(ctx, link);
+
+                    ctx.push_raw_str("\\href{");
+                    ctx.push_url(&url);
+                    ctx.push_raw_str("}{");
+                    render_image(log, ctx, source, "\\linewidth");
+                    ctx.push_raw('}');
+                }
+                None => render_image(log, ctx, source, "\\linewidth"),
+            }
+
+            if centered {
+                ctx.push_raw_str("\n\\end{center}\n");
+            }
+        }
+        Element::Gallery { images, .. } => {
+            ctx.end_paragraph();
+            ctx.push_raw_str("\\noindent\n");
+
+            for image in images {
+                render_image(log, ctx, &image.source, "0.3\\linewidth");
+                ctx.push_raw_str("\\hfill\n");
+            }
+
+            ctx.end_paragraph();
+        }
+        Element::Media(media) => {
+            let source_url = ctx.handle().get_file_link(
+                log,
+                &media.source,
+                ctx.info(),
+                ctx.settings(),
+            );
+
+            match source_url {
+                Some(url) => render_url(ctx, &url),
+                None => {
+                    let message = ctx.handle().get_message(
+                        log,
+                        ctx.language(),
+                        "media-context-bad",
+                    );
+
+                    ctx.push_escaped(message);
+                }
+            }
+        }
+        Element::List { ltype, items, .. } => {
+            // Empty lists are an error in LaTeX.
+            if items.is_empty() {
+                return;
+            }
+
+            let environment = match ltype {
+                ListType::Bullet | ListType::Generic => "itemize",
+                ListType::Numbered => "enumerate",
+            };
+
+            ctx.start_line();
+            str_write!(ctx, "\\begin{{{}}}\n", environment);
+
+            for (i, item) in items.iter().enumerate() {
+                match item {
+                    ListItem::Elements { elements, .. } => {
+                        match ltype {
+                            ListType::Generic => ctx.push_raw_str("\\item[] "),
+                            _ => ctx.push_raw_str("\\item "),
+                        }
+
+                        render_elements(log, ctx, elements);
+                        ctx.push_raw('\n');
+                    }
+                    ListItem::SubList { element } => {
+                        // A sublist must be within an item, so add one if there isn't one.
+                        if i == 0 {
+                            ctx.push_raw_str("\\item[]\n");
+                        }
+
+                        render_element(log, ctx, element);
+                    }
+                }
+            }
+
+            ctx.start_line();
+            str_write!(ctx, "\\end{{{}}}\n", environment);
+        }
+        Element::DefinitionList(items) => {
+            if items.is_empty() {
+                return;
+            }
+
+            ctx.start_line();
+            ctx.push_raw_str("\\begin{description}\n");
+
+            for DefinitionListItem { key, value } in items {
+                ctx.push_raw_str("\\item[{");
+                render_elements(log, ctx, key);
+                ctx.push_raw_str("}] ");
+                render_elements(log, ctx, value);
+                ctx.push_raw('\n');
+            }
+
+            ctx.push_raw_str("\\end{description}\n");
+        }
+        Element::RadioButton { checked, .. } => {
+            let symbol = if *checked { "bullet" } else { "circ" };
+            str_write!(ctx, "$\\{}$ ", symbol);
+        }
+        Element::CheckBox { checked, .. } => {
+            let symbol = if *checked { "boxtimes" } else { "square" };
+            str_write!(ctx, "$\\{}$ ", symbol);
+        }
+        Element::Collapsible { elements, .. } => {
+            // Collapsibles can't be opened on paper, so show their contents.
+            ctx.end_paragraph();
+            render_elements(log, ctx, elements);
+            ctx.end_paragraph();
+        }
+        Element::TableOfContents { .. } => {
+            info!(log, "Rendering table of contents");
+
+            ctx.end_paragraph();
+            ctx.push_raw_str("\\tableofcontents\n\n");
+        }
+        Element::Footnote => {
+            info!(log, "Rendering footnote reference");
+
+            // LaTeX places the footnote contents itself.
+            let index = ctx.next_footnote_index();
+            let contents = ctx.footnotes().get(index.get() - 1);
+
+            ctx.push_raw_str("\\footnote{");
+
+            if let Some(contents) = contents {
+                render_elements(log, ctx, contents);
+            }
+
+            ctx.push_raw('}');
+        }
+        Element::FootnoteBlock { .. } => {
+            info!(
+                log,
+                "Skipping footnote block, footnotes are placed by LaTeX"
+            );
+        }
+        Element::BibliographyCite { label } => {
+            info!(log, "Rendering bibliography citation"; "label" => label.as_ref());
+
+            match ctx.bibliographies().get_reference(label) {
+                Some((number, _)) => str_write!(ctx, "[{}]", number),
+                None => {
+                    ctx.push_raw('[');
+                    ctx.push_escaped(label);
+                    ctx.push_raw(']');
+                }
+            }
+        }
+        Element::BibliographyBlock { index, title, hide } => {
+            info!(log, "Rendering bibliography block"; "index" => index);
+
+            if *hide {
+                return;
+            }
+
+            let (first, bibliography) =
+                match ctx.bibliographies().get_bibliography(*index) {
+                    Some((_, bibliography)) if bibliography.entries.is_empty() => return,
+                    Some(result) => result,
+                    None => return,
+                };
+
+            // Render bibliography title
+            let title_default;
+            let title: &str = match title {
+                Some(title) => title.as_ref(),
+                None => {
+                    title_default = ctx.handle().get_message(
+                        log,
+                        ctx.language(),
+                        "bibliography-block-title",
+                    );
+                    title_default
+                }
+            };
+
+            ctx.end_paragraph();
+            ctx.push_raw_str("\\section*{");
+            ctx.push_escaped(title);
+            ctx.push_raw_str("}\n\n\\begin{description}\n");
+
+            // Render entries in order, continuing the numbering.
+            for (offset, entry) in bibliography.entries.iter().enumerate() {
+                str_write!(ctx, "\\item[[{}]] ", first + offset);
+                render_elements(log, ctx, &entry.elements);
+                ctx.push_raw('\n');
+            }
+
+            ctx.push_raw_str("\\end{description}\n\n");
+        }
+        Element::User { name, .. } => ctx.push_escaped(name),
+        Element::Date { value, format, .. } => {
+            let date = value.format(format.as_ref());
+            ctx.push_escaped(&date);
+        }
+        Element::Color { color, elements } => match get_color(color) {
+            Some(color) => {
+                str_write!(ctx, "\\textcolor{}{{", color);
+                render_elements(log, ctx, elements);
+                ctx.push_raw('}');
+            }
+            None => render_elements(log, ctx, elements),
+        },
+        Element::Code { contents, .. } => {
+            // Verbatim can't appear within table cells, so typeset it directly.
+            if ctx.in_table() {
+                ctx.push_raw_str("\\texttt{");
+                ctx.push_escaped(contents);
+                ctx.push_raw('}');
+                return;
+            }
+
+            // The only thing which can end a verbatim environment is its closing tag.
+            let contents = contents.replace("\\end{verbatim}", "\\end {verbatim}");
+
+            ctx.end_paragraph();
+            ctx.push_raw_str("\\begin{verbatim}\n");
+            ctx.push_raw_str(&contents);
+            ctx.start_line();
+            ctx.push_raw_str("\\end{verbatim}\n\n");
+        }
+        Element::Math { name, latex_source } => {
+            // The source is already LaTeX, so it's passed through,
+            // keeping only known math commands.
+            ctx.start_line();
+            ctx.push_raw_str("\\begin{equation}\n");
+
+            if let Some(name) = name {
+                str_write!(ctx, "\\label{{eq:{}}}\n", label_name(name));
+            }
+
+            ctx.push_math(latex_source.trim());
+            ctx.push_raw_str("\n\\end{equation}\n");
+        }
+        Element::MathInline { latex_source } => {
+            ctx.push_raw_str("\\(");
+            ctx.push_math(latex_source.trim());
+            ctx.push_raw_str("\\)");
+        }
+        Element::EquationReference(name) => {
+            str_write!(ctx, "\\eqref{{eq:{}}}", label_name(name));
+        }
+        Element::Embed(embed) => render_url(ctx, &embed.direct_url()),
+        Element::Html { .. } => {
+            // Can't be shown on paper, leave a note for anyone reading the source.
+            ctx.start_line();
+            ctx.push_raw_str("% HTML block omitted\n");
+        }
+        Element::Iframe { url, .. } => render_url(ctx, url),
+        Element::Include {
+            variables,
+            elements,
+            location: _location,
+            ..
+        } => {
+            info!(
+                log,
+                "Rendering include";
+                "location" => str!(_location),
+                "variables-len" => variables.len(),
+                "elements-len" => elements.len(),
+            );
+
+            ctx.variables_mut().push_scope(variables);
+
+            render_elements(log, ctx, elements);
+
+            ctx.variables_mut().pop_scope();
+        }
+        Element::LineBreak => render_line_break(ctx),
+        Element::LineBreaks(amount) => {
+            for _ in 0..amount.get() {
+                render_line_break(ctx);
+            }
+        }
+        Element::ClearFloat(_) => ctx.end_paragraph(),
+        Element::HorizontalRule => {
+            ctx.end_paragraph();
+            ctx.push_raw_str("\\noindent\\rule{\\linewidth}{0.4pt}");
+            ctx.end_paragraph();
+        }
+        Element::Partial(_) => panic!("Encountered partial element during parsing"),
+    }
+}
+
+fn render_command(
+    log: &Logger,
+    ctx: &mut LatexContext,
+    command: &str,
+    elements: &[Element],
+) {
+    str_write!(ctx, "\\{}{{", command);
+    render_elements(log, ctx, elements);
+    ctx.push_raw('}');
+}
+
+fn render_environment(
+    log: &Logger,
+    ctx: &mut LatexContext,
+    environment: &str,
+    elements: &[Element],
+) {
+    ctx.end_paragraph();
+    str_write!(ctx, "\\begin{{{}}}\n", environment);
+    render_elements(log, ctx, elements);
+    ctx.start_line();
+    str_write!(ctx, "\\end{{{}}}\n", environment);
+    ctx.end_paragraph();
+}
+
+fn render_table(log: &Logger, ctx: &mut LatexContext, table: &Table) {
+    let columns = table
+        .rows
+        .iter()
+        .map(|row| {
+            row.cells
+                .iter()
+                .map(|cell| cell.column_span.get())
+                .sum::<u32>()
+        })
+        .max()
+        .unwrap_or(0);
+
+    if columns == 0 {
+        return;
+    }
+
+    // Cells are paragraph columns of equal width, so long text wraps.
+    // Tables can span pages, but a nested table must be a plain tabular.
+    let environment = if ctx.in_table() {
+        "tabular"
+    } else {
+        "longtable"
+    };
+
+    ctx.end_paragraph();
+    str_write!(ctx, "\\begin{{{}}}{{|", environment);
+
+    for _ in 0..columns {
+        ctx.push_raw_str(&column_type(1, columns, None));
+        ctx.push_raw('|');
+    }
+
+    ctx.push_raw_str("}\n\\hline\n");
+    ctx.enter_table();
+
+    for row in &table.rows {
+        for (i, cell) in row.cells.iter().enumerate() {
+            if i > 0 {
+                ctx.push_raw_str(" & ");
+            }
+
+            let span = cell.column_span.get();
+            let multicolumn = span > 1 || cell.align.is_some();
+
+            if multicolumn {
+                let left_border = if i == 0 { "|" } else { "" };
+
+                str_write!(
+                    ctx,
+                    "\\multicolumn{{{}}}{{{}{}|}}{{",
+                    span,
+                    left_border,
+                    column_type(span, columns, cell.align),
+                );
+            }
+
+            if cell.header {
+                render_command(log, ctx, "textbf", &cell.elements);
+            } else {
+                render_elements(log, ctx, &cell.elements);
+            }
+
+            if multicolumn {
+                ctx.push_raw('}');
+            }
+        }
+
+        ctx.push_raw_str(" \\\\\n\\hline\n");
+    }
+
+    ctx.exit_table();
+    str_write!(ctx, "\\end{{{}}}\n", environment);
+    ctx.end_paragraph();
+}
+
+/// Gets the type of a table column spanning `span` of `columns` columns.
+fn column_type(span: u32, columns: u32, align: Option<Alignment>) -> String {
+    let prefix = match align {
+        Some(Alignment::Left) => ">{\\raggedright\\arraybackslash}",
+        Some(Alignment::Right) => ">{\\raggedleft\\arraybackslash}",
+        Some(Alignment::Center) => ">{\\centering\\arraybackslash}",
+        Some(Alignment::Justify) | None => "",
+    };
+
+    format!(
+        "{}p{{\\dimexpr\\linewidth*{}/{}-2\\tabcolsep\\relax}}",
+        prefix, span, columns,
+    )
+}
+
+fn render_image(log: &Logger, ctx: &mut LatexContext, source: &ImageSource, width: &str) {
+    let source_url = ctx
+        .handle()
+        .get_image_link(log, source, ctx.info(), ctx.settings());
+
+    match source_url {
+        Some(url) => {
+            str_write!(
+                ctx,
+                "\\includegraphics[width={},height=0.5\\textheight,keepaspectratio]{{",
+                width,
+            );
+            ctx.push_graphics_path(&url);
+            ctx.push_raw('}');
+        }
+        None => {
+            let message =
+                ctx.handle()
+                    .get_message(log, ctx.language(), "image-context-bad");
+
+            ctx.push_escaped(message);
+        }
+    }
+}
+
+fn render_url(ctx: &mut LatexContext, url: &str) {
+    ctx.push_raw_str("\\url{");
+    ctx.push_url(url);
+    ctx.push_raw('}');
+}
+
+fn render_line_break(ctx: &mut LatexContext) {
+    if ctx.can_break_line() {
+        ctx.push_raw_str("\\newline\n");
+    }
+}
+
+/// Converts a CSS color into arguments for `\textcolor`.
+///
+/// Colors which `xcolor` doesn't understand are ignored, since
+/// they would otherwise be an error when compiling the document.
+fn get_color(color: &str) -> Option<String> {
+    let color = color.trim();
+
+    if let Some(hex) = color.strip_prefix('#') {
+        if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+
+        return match hex.len() {
+            3 => {
+                let hex: String = hex.chars().flat_map(|c| [c, c]).collect();
+                Some(format!("[HTML]{{{}}}", hex.to_ascii_uppercase()))
+            }
+            6 => Some(format!("[HTML]{{{}}}", hex.to_ascii_uppercase())),
+            _ => None,
+        };
+    }
+
+    let color = color.to_ascii_lowercase();
+    if XCOLOR_NAMES.contains(&color.as_str()) {
+        Some(format!("{{{}}}", color))
+    } else {
+        None
+    }
+}
+
+fn get_url_from_link<'a>(ctx: &LatexContext, link: &'a LinkLocation<'a>) -> Cow<'a, str> {
+    let url = normalize_link(link, ctx.handle());
+
+    // TODO: when we remove inline javascript stuff
+    if url.as_ref() == "javascript:;" {
+        return Cow::Borrowed("#");
+    }
+
+    url
+}
@@ -0,0 +1,238 @@
+/*
+ * render/latex/escape.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Escaping for text placed in LaTeX source.
+
+use super::math::{MATH_COMMANDS, MATH_ENVIRONMENTS, MATH_SYMBOLS};
+
+/// Escapes text so it is typeset as-is.
+pub fn escape(buffer: &mut String, s: &str) {
+    for ch in s.chars() {
+        match ch {
+            '\\' => buffer.push_str("\\textbackslash{}"),
+            '{' => buffer.push_str("\\{"),
+            '}' => buffer.push_str("\\}"),
+            '$' => buffer.push_str("\\$"),
+            '&' => buffer.push_str("\\&"),
+            '%' => buffer.push_str("\\%"),
+            '#' => buffer.push_str("\\#"),
+            '_' => buffer.push_str("\\_"),
+            '^' => buffer.push_str("\\textasciicircum{}"),
+            '~' => buffer.push_str("\\textasciitilde{}"),
+            '<' => buffer.push_str("\\textless{}"),
+            '>' => buffer.push_str("\\textgreater{}"),
+            '|' => buffer.push_str("\\textbar{}"),
+            '\n' => buffer.push(' '),
+            _ => buffer.push(ch),
+        }
+    }
+}
+
+/// Escapes a URL, for `\href` and `\url`.
+///
+/// Only the characters which would otherwise end or break
+/// the argument are escaped, since the rest are taken literally.
+/// Characters which can't appear in a URL are percent-encoded.
+pub fn escape_url(buffer: &mut String, url: &str) {
+    for ch in url.chars() {
+        match ch {
+            '%' => buffer.push_str("\\%"),
+            '#' => buffer.push_str("\\#"),
+            '\\' | '{' | '}' | ' ' => str_write!(buffer, "%{:02X}", ch as u32),
+            _ => buffer.push(ch),
+        }
+    }
+}
+
+/// Escapes an image path, for `\includegraphics`.
+///
+/// Unlike `\href`, there is no way to escape characters in a file name,
+/// so any which TeX would interpret, as well as whitespace, are replaced
+/// with underscores. Images with such paths will need to be renamed to match.
+pub fn escape_graphics_path(buffer: &mut String, path: &str) {
+    for ch in path.chars() {
+        match ch {
+            '\\' | '{' | '}' | '%' | '#' | '~' | '&' | '$' | '^' => buffer.push('_'),
+            _ if ch.is_whitespace() || ch.is_control() => buffer.push('_'),
+            _ => buffer.push(ch),
+        }
+    }
+}
+
+/// Copies the LaTeX source of a math block, keeping only known math commands.
+///
+/// Any other control word or symbol is replaced with `{}`, so the text on
+/// either side can't join into a new one. This is an allowlist, since TeX
+/// has too many ways of reading files or building commands to list them all.
+/// Since `^^` sequences are converted to other characters before commands
+/// are read, they are broken up as well.
+pub fn sanitize_math(buffer: &mut String, source: &str) {
+    let chars: Vec<char> = source.chars().collect();
+    let mut index = 0;
+
+    while index < chars.len() {
+        let ch = chars[index];
+        index += 1;
+
+        match ch {
+            '\\' => {
+                let start = index;
+
+                while index < chars.len() && chars[index].is_ascii_alphabetic() {
+                    index += 1;
+                }
+
+                let name: String = chars[start..index].iter().collect();
+
+                // Control symbol, such as "\\" or "\{"
+                if name.is_empty() {
+                    match chars.get(index) {
+                        Some(&symbol) if MATH_SYMBOLS.contains(symbol) => {
+                            buffer.push('\\');
+                            buffer.push(symbol);
+                            index += 1;
+                        }
+                        _ => buffer.push_str("{}"),
+                    }
+
+                    continue;
+                }
+
+                // Environments need their name checked too
+                if name == "begin" || name == "end" {
+                    match environment_name(&chars, index) {
+                        Some((environment, end))
+                            if MATH_ENVIRONMENTS.contains(&environment.as_str()) =>
+                        {
+                            buffer.push('\\');
+                            buffer.push_str(&name);
+                            buffer.extend(&chars[index..end]);
+                            index = end;
+                        }
+                        _ => buffer.push_str("{}"),
+                    }
+
+                    continue;
+                }
+
+                if MATH_COMMANDS.contains(name.as_str()) {
+                    buffer.push('\\');
+                    buffer.push_str(&name);
+                } else {
+                    buffer.push_str("{}");
+                }
+            }
+            '^' if chars.get(index) == Some(&'^') => buffer.push_str("^{}"),
+            _ => buffer.push(ch),
+        }
+    }
+}
+
+/// Reads the `{name}` argument of `\begin` or `\end`.
+///
+/// Returns the name and the index just past the closing brace.
+fn environment_name(chars: &[char], mut index: usize) -> Option<(String, usize)> {
+    while chars.get(index)?.is_whitespace() {
+        index += 1;
+    }
+
+    if chars[index] != '{' {
+        return None;
+    }
+
+    let start = index + 1;
+    let length = chars[start..].iter().position(|&ch| ch == '}')?;
+    let name = chars[start..start + length].iter().collect();
+
+    Some((name, start + length + 1))
+}
+
+/// Converts a name into something usable as a `\label`.
+pub fn label_name(name: &str) -> String {
+    name.chars()
+        .map(|ch| match ch {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '_' | '.' | ':' => ch,
+            _ => '-',
+        })
+        .collect()
+}
+
+#[test]
+fn latex_escape() {
+    macro_rules! check {
+        ($function:ident, $input:expr, $expected:expr $(,)?) => {{
+            let mut buffer = String::new();
+            $function(&mut buffer, $input);
+            assert_eq!(buffer, $expected, "Escaped output doesn't match");
+        }};
+    }
+
+    check!(escape, "plain text", "plain text");
+    check!(escape, "50% of $5 & #1", "50\\% of \\$5 \\& \\#1");
+    check!(
+        escape,
+        "a_b^c~d",
+        "a\\_b\\textasciicircum{}c\\textasciitilde{}d"
+    );
+    check!(escape, "\\begin{x}", "\\textbackslash{}begin\\{x\\}");
+    check!(
+        escape_url,
+        "https://example.com/a b?q=%20#c",
+        "https://example.com/a%20b?q=\\%20\\#c",
+    );
+    check!(escape_url, "file\\{}.png", "file%5C%7B%7D.png");
+    check!(
+        escape_graphics_path,
+        "images/my picture~1%.png",
+        "images/my_picture_1_.png",
+    );
+    check!(escape_graphics_path, "a\\input{b}#c", "a_input_b__c");
+
+    check!(sanitize_math, "x^2 + \\frac{1}{2}", "x^2 + \\frac{1}{2}");
+    check!(
+        sanitize_math,
+        "\\begin{pmatrix} a & b \\\\ c & d \\end{pmatrix}",
+        "\\begin{pmatrix} a & b \\\\ c & d \\end{pmatrix}",
+    );
+    check!(sanitize_math, "\\input{/etc/passwd}", "{}{/etc/passwd}");
+    check!(sanitize_math, "\\immediate\\write18{ls}", "{}{}18{ls}");
+    check!(sanitize_math, "\\int\\input x", "\\int{} x");
+    check!(sanitize_math, "\\\\input", "\\\\input");
+    check!(sanitize_math, "\\inp^^75t", "{}^{}^75t");
+    check!(sanitize_math, "\\^^5cinput", "{}^{}^5cinput");
+    check!(sanitize_math, "\\csname input\\endcsname", "{} input{}");
+    check!(
+        sanitize_math,
+        "\\InputIfFileExists{/etc/passwd}{}{}",
+        "{}{/etc/passwd}{}{}",
+    );
+    check!(
+        sanitize_math,
+        "\\makeatletter\\@@input /etc/passwd",
+        "{}{}@@input /etc/passwd",
+    );
+    check!(
+        sanitize_math,
+        "\\begin{filecontents}{x}",
+        "{}{filecontents}{x}",
+    );
+
+    assert_eq!(label_name("my equation!"), "my-equation-");
+}
@@ -0,0 +1,616 @@
+/*
+ * render/latex/math.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! The commands allowed within math blocks when rendering LaTeX.
+//!
+//! These are the math commands from LaTeX, `amsmath`, and `amssymb`
+//! which only typeset, and so are safe to copy into the document.
+
+use std::collections::HashSet;
+
+/// Control symbols, the characters allowed after a backslash on their own.
+pub const MATH_SYMBOLS: &str = "\\ ,;:!{}|#$%&_>";
+
+/// Environments allowed within math, for `\begin` and `\end`.
+pub const MATH_ENVIRONMENTS: [&str; 14] = [
+    "matrix",
+    "pmatrix",
+    "bmatrix",
+    "Bmatrix",
+    "vmatrix",
+    "Vmatrix",
+    "smallmatrix",
+    "cases",
+    "aligned",
+    "alignedat",
+    "gathered",
+    "split",
+    "array",
+    "subarray",
+];
+
+lazy_static! {
+    /// Control words allowed within math.
+    pub static ref MATH_COMMANDS: HashSet<&'static str> = {
+        let mut commands = HashSet::new();
+
+        // Greek and Hebrew letters
+        commands.extend(&[
+            "alpha",
+            "beta",
+            "gamma",
+            "delta",
+            "epsilon",
+            "varepsilon",
+            "zeta",
+            "eta",
+            "theta",
+            "vartheta",
+            "iota",
+            "kappa",
+            "varkappa",
+            "lambda",
+            "mu",
+            "nu",
+            "xi",
+            "pi",
+            "varpi",
+            "rho",
+            "varrho",
+            "sigma",
+            "varsigma",
+            "tau",
+            "upsilon",
+            "phi",
+            "varphi",
+            "chi",
+            "psi",
+            "omega",
+            "Gamma",
+            "Delta",
+            "Theta",
+            "Lambda",
+            "Xi",
+            "Pi",
+            "Sigma",
+            "Upsilon",
+            "Phi",
+            "Psi",
+            "Omega",
+            "varGamma",
+            "varDelta",
+            "varTheta",
+            "varLambda",
+            "varXi",
+            "varPi",
+            "varSigma",
+            "varUpsilon",
+            "varPhi",
+            "varPsi",
+            "varOmega",
+            "digamma",
+            "aleph",
+            "beth",
+            "gimel",
+            "daleth",
+        ]);
+
+        // Binary operators
+        commands.extend(&[
+            "pm",
+            "mp",
+            "times",
+            "div",
+            "cdot",
+            "cdotp",
+            "ldotp",
+            "ast",
+            "star",
+            "circ",
+            "bullet",
+            "oplus",
+            "ominus",
+            "otimes",
+            "oslash",
+            "odot",
+            "bigcirc",
+            "dagger",
+            "ddagger",
+            "amalg",
+            "cap",
+            "cup",
+            "uplus",
+            "sqcap",
+            "sqcup",
+            "vee",
+            "wedge",
+            "land",
+            "lor",
+            "wr",
+            "setminus",
+            "smallsetminus",
+            "diamond",
+            "bigtriangleup",
+            "bigtriangledown",
+            "triangleleft",
+            "triangleright",
+            "lhd",
+            "rhd",
+            "unlhd",
+            "unrhd",
+            "dotplus",
+            "ltimes",
+            "rtimes",
+            "leftthreetimes",
+            "rightthreetimes",
+            "curlywedge",
+            "curlyvee",
+            "barwedge",
+            "doublebarwedge",
+            "boxplus",
+            "boxminus",
+            "boxtimes",
+            "boxdot",
+            "centerdot",
+            "intercal",
+            "divideontimes",
+            "Cap",
+            "Cup",
+        ]);
+
+        // Relations
+        commands.extend(&[
+            "leq",
+            "le",
+            "geq",
+            "ge",
+            "neq",
+            "ne",
+            "equiv",
+            "sim",
+            "simeq",
+            "approx",
+            "cong",
+            "propto",
+            "prec",
+            "succ",
+            "preceq",
+            "succeq",
+            "ll",
+            "gg",
+            "lll",
+            "ggg",
+            "subset",
+            "supset",
+            "subseteq",
+            "supseteq",
+            "subsetneq",
+            "supsetneq",
+            "nsubseteq",
+            "nsupseteq",
+            "sqsubset",
+            "sqsupset",
+            "sqsubseteq",
+            "sqsupseteq",
+            "in",
+            "ni",
+            "notin",
+            "owns",
+            "vdash",
+            "dashv",
+            "models",
+            "vDash",
+            "Vdash",
+            "Vvdash",
+            "perp",
+            "mid",
+            "nmid",
+            "parallel",
+            "nparallel",
+            "asymp",
+            "bowtie",
+            "smile",
+            "frown",
+            "doteq",
+            "triangleq",
+            "lesssim",
+            "gtrsim",
+            "leqslant",
+            "geqslant",
+            "leqq",
+            "geqq",
+            "lessapprox",
+            "gtrapprox",
+            "lessgtr",
+            "gtrless",
+            "nless",
+            "ngtr",
+            "nleq",
+            "ngeq",
+            "ncong",
+            "nsim",
+            "approxeq",
+            "thicksim",
+            "thickapprox",
+            "backsim",
+            "therefore",
+            "because",
+            "varpropto",
+            "between",
+            "pitchfork",
+        ]);
+
+        // Arrows
+        commands.extend(&[
+            "leftarrow",
+            "gets",
+            "rightarrow",
+            "to",
+            "leftrightarrow",
+            "Leftarrow",
+            "Rightarrow",
+            "Leftrightarrow",
+            "uparrow",
+            "downarrow",
+            "updownarrow",
+            "Uparrow",
+            "Downarrow",
+            "Updownarrow",
+            "longleftarrow",
+            "longrightarrow",
+            "longleftrightarrow",
+            "Longleftarrow",
+            "Longrightarrow",
+            "Longleftrightarrow",
+            "iff",
+            "implies",
+            "impliedby",
+            "mapsto",
+            "longmapsto",
+            "hookleftarrow",
+            "hookrightarrow",
+            "nearrow",
+            "searrow",
+            "swarrow",
+            "nwarrow",
+            "leftharpoonup",
+            "leftharpoondown",
+            "rightharpoonup",
+            "rightharpoondown",
+            "rightleftharpoons",
+            "leftrightharpoons",
+            "twoheadrightarrow",
+            "twoheadleftarrow",
+            "rightsquigarrow",
+            "leadsto",
+            "circlearrowleft",
+            "circlearrowright",
+            "curvearrowleft",
+            "curvearrowright",
+            "nrightarrow",
+            "nleftarrow",
+            "nRightarrow",
+            "nLeftarrow",
+            "nleftrightarrow",
+            "nLeftrightarrow",
+            "xrightarrow",
+            "xleftarrow",
+        ]);
+
+        // Other symbols
+        commands.extend(&[
+            "infty",
+            "nabla",
+            "partial",
+            "forall",
+            "exists",
+            "nexists",
+            "emptyset",
+            "varnothing",
+            "neg",
+            "lnot",
+            "top",
+            "bot",
+            "angle",
+            "measuredangle",
+            "sphericalangle",
+            "triangle",
+            "square",
+            "blacksquare",
+            "lozenge",
+            "blacklozenge",
+            "Box",
+            "Diamond",
+            "clubsuit",
+            "diamondsuit",
+            "heartsuit",
+            "spadesuit",
+            "flat",
+            "natural",
+            "sharp",
+            "hbar",
+            "hslash",
+            "ell",
+            "wp",
+            "Re",
+            "Im",
+            "mho",
+            "prime",
+            "backprime",
+            "surd",
+            "complement",
+            "eth",
+            "imath",
+            "jmath",
+            "dots",
+            "ldots",
+            "cdots",
+            "vdots",
+            "ddots",
+            "dotsc",
+            "dotsb",
+            "dotsm",
+            "dotsi",
+            "dotso",
+            "checkmark",
+            "colon",
+            "not",
+            "backslash",
+        ]);
+
+        // Large operators
+        commands.extend(&[
+            "sum",
+            "prod",
+            "coprod",
+            "int",
+            "iint",
+            "iiint",
+            "iiiint",
+            "idotsint",
+            "oint",
+            "bigcup",
+            "bigcap",
+            "bigvee",
+            "bigwedge",
+            "bigoplus",
+            "bigotimes",
+            "bigodot",
+            "biguplus",
+            "bigsqcup",
+        ]);
+
+        // Named functions
+        commands.extend(&[
+            "sin",
+            "cos",
+            "tan",
+            "cot",
+            "sec",
+            "csc",
+            "arcsin",
+            "arccos",
+            "arctan",
+            "sinh",
+            "cosh",
+            "tanh",
+            "coth",
+            "exp",
+            "log",
+            "ln",
+            "lg",
+            "lim",
+            "liminf",
+            "limsup",
+            "varliminf",
+            "varlimsup",
+            "sup",
+            "inf",
+            "max",
+            "min",
+            "arg",
+            "deg",
+            "det",
+            "dim",
+            "gcd",
+            "hom",
+            "ker",
+            "Pr",
+            "injlim",
+            "projlim",
+            "mod",
+            "bmod",
+            "pmod",
+            "pod",
+            "operatorname",
+        ]);
+
+        // Accents
+        commands.extend(&[
+            "hat",
+            "widehat",
+            "tilde",
+            "widetilde",
+            "bar",
+            "overline",
+            "underline",
+            "vec",
+            "overrightarrow",
+            "overleftarrow",
+            "overleftrightarrow",
+            "underrightarrow",
+            "underleftarrow",
+            "underleftrightarrow",
+            "dot",
+            "ddot",
+            "dddot",
+            "ddddot",
+            "acute",
+            "grave",
+            "check",
+            "breve",
+            "mathring",
+            "overbrace",
+            "underbrace",
+        ]);
+
+        // Delimiters
+        commands.extend(&[
+            "left",
+            "right",
+            "middle",
+            "big",
+            "Big",
+            "bigg",
+            "Bigg",
+            "bigl",
+            "bigr",
+            "Bigl",
+            "Bigr",
+            "biggl",
+            "biggr",
+            "Biggl",
+            "Biggr",
+            "langle",
+            "rangle",
+            "lfloor",
+            "rfloor",
+            "lceil",
+            "rceil",
+            "lvert",
+            "rvert",
+            "lVert",
+            "rVert",
+            "vert",
+            "Vert",
+            "lbrace",
+            "rbrace",
+            "lbrack",
+            "rbrack",
+            "ulcorner",
+            "urcorner",
+            "llcorner",
+            "lrcorner",
+        ]);
+
+        // Fractions, roots, and stacking
+        commands.extend(&[
+            "frac",
+            "dfrac",
+            "tfrac",
+            "cfrac",
+            "binom",
+            "dbinom",
+            "tbinom",
+            "genfrac",
+            "over",
+            "atop",
+            "choose",
+            "brace",
+            "brack",
+            "sqrt",
+            "root",
+            "of",
+            "leftroot",
+            "uproot",
+            "stackrel",
+            "overset",
+            "underset",
+            "substack",
+            "sideset",
+            "boxed",
+        ]);
+
+        // Fonts and styles
+        commands.extend(&[
+            "mathrm",
+            "mathbf",
+            "mathit",
+            "mathsf",
+            "mathtt",
+            "mathcal",
+            "mathbb",
+            "mathfrak",
+            "mathscr",
+            "mathnormal",
+            "boldsymbol",
+            "pmb",
+            "text",
+            "textrm",
+            "textbf",
+            "textit",
+            "textsf",
+            "texttt",
+            "textup",
+            "textnormal",
+            "emph",
+            "color",
+            "displaystyle",
+            "textstyle",
+            "scriptstyle",
+            "scriptscriptstyle",
+        ]);
+
+        // Spacing and layout
+        commands.extend(&[
+            "quad",
+            "qquad",
+            "enspace",
+            "thinspace",
+            "negthinspace",
+            "medspace",
+            "negmedspace",
+            "thickspace",
+            "negthickspace",
+            "hspace",
+            "mspace",
+            "phantom",
+            "hphantom",
+            "vphantom",
+            "smash",
+            "mathstrut",
+            "strut",
+            "limits",
+            "nolimits",
+            "displaylimits",
+            "mathop",
+            "mathbin",
+            "mathrel",
+            "mathord",
+            "mathpunct",
+            "mathopen",
+            "mathclose",
+            "allowbreak",
+            "hline",
+            "cline",
+            "intertext",
+            "tag",
+            "notag",
+            "nonumber",
+            "label",
+            "eqref",
+            "ref",
+            "begin",
+            "end",
+        ]);
+
+        commands
+    };
+}
@@ -0,0 +1,134 @@
+/*
+ * render/latex/mod.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Renders a syntax tree as a standalone LaTeX document.
+//!
+//! This is intended for print, such as for anthologies. Content which only
+//! makes sense on a web page is adapted: collapsibles are expanded, tabs are
+//! shown one after another, and embedded HTML or iframes become links.
+//!
+//! The document can be compiled with `pdflatex`, but it only supports
+//! Latin, Greek, and Cyrillic text. For other scripts, such as Chinese,
+//! Japanese, or Korean, use `xelatex` or `lualatex`, where the preamble
+//! loads `fontspec` instead. A font covering the text still needs to be set,
+//! such as with `\setmainfont`, or by adding `xeCJK` or `luatexja`.
+
+#[cfg(test)]
+mod test;
+
+mod context;
+mod elements;
+mod escape;
+mod math;
+
+use self::context::LatexContext;
+use self::elements::render_elements;
+use self::escape::escape;
+use crate::data::PageInfo;
+use crate::log::prelude::*;
+use crate::render::{typography, Handle, Render};
+use crate::settings::WikitextSettings;
+use crate::tree::SyntaxTree;
+
+/// The packages used by the elements this renderer produces.
+///
+/// Font encoding is chosen based on the engine, since `inputenc` and
+/// `fontenc` don't support most scripts. See the module documentation.
+const PREAMBLE: &str = "\
+\\documentclass{article}
+\\usepackage{iftex}
+\\ifPDFTeX
+\\usepackage[utf8]{inputenc}
+\\usepackage[T1]{fontenc}
+\\else
+\\usepackage{fontspec}
+\\fi
+\\usepackage{amsmath}
+\\usepackage{amssymb}
+\\usepackage{array}
+\\usepackage{graphicx}
+\\usepackage{longtable}
+\\usepackage[normalem]{ulem}
+\\usepackage{xcolor}
+\\usepackage{hyperref}
+";
+
+#[derive(Debug)]
+pub struct LatexRender;
+
+impl Render for LatexRender {
+    type Output = String;
+
+    fn render(
+        &self,
+        log: &Logger,
+        tree: &SyntaxTree,
+        page_info: &PageInfo,
+        settings: &WikitextSettings,
+    ) -> String {
+        info!(
+            log,
+            "Rendering syntax tree";
+            "target" => "latex",
+            "site" => page_info.site.as_ref(),
+            "page" => page_info.page.as_ref(),
+            "category" => match &page_info.category {
+                Some(category) => category.as_ref(),
+                None => "_default",
+            },
+        );
+
+        let typography = typography::get_substitutions(log, tree, &settings.typography);
+        let mut ctx = LatexContext::new(
+            page_info,
+            &Handle,
+            settings,
+            &tree.footnotes,
+            &tree.bibliographies,
+            &typography,
+        );
+
+        // Document header
+        ctx.push_raw_str(PREAMBLE);
+        ctx.push_raw_str("\n\\title{");
+        ctx.push_escaped(&page_info.title);
+
+        if let Some(ref alt_title) = page_info.alt_title {
+            ctx.push_raw_str("\\\\\n\\large ");
+            ctx.push_escaped(alt_title);
+        }
+
+        ctx.push_raw_str("}\n\\date{}\n\n\\begin{document}\n\\maketitle\n\n");
+
+        // Document body
+        render_elements(log, &mut ctx, &tree.elements);
+
+        ctx.end_paragraph();
+        ctx.push_raw_str("\\end{document}\n");
+        ctx.into()
+    }
+}
+
+/// Escapes text for LaTeX, so it is typeset as-is.
+pub fn escape_latex(text: &str) -> String {
+    let mut buffer = String::new();
+    escape(&mut buffer, text);
+    buffer
+}
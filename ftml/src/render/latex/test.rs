@@ -0,0 +1,77 @@
+/*
+ * render/latex/test.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Runs the golden-file tests in `/test/latex`.
+//!
+//! Each `.ftml` file is rendered and compared against the `.tex` file of the same name.
+
+use super::LatexRender;
+use crate::data::PageInfo;
+use crate::render::Render;
+use crate::settings::{WikitextMode, WikitextSettings};
+use std::fs;
+use std::path::PathBuf;
+
+lazy_static! {
+    static ref TEST_DIRECTORY: PathBuf = {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("test");
+        path.push("latex");
+        path
+    };
+}
+
+#[test]
+fn latex() {
+    let log = crate::build_logger();
+    let page_info = PageInfo::dummy();
+    let settings = WikitextSettings::from_mode(WikitextMode::Page);
+
+    let mut paths: Vec<PathBuf> = fs::read_dir(&*TEST_DIRECTORY)
+        .expect("Unable to read directory")
+        .map(|entry| entry.expect("Unable to read directory entry").path())
+        .filter(|path| path.extension().map(|s| s == "ftml").unwrap_or(false))
+        .collect();
+
+    paths.sort();
+    assert!(!paths.is_empty(), "No LaTeX tests found");
+
+    for path in paths {
+        println!("+ {}", path.display());
+
+        let mut input = fs::read_to_string(&path).expect("Unable to read input");
+        let expected = fs::read_to_string(path.with_extension("tex"))
+            .expect("Unable to read expected output")
+            .replace("\r\n", "\n");
+
+        crate::preprocess(&log, &mut input, &settings);
+        let tokens = crate::tokenize(&log, &input);
+        let result = crate::parse(&log, &tokens, &page_info, &settings);
+        let (tree, _warnings) = result.into();
+        let output = LatexRender.render(&log, &tree, &page_info, &settings);
+
+        assert_eq!(
+            output,
+            expected,
+            "Rendered LaTeX for {} doesn't match expected",
+            path.display(),
+        );
+    }
+}
@@ -29,12 +29,16 @@ mod prelude {
 pub mod debug;
 pub mod html;
 pub mod json;
+pub mod latex;
 pub mod null;
+pub mod search;
 pub mod text;
 
+mod bidi;
 mod handle;
+mod typography;
 
-use self::handle::{Handle, ModuleRenderMode};
+pub(crate) use self::handle::{Handle, ModuleRenderMode};
 use crate::data::PageInfo;
 use crate::log::prelude::*;
 use crate::settings::WikitextSettings;
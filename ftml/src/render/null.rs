@@ -47,7 +47,14 @@ fn null() {
     let log = crate::build_logger();
     let page_info = PageInfo::dummy();
     let settings = WikitextSettings::from_mode(WikitextMode::Page);
-    let result = SyntaxTree::from_element_result(vec![], vec![], vec![], vec![], vec![]);
+    let result = SyntaxTree::from_element_result(
+        vec![],
+        vec![],
+        vec![],
+        vec![],
+        vec![],
+        crate::tree::BibliographyList::new(),
+    );
     let (tree, _) = result.into();
     let output = NullRender.render(&log, &tree, &page_info, &settings);
 
@@ -0,0 +1,212 @@
+/*
+ * render/search/context.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::output::{SearchCode, SearchHeading, SearchLink, SearchOutput, SearchSection};
+use crate::data::PageInfo;
+use crate::render::Handle;
+use crate::tree::{BibliographyList, Element};
+
+/// Average reading speed used for estimates, in words per minute.
+const READING_SPEED: usize = 200;
+
+#[derive(Debug)]
+pub struct SearchContext<'h, 'e, 't>
+where
+    'e: 't,
+{
+    output: SearchOutput,
+    handle: &'h Handle,
+
+    //
+    // Fields from syntax tree
+    //
+    footnotes: &'e [Vec<Element<'t>>],
+    bibliographies: &'e BibliographyList<'t>,
+
+    //
+    // Other fields to track
+    //
+    footnote_index: usize,
+    heading: Option<usize>,
+    hidden_depth: usize,
+
+    /// Buffers for text which is being collected separately,
+    /// such as heading or link text. Text goes to the last one.
+    captures: Vec<String>,
+}
+
+impl<'h, 'e, 't> SearchContext<'h, 'e, 't> {
+    pub fn new(
+        info: &PageInfo,
+        handle: &'h Handle,
+        footnotes: &'e [Vec<Element<'t>>],
+        bibliographies: &'e BibliographyList<'t>,
+    ) -> Self {
+        SearchContext {
+            output: SearchOutput {
+                title: str!(info.title),
+                alt_title: info.alt_title.as_ref().map(|title| str!(title)),
+                headings: Vec::new(),
+                sections: Vec::new(),
+                links: Vec::new(),
+                image_alts: Vec::new(),
+                code_blocks: Vec::new(),
+                word_count: 0,
+                reading_time: 0,
+            },
+            handle,
+            footnotes,
+            bibliographies,
+            footnote_index: 0,
+            heading: None,
+            hidden_depth: 0,
+            captures: Vec::new(),
+        }
+    }
+
+    // Getters
+    #[inline]
+    pub fn handle(&self) -> &'h Handle {
+        self.handle
+    }
+
+    #[inline]
+    pub fn bibliographies(&self) -> &'e BibliographyList<'t> {
+        self.bibliographies
+    }
+
+    pub fn next_footnote(&mut self) -> Option<&'e [Element<'t>]> {
+        let footnote = self.footnotes.get(self.footnote_index);
+        self.footnote_index += 1;
+        footnote.map(|elements| elements.as_slice())
+    }
+
+    // Body text
+    pub fn push_str(&mut self, text: &str) {
+        if let Some(capture) = self.captures.last_mut() {
+            capture.push_str(text);
+            return;
+        }
+
+        let heading = self.heading;
+        let hidden = self.hidden_depth > 0;
+
+        match self.output.sections.last_mut() {
+            Some(section) if section.heading == heading && section.hidden == hidden => {
+                section.text.push_str(text);
+            }
+            _ => self.output.sections.push(SearchSection {
+                heading,
+                hidden,
+                text: str!(text),
+            }),
+        }
+    }
+
+    /// Separates the text on each side, such as between paragraphs.
+    #[inline]
+    pub fn push_break(&mut self) {
+        self.push_str(" ");
+    }
+
+    #[inline]
+    pub fn start_capture(&mut self) {
+        self.captures.push(String::new());
+    }
+
+    pub fn end_capture(&mut self) -> String {
+        let text = self
+            .captures
+            .pop()
+            .expect("Ended capture when none were started");
+
+        normalize_whitespace(&text)
+    }
+
+    #[inline]
+    pub fn enter_hidden(&mut self) {
+        self.hidden_depth += 1;
+    }
+
+    #[inline]
+    pub fn exit_hidden(&mut self) {
+        self.hidden_depth -= 1;
+    }
+
+    // Extracted items
+    pub fn add_heading(&mut self, level: u8, text: String) {
+        self.heading = Some(self.output.headings.len());
+        self.output.headings.push(SearchHeading { level, text });
+    }
+
+    #[inline]
+    pub fn add_link(&mut self, text: String, url: String) {
+        self.output.links.push(SearchLink { text, url });
+    }
+
+    #[inline]
+    pub fn add_image_alt(&mut self, alt: &str) {
+        self.output.image_alts.push(str!(alt));
+    }
+
+    #[inline]
+    pub fn add_code(&mut self, language: Option<&str>, contents: &str) {
+        self.output.code_blocks.push(SearchCode {
+            language: language.map(|language| str!(language)),
+            contents: str!(contents),
+        });
+    }
+}
+
+impl<'h, 'e, 't> From<SearchContext<'h, 'e, 't>> for SearchOutput {
+    fn from(ctx: SearchContext<'h, 'e, 't>) -> SearchOutput {
+        let mut output = ctx.output;
+
+        for section in &mut output.sections {
+            section.text = normalize_whitespace(&section.text);
+        }
+
+        output.sections.retain(|section| !section.text.is_empty());
+
+        output.word_count = output
+            .sections
+            .iter()
+            .filter(|section| !section.hidden)
+            .map(|section| section.text.split_whitespace().count())
+            .sum();
+
+        output.reading_time = (output.word_count + READING_SPEED - 1) / READING_SPEED;
+        output
+    }
+}
+
+fn normalize_whitespace(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+
+    for word in text.split_whitespace() {
+        if !output.is_empty() {
+            output.push(' ');
+        }
+
+        output.push_str(word);
+    }
+
+    output
+}
@@ -0,0 +1,226 @@
+/*
+ * render/search/elements.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Module that extracts searchable content from `Element` and its children.
+
+use super::SearchContext;
+use crate::log::prelude::*;
+use crate::tree::visit::{
+    walk_container, walk_element, walk_list_item, walk_table_cell, Visitor,
+};
+use crate::tree::{
+    BibliographyEntry, Container, ContainerType, DefinitionListItem, Element,
+    GalleryImage, LinkLocation, ListItem, TableCell,
+};
+use crate::url::normalize_link;
+
+pub fn extract_elements<'a>(
+    log: &Logger,
+    ctx: &mut SearchContext<'_, 'a, 'a>,
+    elements: &'a [Element<'a>],
+) {
+    ElementExtractor { log, ctx }.visit_elements(elements);
+}
+
+/// Visitor which adds the searchable content of each element to the context.
+///
+/// Elements with no searchable text are only walked through,
+/// in case they have children which do.
+struct ElementExtractor<'l, 'c, 'h, 'a> {
+    log: &'l Logger,
+    ctx: &'c mut SearchContext<'h, 'a, 'a>,
+}
+
+impl<'a> Visitor<'a> for ElementExtractor<'_, '_, '_, 'a> {
+    fn visit_element(&mut self, element: &'a Element<'a>) {
+        debug!(self.log, "Extracting element"; "element" => element.name());
+
+        // Separate block elements from surrounding text
+        let block = !element.paragraph_safe();
+        if block {
+            self.ctx.push_break();
+        }
+
+        match element {
+            Element::Text(text) | Element::Raw(text) | Element::Email(text) => {
+                self.ctx.push_str(text)
+            }
+            Element::User { name, .. } => self.ctx.push_str(name),
+            Element::TabView(tabs) => {
+                // Only the first tab is shown initially
+                for (index, tab) in tabs.iter().enumerate() {
+                    if index > 0 {
+                        self.ctx.enter_hidden();
+                    }
+
+                    self.ctx.push_str(&tab.label);
+                    self.ctx.push_break();
+                    self.visit_tab(tab);
+                    self.ctx.push_break();
+
+                    if index > 0 {
+                        self.ctx.exit_hidden();
+                    }
+                }
+            }
+            Element::Anchor {
+                elements,
+                attributes,
+                ..
+            } => {
+                self.ctx.start_capture();
+                self.visit_elements(elements);
+                let text = self.ctx.end_capture();
+
+                self.ctx.push_str(&text);
+
+                if let Some(href) = attributes.get().get("href") {
+                    let link = LinkLocation::parse(cow!(href));
+                    let url = normalize_link(&link, self.ctx.handle());
+
+                    self.ctx.add_link(text, url.into_owned());
+                }
+            }
+            Element::Link { link, label, .. } => {
+                let url = normalize_link(link, self.ctx.handle());
+                let mut text = String::new();
+
+                self.ctx
+                    .handle()
+                    .get_link_label(self.log, link, label, |label| {
+                        text.push_str(label);
+                    });
+
+                self.ctx.push_str(&text);
+                self.ctx.add_link(text, url.into_owned());
+            }
+            Element::Image { attributes, .. } => {
+                if let Some(alt) = attributes.get().get("alt") {
+                    self.ctx.add_image_alt(alt);
+                }
+            }
+            Element::Collapsible { start_open, .. } => {
+                if !start_open {
+                    self.ctx.enter_hidden();
+                }
+
+                walk_element(self, element);
+
+                if !start_open {
+                    self.ctx.exit_hidden();
+                }
+            }
+            Element::Footnote => {
+                // Footnote contents are indexed where they are referenced
+                if let Some(elements) = self.ctx.next_footnote() {
+                    self.ctx.push_break();
+                    self.visit_elements(elements);
+                    self.ctx.push_break();
+                }
+            }
+            Element::BibliographyBlock { index, hide, .. } => {
+                let bibliographies = self.ctx.bibliographies();
+
+                if let Some((_, bibliography)) = bibliographies.get_bibliography(*index) {
+                    if *hide {
+                        self.ctx.enter_hidden();
+                    }
+
+                    for entry in &bibliography.entries {
+                        self.visit_bibliography_entry(entry);
+                    }
+
+                    if *hide {
+                        self.ctx.exit_hidden();
+                    }
+                }
+            }
+            Element::Code { contents, language } => {
+                self.ctx.add_code(language.as_deref(), contents);
+            }
+            Element::LineBreak | Element::LineBreaks(_) => self.ctx.push_break(),
+            Element::Partial(_) => panic!("Encountered partial element during parsing"),
+            _ => walk_element(self, element),
+        }
+
+        if block {
+            self.ctx.push_break();
+        }
+    }
+
+    fn visit_container(&mut self, container: &'a Container<'a>) {
+        match container.ctype() {
+            ContainerType::Header(heading) => {
+                self.ctx.start_capture();
+                walk_container(self, container);
+                let text = self.ctx.end_capture();
+
+                self.ctx.add_heading(heading.level.value(), text);
+            }
+            ContainerType::Hidden | ContainerType::Invisible => {
+                self.ctx.enter_hidden();
+                walk_container(self, container);
+                self.ctx.exit_hidden();
+            }
+            ContainerType::RubyText => {
+                self.ctx.push_break();
+                walk_container(self, container);
+                self.ctx.push_break();
+            }
+            _ => walk_container(self, container),
+        }
+    }
+
+    fn visit_table_cell(&mut self, cell: &'a TableCell<'a>) {
+        walk_table_cell(self, cell);
+        self.ctx.push_break();
+    }
+
+    fn visit_list_item(&mut self, item: &'a ListItem<'a>) {
+        walk_list_item(self, item);
+
+        if let ListItem::Elements { .. } = item {
+            self.ctx.push_break();
+        }
+    }
+
+    fn visit_definition_list_item(&mut self, item: &'a DefinitionListItem<'a>) {
+        self.visit_elements(&item.key);
+        self.ctx.push_break();
+        self.visit_elements(&item.value);
+        self.ctx.push_break();
+    }
+
+    fn visit_gallery_image(&mut self, image: &'a GalleryImage<'a>) {
+        if let Some(alt) = image.attributes.get().get("alt") {
+            self.ctx.add_image_alt(alt);
+        }
+
+        if let Some(ref caption) = image.caption {
+            self.ctx.push_str(caption);
+            self.ctx.push_break();
+        }
+    }
+
+    fn visit_bibliography_entry(&mut self, entry: &'a BibliographyEntry<'a>) {
+        self.visit_elements(&entry.elements);
+        self.ctx.push_break();
+    }
+}
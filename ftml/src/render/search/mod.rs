@@ -0,0 +1,73 @@
+/*
+ * render/search/mod.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! A renderer which extracts searchable content from a page.
+//!
+//! Unlike `TextRender`, this is not intended for display. The output
+//! separates out headings, links, image alt text, and code blocks,
+//! with the body text split by section and hidden content flagged,
+//! so that a search index can weigh each appropriately.
+
+#[cfg(test)]
+mod test;
+
+mod context;
+mod elements;
+mod output;
+
+pub use self::output::{
+    SearchCode, SearchHeading, SearchLink, SearchOutput, SearchSection,
+};
+
+use self::context::SearchContext;
+use self::elements::extract_elements;
+use crate::data::PageInfo;
+use crate::log::prelude::*;
+use crate::render::{Handle, Render};
+use crate::settings::WikitextSettings;
+use crate::tree::SyntaxTree;
+
+#[derive(Debug)]
+pub struct SearchRender;
+
+impl Render for SearchRender {
+    type Output = SearchOutput;
+
+    fn render(
+        &self,
+        log: &Logger,
+        tree: &SyntaxTree,
+        page_info: &PageInfo,
+        _settings: &WikitextSettings,
+    ) -> SearchOutput {
+        info!(
+            log,
+            "Extracting searchable content from syntax tree";
+            "site" => page_info.site.as_ref(),
+            "page" => page_info.page.as_ref(),
+        );
+
+        let mut ctx =
+            SearchContext::new(page_info, &Handle, &tree.footnotes, &tree.bibliographies);
+
+        extract_elements(log, &mut ctx, &tree.elements);
+        ctx.into()
+    }
+}
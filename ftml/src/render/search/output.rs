@@ -0,0 +1,84 @@
+/*
+ * render/search/output.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+/// Searchable content extracted from a page, as produced by `SearchRender`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct SearchOutput {
+    pub title: String,
+    pub alt_title: Option<String>,
+
+    /// All headings on the page, in order.
+    pub headings: Vec<SearchHeading>,
+
+    /// The body text of the page, split by heading.
+    ///
+    /// Hidden or collapsed content is placed in its own section,
+    /// so it can be weighted differently from visible text.
+    pub sections: Vec<SearchSection>,
+
+    /// The text of each link, along with where it points.
+    pub links: Vec<SearchLink>,
+
+    /// The alt text of each image.
+    pub image_alts: Vec<String>,
+
+    /// Code blocks, which are not included in the body text.
+    pub code_blocks: Vec<SearchCode>,
+
+    /// The number of words of visible body text.
+    pub word_count: usize,
+
+    /// The estimated time to read the page, in minutes.
+    pub reading_time: usize,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct SearchHeading {
+    pub level: u8,
+    pub text: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct SearchSection {
+    /// The index of the heading this text is under, or `None` if it comes before any.
+    pub heading: Option<usize>,
+
+    /// Whether this text is hidden or collapsed when the page is first shown.
+    pub hidden: bool,
+
+    pub text: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct SearchLink {
+    pub text: String,
+    pub url: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct SearchCode {
+    pub language: Option<String>,
+    pub contents: String,
+}
@@ -0,0 +1,143 @@
+/*
+ * render/search/test.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::*;
+use crate::settings::WikitextMode;
+
+#[test]
+fn search() {
+    let log = crate::build_logger();
+    let page_info = PageInfo::dummy();
+    let settings = WikitextSettings::from_mode(WikitextMode::Page);
+
+    let text = "\
+Intro with a [https://example.com/ link].
+
++ Apple **one**
+
+Red fruit.[[footnote]]Usually.[[/footnote]]
+
+[[image /a.png alt=\"An apple\"]]
+
+[[code type=\"rust\"]]
+let apple = 1;
+[[/code]]
+
+++ Banana
+
+[[collapsible]]
+Secret banana facts.
+[[/collapsible]]
+
+[[footnoteblock]]";
+
+    let tokenization = crate::tokenize(&log, text);
+    let result = crate::parse(&log, &tokenization, &page_info, &settings);
+    let (tree, _warnings) = result.into();
+    let output = SearchRender.render(&log, &tree, &page_info, &settings);
+
+    assert_eq!(output.title, "A page for the age");
+    assert_eq!(
+        output.headings,
+        vec![
+            SearchHeading {
+                level: 1,
+                text: str!("Apple one"),
+            },
+            SearchHeading {
+                level: 2,
+                text: str!("Banana"),
+            },
+        ],
+    );
+    assert_eq!(
+        output.sections,
+        vec![
+            SearchSection {
+                heading: None,
+                hidden: false,
+                text: str!("Intro with a link."),
+            },
+            SearchSection {
+                heading: Some(0),
+                hidden: false,
+                text: str!("Red fruit. Usually."),
+            },
+            SearchSection {
+                heading: Some(1),
+                hidden: true,
+                text: str!("Secret banana facts."),
+            },
+        ],
+    );
+    assert_eq!(
+        output.links,
+        vec![SearchLink {
+            text: str!("link"),
+            url: str!("https://example.com/"),
+        }],
+    );
+    assert_eq!(output.image_alts, vec![str!("An apple")]);
+    assert_eq!(
+        output.code_blocks,
+        vec![SearchCode {
+            language: Some(str!("rust")),
+            contents: str!("let apple = 1;"),
+        }],
+    );
+    assert_eq!(output.word_count, 7);
+    assert_eq!(output.reading_time, 1);
+}
+
+#[test]
+fn hidden_bibliography() {
+    let log = crate::build_logger();
+    let page_info = PageInfo::dummy();
+    let settings = WikitextSettings::from_mode(WikitextMode::Page);
+
+    let text = "\
+Cited((bibcite apple)).
+
+[[bibliography hide=\"true\"]]
+: apple : The apple.
+[[/bibliography]]";
+
+    let tokenization = crate::tokenize(&log, text);
+    let result = crate::parse(&log, &tokenization, &page_info, &settings);
+    let (tree, _warnings) = result.into();
+    let output = SearchRender.render(&log, &tree, &page_info, &settings);
+
+    assert_eq!(
+        output.sections,
+        vec![
+            SearchSection {
+                heading: None,
+                hidden: false,
+                text: str!("Cited."),
+            },
+            SearchSection {
+                heading: None,
+                hidden: true,
+                text: str!("The apple."),
+            },
+        ],
+    );
+    assert_eq!(output.word_count, 1);
+}
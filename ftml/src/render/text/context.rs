@@ -20,9 +20,10 @@
 
 use crate::data::PageInfo;
 use crate::non_empty_vec::NonEmptyVec;
+use crate::render::typography::Typography;
 use crate::render::Handle;
 use crate::settings::WikitextSettings;
-use crate::tree::{Element, VariableScopes};
+use crate::tree::{BibliographyList, Element, VariableScopes};
 use std::fmt::{self, Write};
 use std::num::NonZeroUsize;
 
@@ -46,6 +47,8 @@ where
     //
     table_of_contents: &'e [Element<'t>],
     footnotes: &'e [Vec<Element<'t>>],
+    bibliographies: &'e BibliographyList<'t>,
+    typography: &'e Typography,
 
     //
     // Other fields to track
@@ -79,6 +82,8 @@ where
         settings: &'e WikitextSettings,
         table_of_contents: &'e [Element<'t>],
         footnotes: &'e [Vec<Element<'t>>],
+        bibliographies: &'e BibliographyList<'t>,
+        typography: &'e Typography,
     ) -> Self {
         TextContext {
             output: String::new(),
@@ -88,6 +93,8 @@ where
             variables: VariableScopes::new(),
             table_of_contents,
             footnotes,
+            bibliographies,
+            typography,
             prefixes: Vec::new(),
             list_depths: NonEmptyVec::new(1),
             invisible: 0,
@@ -142,6 +149,16 @@ where
         self.footnotes
     }
 
+    #[inline]
+    pub fn bibliographies(&self) -> &'e BibliographyList<'t> {
+        self.bibliographies
+    }
+
+    #[inline]
+    pub fn typography(&self) -> &'e Typography {
+        self.typography
+    }
+
     pub fn next_equation_index(&mut self) -> NonZeroUsize {
         let index = self.equation_index;
         self.equation_index = NonZeroUsize::new(index.get() + 1).unwrap();
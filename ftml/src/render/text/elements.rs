@@ -24,7 +24,8 @@ use super::TextContext;
 use crate::log::prelude::*;
 use crate::render::ModuleRenderMode;
 use crate::tree::{
-    ContainerType, DefinitionListItem, Element, LinkLocation, ListItem, ListType, Tab,
+    ContainerType, DefinitionListItem, Element, LinkLocation, ListItem, ListType,
+    MediaType, Tab,
 };
 use crate::url::normalize_link;
 use std::borrow::Cow;
@@ -64,6 +65,15 @@ pub fn render_element(log: &Logger, ctx: &mut TextContext, element: &Element) {
                     (true, Some(heading.level.prefix_with_space()))
                 }
 
+                // Ruby text is rendered in parentheses after its base,
+                // as in "base (reading)".
+                ContainerType::RubyText => {
+                    ctx.push_str(" (");
+                    render_elements(log, ctx, container.elements());
+                    ctx.push(')');
+                    return;
+                }
+
                 // Inline or miscellaneous container.
                 _ => (false, None),
             };
@@ -97,9 +107,11 @@ pub fn render_element(log: &Logger, ctx: &mut TextContext, element: &Element) {
             ctx.handle()
                 .render_module(log, ctx.buffer(), module, ModuleRenderMode::Text)
         }
-        Element::Text(text) | Element::Raw(text) | Element::Email(text) => {
-            ctx.push_str(text)
+        Element::Text(text) => {
+            let text = ctx.typography().text(element, text);
+            ctx.push_str(text);
         }
+        Element::Raw(text) | Element::Email(text) => ctx.push_str(text),
         Element::Variable(name) => {
             let value = ctx.variables().get(name);
 
@@ -210,6 +222,69 @@ pub fn render_element(log: &Logger, ctx: &mut TextContext, element: &Element) {
                 None => str_write!(ctx, "Missing Image"),
             }
         }
+        Element::Gallery { images, .. } => {
+            if !ctx.ends_with_newline() {
+                ctx.add_newline();
+            }
+
+            // Render as a list of links to each image
+            for image in images {
+                let source_url = ctx.handle().get_image_link(
+                    log,
+                    &image.source,
+                    ctx.info(),
+                    ctx.settings(),
+                );
+
+                let label = image.label();
+                ctx.push_str("* ");
+
+                match source_url {
+                    Some(url) if url == label => ctx.push_str(&url),
+                    Some(url) => str_write!(ctx, "{}: {}", label, &url),
+                    None => str_write!(ctx, "{}: Missing Image", label),
+                }
+
+                if let Some(link) = &image.link {
+                    str_write!(ctx, " [Link: {}]", get_url_from_link(ctx, link));
+                }
+
+                ctx.add_newline();
+            }
+        }
+        Element::Media(media) => {
+            let source_url = ctx.handle().get_file_link(
+                log,
+                &media.source,
+                ctx.info(),
+                ctx.settings(),
+            );
+
+            let (label, missing) = match media.mtype {
+                MediaType::Audio => ("audio", "Missing Audio"),
+                MediaType::Video => ("video", "Missing Video"),
+            };
+
+            match source_url {
+                Some(url) => {
+                    str_write!(ctx, "Download {}: {}", label, &url);
+
+                    let captions_url = media.captions.as_ref().and_then(|source| {
+                        ctx.handle().get_file_link(
+                            log,
+                            source,
+                            ctx.info(),
+                            ctx.settings(),
+                        )
+                    });
+
+                    if let Some(url) = captions_url {
+                        str_write!(ctx, " [Captions: {}]", url);
+                    }
+                }
+                None => ctx.push_str(missing),
+            }
+        }
         Element::List { ltype, items, .. } => {
             if !ctx.ends_with_newline() {
                 ctx.add_newline();
@@ -359,6 +434,54 @@ pub fn render_element(log: &Logger, ctx: &mut TextContext, element: &Element) {
                 ctx.add_newline();
             }
         }
+        Element::BibliographyCite { label } => {
+            info!(log, "Rendering bibliography citation"; "label" => label.as_ref());
+
+            match ctx.bibliographies().get_reference(label) {
+                Some((number, _)) => str_write!(ctx, "[{}]", number),
+                None => str_write!(ctx, "[{}]", label),
+            }
+        }
+        Element::BibliographyBlock { index, title, hide } => {
+            info!(log, "Rendering bibliography block"; "index" => index);
+
+            if *hide {
+                return;
+            }
+
+            let (first, bibliography) =
+                match ctx.bibliographies().get_bibliography(*index) {
+                    Some((_, bibliography)) if bibliography.entries.is_empty() => return,
+                    Some(result) => result,
+                    None => return,
+                };
+
+            // Render bibliography title
+            let title_default;
+            let title: &str = match title {
+                Some(title) => title.as_ref(),
+                None => {
+                    title_default = ctx.handle().get_message(
+                        log,
+                        ctx.language(),
+                        "bibliography-block-title",
+                    );
+                    title_default
+                }
+            };
+
+            ctx.add_newline();
+            ctx.push_str(title);
+            ctx.add_newline();
+
+            // Render entries in order, continuing the numbering.
+            for (offset, entry) in bibliography.entries.iter().enumerate() {
+                str_write!(ctx, "{}. ", first + offset);
+
+                render_elements(log, ctx, &entry.elements);
+                ctx.add_newline();
+            }
+        }
         Element::User { name, .. } => ctx.push_str(name),
         Element::Date { value, format, .. } => {
             str_write!(ctx, "{}", value.format(format.as_ref()));
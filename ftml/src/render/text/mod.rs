@@ -25,9 +25,12 @@ use self::context::TextContext;
 use self::elements::render_elements;
 use crate::data::PageInfo;
 use crate::log::prelude::*;
+use crate::render::bidi::balance_controls;
+use crate::render::typography::{self, Typography};
 use crate::render::{Handle, Render};
 use crate::settings::WikitextSettings;
 use crate::tree::{Element, SyntaxTree};
+use std::borrow::Cow;
 
 #[derive(Debug)]
 pub struct TextRender;
@@ -41,17 +44,38 @@ impl TextRender {
         page_info: &PageInfo,
         settings: &WikitextSettings,
     ) -> String {
-        self.render_partial_direct(log, elements, page_info, settings, &[], &[])
+        let tree = SyntaxTree::default();
+        let typography = Typography::default();
+
+        self.render_partial_direct(log, elements, page_info, settings, &tree, &typography)
+    }
+
+    /// Renders the given elements, with the typography substitutions
+    /// of the tree they come from.
+    #[inline]
+    pub(crate) fn render_partial_typography(
+        &self,
+        log: &Logger,
+        elements: &[Element],
+        page_info: &PageInfo,
+        settings: &WikitextSettings,
+        typography: &Typography,
+    ) -> String {
+        let tree = SyntaxTree::default();
+
+        self.render_partial_direct(log, elements, page_info, settings, &tree, typography)
     }
 
+    /// Renders the given elements, using the rest of the tree for
+    /// its table of contents, footnotes, and bibliographies.
     fn render_partial_direct(
         &self,
         log: &Logger,
         elements: &[Element],
         page_info: &PageInfo,
         settings: &WikitextSettings,
-        table_of_contents: &[Element],
-        footnotes: &[Vec<Element>],
+        tree: &SyntaxTree,
+        typography: &Typography,
     ) -> String {
         info!(
             log,
@@ -65,8 +89,15 @@ impl TextRender {
             },
         );
 
-        let mut ctx =
-            TextContext::new(page_info, &Handle, settings, table_of_contents, footnotes);
+        let mut ctx = TextContext::new(
+            page_info,
+            &Handle,
+            settings,
+            &tree.table_of_contents,
+            &tree.footnotes,
+            &tree.bibliographies,
+            typography,
+        );
         render_elements(log, &mut ctx, elements);
 
         // Remove leading and trailing newlines
@@ -78,7 +109,13 @@ impl TextRender {
             ctx.buffer().pop();
         }
 
-        ctx.into()
+        // Close any bidirectional formatting left open,
+        // so it can't affect text placed after this output.
+        let output: String = ctx.into();
+        match balance_controls(&output) {
+            Cow::Borrowed(_) => output,
+            Cow::Owned(balanced) => balanced,
+        }
     }
 }
 
@@ -93,13 +130,15 @@ impl Render for TextRender {
         page_info: &PageInfo,
         settings: &WikitextSettings,
     ) -> String {
+        let typography = typography::get_substitutions(log, tree, &settings.typography);
+
         self.render_partial_direct(
             log,
             &tree.elements,
             page_info,
             settings,
-            &tree.table_of_contents,
-            &tree.footnotes,
+            tree,
+            &typography,
         )
     }
 }
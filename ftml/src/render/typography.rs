@@ -0,0 +1,532 @@
+/*
+ * render/typography.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Perform Wikidot's typographical modifications.
+//! For full information, see the original source file:
+//! https://github.com/gabrys/wikidot/blob/master/lib/Text_Wiki/Text/Wiki/Parse/Default/Typography.php
+//!
+//! The transformations performed here are listed:
+//! * `` .. '' to fancy double quotes
+//! * ` .. ' to fancy single quotes
+//! * ,, .. '' to fancy lowered double quotes
+//! * ... to an ellipsis
+//!
+//! Em dash conversion was originally implemented here, however
+//! it was moved to the parser to prevent typography from converting
+//! the `--` in `[!--` and `--]` into em dashes.
+//!
+//! Wikidot performs these on the raw text, which mangles code blocks, raw text,
+//! and URLs. Instead, these are done at render time, and only on `Element::Text`.
+//! Since quotes can surround formatting, e.g. ``` ``**bold**'' ```, the text of each
+//! run of inline elements is joined together before the substitutions are run,
+//! with the result split back into the original text elements afterwards.
+//! The tree is left as-is, with renderers substituting in the new text as they go.
+
+use crate::log::prelude::*;
+use crate::settings::{QuoteStyle, TypographySettings};
+use crate::tree::visit::{walk_element, Visitor};
+use crate::tree::{DefinitionListItem, Element, ListItem, SyntaxTree, Tab, TableCell};
+use regex::Regex;
+use std::collections::HashMap;
+use std::mem;
+use std::ops::Range;
+
+lazy_static! {
+    static ref SINGLE_QUOTES: Regex = Regex::new(r"`(.*?)'").unwrap();
+    static ref DOUBLE_QUOTES: Regex = Regex::new(r"``(.*?)''").unwrap();
+    static ref LOW_DOUBLE_QUOTES: Regex = Regex::new(r",,(.*?)''").unwrap();
+    static ref ELLIPSIS: Regex = Regex::new(r"(?:\.\.\.|\. \. \.)").unwrap();
+}
+
+/// The quotation marks to substitute in, as `(begin, end)` pairs.
+#[derive(Debug, Copy, Clone)]
+struct QuoteMarks {
+    double: (&'static str, &'static str),
+    single: (&'static str, &'static str),
+    low: (&'static str, &'static str),
+}
+
+impl QuoteStyle {
+    fn marks(self) -> QuoteMarks {
+        match self {
+            // “ - LEFT DOUBLE QUOTATION MARK
+            // ” - RIGHT DOUBLE QUOTATION MARK
+            // ‘ - LEFT SINGLE QUOTATION MARK
+            // ’ - RIGHT SINGLE QUOTATION MARK
+            // „ - DOUBLE LOW-9 QUOTATION MARK
+            QuoteStyle::English => QuoteMarks {
+                double: ("\u{201c}", "\u{201d}"),
+                single: ("\u{2018}", "\u{2019}"),
+                low: ("\u{201e}", "\u{201d}"),
+            },
+
+            // « - LEFT-POINTING DOUBLE ANGLE QUOTATION MARK
+            // » - RIGHT-POINTING DOUBLE ANGLE QUOTATION MARK
+            // ‹ - SINGLE LEFT-POINTING ANGLE QUOTATION MARK
+            // › - SINGLE RIGHT-POINTING ANGLE QUOTATION MARK
+            QuoteStyle::French => QuoteMarks {
+                double: ("\u{ab}", "\u{bb}"),
+                single: ("\u{2039}", "\u{203a}"),
+                low: ("\u{201e}", "\u{201d}"),
+            },
+
+            // ‚ - SINGLE LOW-9 QUOTATION MARK
+            QuoteStyle::German => QuoteMarks {
+                double: ("\u{201e}", "\u{201c}"),
+                single: ("\u{201a}", "\u{2018}"),
+                low: ("\u{201e}", "\u{201c}"),
+            },
+
+            // 「 - LEFT CORNER BRACKET
+            // 」 - RIGHT CORNER BRACKET
+            // 『 - LEFT WHITE CORNER BRACKET
+            // 』 - RIGHT WHITE CORNER BRACKET
+            QuoteStyle::Japanese => QuoteMarks {
+                double: ("\u{300c}", "\u{300d}"),
+                single: ("\u{300e}", "\u{300f}"),
+                low: ("\u{201e}", "\u{201d}"),
+            },
+        }
+    }
+}
+
+/// The typography substitutions for a syntax tree.
+///
+/// Rather than writing into a copy of the tree, renderers look up the
+/// new text of each text element with [`Typography::text()`] as they reach it.
+/// Elements are identified by their address, so this is only valid for the
+/// tree it was created from, for as long as that tree is borrowed.
+#[derive(Debug, Default)]
+pub struct Typography {
+    changes: HashMap<usize, String>,
+}
+
+impl Typography {
+    /// Gets the text of the given text element, after any substitutions.
+    #[inline]
+    pub fn text<'a>(&'a self, element: &Element, text: &'a str) -> &'a str {
+        match self.changes.get(&address(element)) {
+            Some(output) => output,
+            None => text,
+        }
+    }
+}
+
+#[inline]
+fn address(element: &Element) -> usize {
+    element as *const Element as usize
+}
+
+/// Finds the typography substitutions for a syntax tree, if enabled.
+pub fn get_substitutions(
+    log: &Logger,
+    tree: &SyntaxTree,
+    settings: &TypographySettings,
+) -> Typography {
+    if !settings.enabled {
+        debug!(log, "Typography disabled, skipping");
+        return Typography::default();
+    }
+
+    info!(
+        log,
+        "Performing typography substitutions";
+        "quotes" => format!("{:?}", settings.quotes),
+    );
+
+    let mut runs = TextRuns::default();
+    runs.visit_elements(&tree.elements);
+    runs.push_break();
+    runs.visit_elements(&tree.table_of_contents);
+
+    for footnote in &tree.footnotes {
+        runs.push_break();
+        runs.visit_elements(footnote);
+    }
+
+    for bibliography in tree.bibliographies.iter() {
+        for entry in &bibliography.entries {
+            runs.push_break();
+            runs.visit_bibliography_entry(entry);
+        }
+    }
+
+    let addresses = mem::take(&mut runs.addresses);
+    let changes = addresses
+        .into_iter()
+        .zip(runs.substitute(log, settings.quotes))
+        .filter_map(|(address, output)| output.map(|output| (address, output)))
+        .collect();
+
+    Typography { changes }
+}
+
+/// The joined text of many text elements, which can be modified and split back apart.
+///
+/// Each text element has the byte range of the joined text it occupies,
+/// and replaced text is given to whichever element the match started in.
+/// Other elements are represented by placeholder characters outside of any range:
+/// newlines for block elements and line breaks, since the substitutions do
+/// not span lines, and an object replacement character for inline elements.
+#[derive(Debug, Default)]
+struct TextRuns<'a> {
+    text: String,
+    spans: Vec<Range<usize>>,
+    nodes: Vec<&'a str>,
+    addresses: Vec<usize>,
+    replaced: bool,
+}
+
+impl<'a> TextRuns<'a> {
+    fn push_text(&mut self, node: &'a str) {
+        let start = self.text.len();
+        self.text.push_str(node);
+        self.spans.push(start..self.text.len());
+        self.nodes.push(node);
+    }
+
+    fn push_placeholder(&mut self, c: char) {
+        self.text.push(c);
+    }
+
+    #[inline]
+    fn push_break(&mut self) {
+        self.push_placeholder('\n');
+    }
+
+    fn push_nested(&mut self, elements: &'a [Element<'a>]) {
+        self.push_break();
+        self.visit_elements(elements);
+        self.push_break();
+    }
+
+    fn replace(&mut self, range: Range<usize>, replacement: &str) {
+        let Range { start, end } = range;
+        let new_end = start + replacement.len();
+
+        // Positions within the match are moved past the replacement,
+        // so it stays with the element containing the start of the match.
+        let shift = |position: usize| {
+            if position <= start {
+                position
+            } else if position < end {
+                new_end
+            } else {
+                position - end + new_end
+            }
+        };
+
+        // Elements ending before the match are unaffected
+        let first = self.spans.partition_point(|span| span.end <= start);
+        for span in &mut self.spans[first..] {
+            span.start = shift(span.start);
+            span.end = shift(span.end);
+        }
+
+        self.text.replace_range(range, replacement);
+        self.replaced = true;
+    }
+
+    /// Performs all the substitutions, and splits the results back apart.
+    ///
+    /// Returns the new text of each element, or `None` if it is unchanged.
+    fn substitute(mut self, log: &Logger, quotes: QuoteStyle) -> Vec<Option<String>> {
+        let mut buffer = String::new();
+        let marks = quotes.marks();
+
+        macro_rules! surround {
+            ($regex:expr, $marks:expr) => {
+                Replacer::RegexSurround {
+                    regex: &$regex,
+                    begin: $marks.0,
+                    end: $marks.1,
+                }
+                .replace(log, &mut self, &mut buffer)
+            };
+        }
+
+        // Quotes
+        surround!(DOUBLE_QUOTES, marks.double);
+        surround!(LOW_DOUBLE_QUOTES, marks.low);
+        surround!(SINGLE_QUOTES, marks.single);
+
+        // Miscellaneous
+        // … - HORIZONTAL ELLIPSIS
+        Replacer::RegexReplace {
+            regex: &ELLIPSIS,
+            replacement: "\u{2026}",
+        }
+        .replace(log, &mut self, &mut buffer);
+
+        if !self.replaced {
+            return vec![None; self.nodes.len()];
+        }
+
+        // Split the text back into its elements
+        let text = &self.text;
+
+        self.nodes
+            .iter()
+            .zip(&self.spans)
+            .map(|(node, span)| {
+                let output = &text[span.clone()];
+
+                if *node == output {
+                    None
+                } else {
+                    Some(str!(output))
+                }
+            })
+            .collect()
+    }
+}
+
+impl<'a> Visitor<'a> for TextRuns<'a> {
+    fn visit_element(&mut self, element: &'a Element<'a>) {
+        let inline = element.paragraph_safe();
+
+        match element {
+            Element::Text(text) => {
+                self.push_text(text);
+                self.addresses.push(address(element));
+            }
+            Element::LineBreak | Element::LineBreaks(_) => self.push_break(),
+
+            // Elements containing other elements, where text may be.
+            // The table, list, and tab parts each separate themselves.
+            Element::Container(_)
+            | Element::Anchor { .. }
+            | Element::Color { .. }
+            | Element::Collapsible { .. }
+            | Element::Include { .. } => {
+                if !inline {
+                    self.push_break();
+                }
+
+                walk_element(self, element);
+
+                if !inline {
+                    self.push_break();
+                }
+            }
+            Element::Table(_)
+            | Element::TabView(_)
+            | Element::List { .. }
+            | Element::DefinitionList(_)
+            | Element::Partial(_) => walk_element(self, element),
+
+            // OBJECT REPLACEMENT CHARACTER
+            _ if inline => self.push_placeholder('\u{fffc}'),
+            _ => self.push_break(),
+        }
+    }
+
+    fn visit_table_cell(&mut self, cell: &'a TableCell<'a>) {
+        self.push_nested(&cell.elements);
+    }
+
+    fn visit_tab(&mut self, tab: &'a Tab<'a>) {
+        self.push_nested(&tab.elements);
+    }
+
+    fn visit_list_item(&mut self, item: &'a ListItem<'a>) {
+        match item {
+            ListItem::Elements { elements, .. } => self.push_nested(elements),
+            ListItem::SubList { element } => self.visit_element(element),
+        }
+    }
+
+    fn visit_definition_list_item(&mut self, item: &'a DefinitionListItem<'a>) {
+        self.push_nested(&item.key);
+        self.push_nested(&item.value);
+    }
+}
+
+/// Helper struct to easily perform string replacements.
+#[derive(Debug)]
+enum Replacer<'a> {
+    /// Replaces any text matching the regular expression with the static string.
+    /// The entire match is used, any capture groups are ignored.
+    RegexReplace {
+        regex: &'a Regex,
+        replacement: &'a str,
+    },
+
+    /// Takes text matching the regular expression, and replaces the exterior.
+    ///
+    /// The regular expression must return the content to be preserved in
+    /// capture group 1, and surrounds it with the `begin` and `end` strings.
+    ///
+    /// For instance, say:
+    /// * `regex` matched `[% (.+) %]`
+    /// * `begin` was `<(`
+    /// * `end` was `)>`
+    ///
+    /// Then input string `[% wikidork %]` would become `<(wikidork)>`.
+    RegexSurround {
+        regex: &'a Regex,
+        begin: &'a str,
+        end: &'a str,
+    },
+}
+
+impl Replacer<'_> {
+    fn replace(&self, log: &Logger, runs: &mut TextRuns, buffer: &mut String) {
+        use self::Replacer::*;
+
+        match *self {
+            RegexReplace { regex, replacement } => {
+                debug!(
+                    log,
+                    "Running regular expression replacement";
+                    "type" => "regex",
+                    "pattern" => regex.as_str(),
+                    "replacement" => replacement,
+                );
+
+                while let Some(mtch) = regex.find(&runs.text) {
+                    let range = mtch.start()..mtch.end();
+
+                    runs.replace(range, replacement);
+                }
+            }
+            RegexSurround { regex, begin, end } => {
+                debug!(
+                    log,
+                    "Running regular expression capture replacement";
+                    "type" => "surround",
+                    "pattern" => regex.as_str(),
+                    "begin" => begin,
+                    "end" => end,
+                );
+
+                while let Some(capture) = regex.captures(&runs.text) {
+                    let full = capture
+                        .get(0)
+                        .expect("Regular expression lacks a full match");
+
+                    let inner = capture
+                        .get(1)
+                        .expect("Regular expression lacks a content group");
+
+                    let (begin_range, end_range) =
+                        (full.start()..inner.start(), inner.end()..full.end());
+
+                    // Replace the ending delimiter first, so the
+                    // indices for the beginning remain valid.
+                    buffer.clear();
+                    buffer.push_str(end);
+                    runs.replace(end_range, buffer);
+                    runs.replace(begin_range, begin);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+fn substitute_str(text: &str, quotes: QuoteStyle) -> String {
+    let log = crate::build_logger();
+    let mut runs = TextRuns::default();
+    runs.push_text(text);
+
+    match runs.substitute(&log, quotes).pop() {
+        Some(Some(output)) => output,
+        _ => str!(text),
+    }
+}
+
+#[cfg(test)]
+const TEST_CASES: [(&str, &str); 3] = [
+    (
+        "John laughed. ``You'll never defeat me!''\n``That's where you're wrong...''",
+        "John laughed. “You'll never defeat me!”\n“That's where you're wrong…”",
+    ),
+    (
+        ",,あんたはばかです！''\n``Ehh?''\n,,ほんと！''\n[[footnoteblock]]",
+        "„あんたはばかです！”\n“Ehh?”\n„ほんと！”\n[[footnoteblock]]",
+    ),
+    (
+        "**ENTITY MAKES DRAMATIC MOTION** . . . ",
+        "**ENTITY MAKES DRAMATIC MOTION** … ",
+    ),
+];
+
+#[test]
+fn regexes() {
+    let _ = &*SINGLE_QUOTES;
+    let _ = &*DOUBLE_QUOTES;
+    let _ = &*LOW_DOUBLE_QUOTES;
+    let _ = &*ELLIPSIS;
+}
+
+#[test]
+fn test_substitute() {
+    for (input, expected) in &TEST_CASES {
+        let output = substitute_str(input, QuoteStyle::English);
+
+        assert_eq!(
+            &output, expected,
+            "Output of typography substitution test didn't match",
+        );
+    }
+}
+
+#[test]
+fn quote_styles() {
+    const INPUT: &str = "``Double'' `single' ,,low''";
+
+    let check = |quotes, expected: &str| {
+        let output = substitute_str(INPUT, quotes);
+
+        assert_eq!(
+            output, expected,
+            "Quote style {:?} output doesn't match",
+            quotes,
+        );
+    };
+
+    check(QuoteStyle::English, "“Double” ‘single’ „low”");
+    check(QuoteStyle::French, "«Double» ‹single› „low”");
+    check(QuoteStyle::German, "„Double“ ‚single‘ „low“");
+    check(QuoteStyle::Japanese, "「Double」 『single』 „low”");
+}
+
+#[test]
+fn split_runs() {
+    let log = crate::build_logger();
+    let mut runs = TextRuns::default();
+    runs.push_text("Wait..");
+    runs.push_text(". ``Bold");
+    runs.push_placeholder('\u{fffc}');
+    runs.push_text("'' end");
+
+    let outputs = runs.substitute(&log, QuoteStyle::English);
+
+    assert_eq!(
+        outputs,
+        vec![
+            Some(str!("Wait…")),
+            Some(str!(" “Bold")),
+            Some(str!("” end")),
+        ],
+        "Substitutions spanning text elements weren't split correctly",
+    );
+}
@@ -0,0 +1,111 @@
+/*
+ * schema/mod.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! JSON Schemas and TypeScript definitions for ftml's serialized types.
+//!
+//! These describe the JSON form of the syntax tree, parse warnings, HTML output,
+//! page information, and settings, for consumers not written in Rust.
+//! They are generated from the Rust types themselves, and the generated files
+//! are kept in the `schema/` directory of the crate. A test fails if those files
+//! no longer match the types, in which case they can be regenerated by running
+//! the tests with the environment variable `FTML_UPDATE_SCHEMA` set.
+//! Another fails if the declarations written for the WebAssembly bindings
+//! differ from the generated ones, and those must be updated by hand.
+//!
+//! Whenever the serialized form changes in a way which is not backwards-compatible,
+//! [`SCHEMA_VERSION`] should be increased. This version is included in each schema's
+//! `$id`, and in the output of [`JsonRender`].
+//!
+//! [`JsonRender`]: crate::render::json::JsonRender
+
+#[cfg(test)]
+mod test;
+
+mod typescript;
+
+use crate::data::PageInfo;
+use crate::parsing::ParseWarning;
+use crate::render::html::HtmlOutput;
+use crate::settings::WikitextSettings;
+use crate::tree::SyntaxTree;
+use schemars::gen::SchemaSettings;
+use schemars::schema::{RootSchema, SchemaObject};
+use schemars::visit::{visit_schema_object, Visitor};
+use schemars::JsonSchema;
+
+/// The version of the serialized form of ftml's types.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Produces the JSON Schema for each exported type, along with its name.
+///
+/// The name is in kebab-case, and is what the schema file is named after.
+pub fn json_schemas() -> Vec<(&'static str, RootSchema)> {
+    vec![
+        ("syntax-tree", root_schema::<SyntaxTree>("syntax-tree")),
+        (
+            "parse-warning",
+            root_schema::<ParseWarning>("parse-warning"),
+        ),
+        ("html-output", root_schema::<HtmlOutput>("html-output")),
+        ("page-info", root_schema::<PageInfo>("page-info")),
+        (
+            "wikitext-settings",
+            root_schema::<WikitextSettings>("wikitext-settings"),
+        ),
+    ]
+}
+
+/// Produces TypeScript definitions for all of the exported types.
+pub fn typescript_definitions() -> String {
+    let schemas: Vec<RootSchema> = json_schemas()
+        .into_iter()
+        .map(|(_, schema)| schema)
+        .collect();
+
+    typescript::generate(&schemas)
+}
+
+fn root_schema<T: JsonSchema>(name: &str) -> RootSchema {
+    let mut schema = SchemaSettings::draft07()
+        .with_visitor(RemoveDescriptions)
+        .into_generator()
+        .into_root_schema_for::<T>();
+
+    schema.schema.metadata().id =
+        Some(format!("urn:ftml:schema:v{}:{}", SCHEMA_VERSION, name));
+
+    schema
+}
+
+/// Removes documentation from schemas, which is taken from the Rust doc comments.
+///
+/// This way only changes to the types themselves alter the schemas.
+#[derive(Debug, Clone)]
+struct RemoveDescriptions;
+
+impl Visitor for RemoveDescriptions {
+    fn visit_schema_object(&mut self, schema: &mut SchemaObject) {
+        if let Some(metadata) = &mut schema.metadata {
+            metadata.description = None;
+        }
+
+        visit_schema_object(self, schema);
+    }
+}
@@ -0,0 +1,144 @@
+/*
+ * schema/test.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::{json_schemas, typescript_definitions, SCHEMA_VERSION};
+use regex::Regex;
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+#[test]
+fn schema() {
+    let directory: PathBuf = [env!("CARGO_MANIFEST_DIR"), "schema"].iter().collect();
+    let update = env::var_os("FTML_UPDATE_SCHEMA").is_some();
+
+    let mut files = vec![(str!("ftml.d.ts"), typescript_definitions())];
+    for (name, schema) in json_schemas() {
+        let mut json = serde_json::to_string_pretty(&schema).unwrap();
+        json.push('\n');
+        files.push((format!("{}.json", name), json));
+    }
+
+    let mut outdated = Vec::new();
+    for (name, expected) in files {
+        let path = directory.join(&name);
+
+        if update {
+            fs::write(&path, expected).expect("Unable to write schema file");
+            continue;
+        }
+
+        let actual = fs::read_to_string(&path).unwrap_or_default();
+        if actual != expected {
+            outdated.push(name);
+        }
+    }
+
+    assert!(
+        outdated.is_empty(),
+        "Schema files are out of date with the Rust types: {:?}\n\
+         If this change is intentional, run the tests with FTML_UPDATE_SCHEMA=1 \
+         to regenerate them, and increase SCHEMA_VERSION (currently {}) if the \
+         change is not backwards-compatible.",
+        outdated,
+        SCHEMA_VERSION,
+    );
+}
+
+/// The TypeScript declarations written by hand for the WebAssembly bindings,
+/// and the generated types each must match.
+///
+/// The bindings declare their own classes under some of the generated names,
+/// so interfaces are declared there with an `I` prefix.
+const WASM_DEFINITIONS: [(&str, &str, &[&str]); 2] = [
+    (
+        "wasm/render.rs",
+        include_str!("../wasm/render.rs"),
+        &[
+            "HtmlOutput",
+            "HtmlMeta",
+            "HtmlMetaType",
+            "Backlinks",
+            "PageRef",
+        ],
+    ),
+    (
+        "wasm/settings.rs",
+        include_str!("../wasm/settings.rs"),
+        &[
+            "WikitextSettings",
+            "WikitextMode",
+            "HtmlSyntax",
+            "PreprocessSettings",
+            "PreprocessPass",
+            "TypographySettings",
+            "QuoteStyle",
+            "HtmlBlockSettings",
+            "HtmlBlockHosting",
+            "IframeSettings",
+            "ResourceLimits",
+        ],
+    ),
+];
+
+#[test]
+fn wasm_definitions() {
+    let definitions = typescript_definitions();
+    let interface_regex = Regex::new(r"^export interface (\w+)").unwrap();
+    let name_regex = Regex::new(r"^export (?:interface|type) (\w+)").unwrap();
+
+    // Split the generated definitions into each declaration
+    let mut declarations = HashMap::new();
+    let mut interfaces = Vec::new();
+
+    for declaration in definitions.split("\n\n") {
+        if let Some(capture) = interface_regex.captures(declaration) {
+            interfaces.push(capture[1].to_string());
+        }
+
+        if let Some(capture) = name_regex.captures(declaration) {
+            declarations.insert(capture[1].to_string(), declaration);
+        }
+    }
+
+    let rename_regex = Regex::new(&format!(r"\b({})\b", interfaces.join("|"))).unwrap();
+
+    let mut outdated = Vec::new();
+    for (path, source, names) in &WASM_DEFINITIONS {
+        for name in names.iter() {
+            let declaration = declarations
+                .get(*name)
+                .unwrap_or_else(|| panic!("No generated declaration for {}", name));
+
+            let expected = rename_regex.replace_all(declaration, "I$1");
+            if !source.contains(expected.as_ref()) {
+                outdated.push(format!("{} in {}", name, path));
+            }
+        }
+    }
+
+    assert!(
+        outdated.is_empty(),
+        "TypeScript declarations for the WebAssembly bindings don't match \
+         the generated definitions in schema/ftml.d.ts: {:?}",
+        outdated,
+    );
+}
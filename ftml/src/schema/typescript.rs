@@ -0,0 +1,341 @@
+/*
+ * schema/typescript.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Converts JSON Schemas into TypeScript definitions.
+//!
+//! This only handles the subset of JSON Schema produced by `schemars`
+//! for ftml's types, and anything else becomes `unknown`.
+
+use super::SCHEMA_VERSION;
+use schemars::schema::{
+    InstanceType, ObjectValidation, RootSchema, Schema, SchemaObject, SingleOrVec,
+};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// Declarations longer than this are split across lines.
+const MAX_LINE_LENGTH: usize = 80;
+
+/// Generates a TypeScript declaration for each root type and each of their definitions.
+pub fn generate(schemas: &[RootSchema]) -> String {
+    // Definitions are shared between roots, so are merged by name.
+    let mut declarations = BTreeMap::new();
+
+    for schema in schemas {
+        let name = schema
+            .schema
+            .metadata
+            .as_ref()
+            .and_then(|metadata| metadata.title.as_deref())
+            .expect("Root schema has no title");
+
+        declarations.insert(name, &schema.schema);
+
+        for (name, definition) in &schema.definitions {
+            if let Schema::Object(definition) = definition {
+                declarations.insert(name, definition);
+            }
+        }
+    }
+
+    let mut output = format!(
+        "// Generated from the ftml JSON Schemas, version {}. Do not edit.\n",
+        SCHEMA_VERSION,
+    );
+
+    // Flattening copies a type's schema in place of a reference to it,
+    // so any copies of a union are replaced with the union's name.
+    let unions: Vec<(String, &str)> = declarations
+        .iter()
+        .filter(|(_, schema)| union_members(schema).is_some())
+        .map(|(name, schema)| (group(&type_of(schema)), *name))
+        .filter(|(ty, _)| ty.starts_with('('))
+        .collect();
+
+    let replace_unions = |mut ty: String| {
+        for (union, name) in &unions {
+            ty = ty.replace(union.as_str(), name);
+        }
+
+        ty
+    };
+
+    for (name, schema) in declarations {
+        output.push('\n');
+
+        match interface_properties(schema) {
+            Some(object) => {
+                str_write!(output, "export interface {} {{\n", name);
+
+                for property in properties(object) {
+                    str_write!(output, "    {};\n", property);
+                }
+
+                output.push_str("}\n");
+            }
+            None => {
+                let ty = replace_unions(type_of(schema));
+                let declaration = format!("export type {} = {};", name, ty);
+
+                match union_members(schema) {
+                    Some(members) if declaration.len() > MAX_LINE_LENGTH => {
+                        str_write!(output, "export type {} =\n", name);
+
+                        for member in members {
+                            str_write!(output, "    | {}\n", replace_unions(member));
+                        }
+
+                        output.pop();
+                        output.push_str(";\n");
+                    }
+                    _ => {
+                        output.push_str(&declaration);
+                        output.push('\n');
+                    }
+                }
+            }
+        }
+    }
+
+    output
+}
+
+/// Gets the properties of this schema if it is a plain object,
+/// which can be declared as an interface.
+fn interface_properties(schema: &SchemaObject) -> Option<&ObjectValidation> {
+    let plain = schema.subschemas.is_none()
+        && schema.enum_values.is_none()
+        && schema.const_value.is_none()
+        && schema.reference.is_none()
+        && matches!(
+            &schema.instance_type,
+            Some(SingleOrVec::Single(instance_type))
+                if **instance_type == InstanceType::Object,
+        );
+
+    match &schema.object {
+        Some(object) if plain && object.additional_properties.is_none() => Some(object),
+        _ => None,
+    }
+}
+
+/// Gets the members of this schema if it is only a union.
+fn union_members(schema: &SchemaObject) -> Option<Vec<String>> {
+    if let Some(values) = &schema.enum_values {
+        return Some(values.iter().map(literal).collect());
+    }
+
+    if schema.instance_type.is_some() || schema.reference.is_some() {
+        return None;
+    }
+
+    let subschemas = schema.subschemas.as_ref()?;
+    if subschemas.all_of.is_some() {
+        return None;
+    }
+
+    let schemas = subschemas.one_of.as_ref().or(subschemas.any_of.as_ref())?;
+    let mut members = Vec::new();
+
+    for schema in schemas {
+        match schema {
+            // Enum variants without data are grouped together
+            Schema::Object(SchemaObject {
+                enum_values: Some(values),
+                ..
+            }) => members.extend(values.iter().map(literal)),
+            _ => members.push(type_of_schema(schema)),
+        }
+    }
+
+    Some(members)
+}
+
+fn type_of_schema(schema: &Schema) -> String {
+    match schema {
+        Schema::Bool(true) => str!("unknown"),
+        Schema::Bool(false) => str!("never"),
+        Schema::Object(schema) => type_of(schema),
+    }
+}
+
+fn type_of(schema: &SchemaObject) -> String {
+    if let Some(reference) = &schema.reference {
+        return str!(reference.trim_start_matches("#/definitions/"));
+    }
+
+    if let Some(value) = &schema.const_value {
+        return literal(value);
+    }
+
+    if let Some(values) = &schema.enum_values {
+        return union(values.iter().map(literal));
+    }
+
+    let mut parts = Vec::new();
+
+    if let Some(instance_type) = &schema.instance_type {
+        let types = match instance_type {
+            SingleOrVec::Single(instance_type) => vec![**instance_type],
+            SingleOrVec::Vec(instance_types) => instance_types.clone(),
+        };
+
+        parts.push(union(
+            types
+                .into_iter()
+                .map(|instance_type| type_of_instance(schema, instance_type)),
+        ));
+    }
+
+    if let Some(subschemas) = &schema.subschemas {
+        if let Some(schemas) = &subschemas.all_of {
+            parts.extend(schemas.iter().map(type_of_schema));
+        }
+
+        if let Some(schemas) = subschemas.one_of.as_ref().or(subschemas.any_of.as_ref()) {
+            parts.push(union(schemas.iter().map(type_of_schema)));
+        }
+    }
+
+    // An object type alongside a union is only a placeholder from flattening
+    if parts.len() > 1 && parts[0] == "Record<string, unknown>" {
+        parts.remove(0);
+    }
+
+    match parts.len() {
+        0 => str!("unknown"),
+        1 => parts.remove(0),
+        _ => parts
+            .iter()
+            .map(|part| group(part))
+            .collect::<Vec<_>>()
+            .join(" & "),
+    }
+}
+
+fn type_of_instance(schema: &SchemaObject, instance_type: InstanceType) -> String {
+    match instance_type {
+        InstanceType::Null => str!("null"),
+        InstanceType::Boolean => str!("boolean"),
+        InstanceType::Integer | InstanceType::Number => str!("number"),
+        InstanceType::String => str!("string"),
+        InstanceType::Array => {
+            let items = schema.array.as_ref().and_then(|array| array.items.as_ref());
+
+            match items {
+                Some(SingleOrVec::Single(item)) => {
+                    format!("{}[]", group(&type_of_schema(item)))
+                }
+                Some(SingleOrVec::Vec(items)) => {
+                    let items: Vec<String> = items.iter().map(type_of_schema).collect();
+                    format!("[{}]", items.join(", "))
+                }
+                None => str!("unknown[]"),
+            }
+        }
+        InstanceType::Object => match &schema.object {
+            Some(object) => {
+                let properties = properties(object);
+
+                if properties.is_empty() {
+                    str!("Record<string, never>")
+                } else {
+                    format!("{{ {}; }}", properties.join("; "))
+                }
+            }
+            None => str!("Record<string, unknown>"),
+        },
+    }
+}
+
+fn properties(object: &ObjectValidation) -> Vec<String> {
+    let mut properties: Vec<String> = object
+        .properties
+        .iter()
+        .map(|(name, property)| {
+            let optional = if object.required.contains(name) {
+                ""
+            } else {
+                "?"
+            };
+
+            format!(
+                "{}{}: {}",
+                property_name(name),
+                optional,
+                type_of_schema(property),
+            )
+        })
+        .collect();
+
+    if let Some(additional) = &object.additional_properties {
+        if !matches!(**additional, Schema::Bool(false)) {
+            properties.push(format!("[key: string]: {}", type_of_schema(additional)));
+        }
+    }
+
+    properties
+}
+
+fn property_name(name: &str) -> String {
+    let mut chars = name.chars();
+    let is_identifier = matches!(chars.next(), Some(c) if c == '_' || c.is_ascii_alphabetic())
+        && chars.all(|c| c == '_' || c.is_ascii_alphanumeric());
+
+    if is_identifier {
+        str!(name)
+    } else {
+        literal(&Value::from(name))
+    }
+}
+
+fn literal(value: &Value) -> String {
+    // JSON literals are valid TypeScript literal types.
+    value.to_string()
+}
+
+fn union<I>(types: I) -> String
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut types: Vec<String> = types.into_iter().collect();
+    types.dedup();
+
+    match types.len() {
+        0 => str!("never"),
+        _ => types.join(" | "),
+    }
+}
+
+/// Wraps a type in parentheses if it is a union or intersection.
+fn group(ty: &str) -> String {
+    let mut depth = 0;
+
+    for c in ty.chars() {
+        match c {
+            '{' | '(' | '[' | '<' => depth += 1,
+            '}' | ')' | ']' | '>' => depth -= 1,
+            '|' | '&' if depth == 0 => return format!("({})", ty),
+            _ => (),
+        }
+    }
+
+    str!(ty)
+}
@@ -0,0 +1,367 @@
+/*
+ * sections.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Splits wikitext into sections by heading, to support editing one section at a time.
+//!
+//! Each heading begins a section, which continues until the next heading
+//! of the same or a higher level. That is, a section includes its subsections,
+//! the same as "edit section" in MediaWiki. Any text before the first heading
+//! is not part of any section.
+//!
+//! Only headings which the parser accepted are considered, so lines which look
+//! like headings within code blocks, comments, and the like are skipped.
+//! Headings from included pages are not part of this page's source, so they
+//! are skipped as well.
+//!
+//! Spans refer to the text which was tokenized, which is the page source
+//! after preprocessing. They can be mapped back to the original source with
+//! [`Section::to_original_indices()`], using the changes from `preprocess()`.
+
+use crate::lint::{scan_tokens, TokenScan};
+use crate::log::prelude::*;
+use crate::parsing::{ExtractedToken, Token};
+use crate::preproc::PreprocessChanges;
+use crate::tokenizer::Tokenization;
+use crate::tree::visit::{walk_container, walk_element, Visitor};
+use crate::tree::{Container, ContainerType, Element, Heading, SyntaxTree};
+use crate::utf16::Utf16IndexMap;
+use std::ops::Range;
+
+/// A section of wikitext, beginning with a heading.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct Section<'t> {
+    /// The index of this section, counting headings in order.
+    ///
+    /// This matches the `data-section` attribute of edit links in HTML output.
+    pub index: usize,
+
+    /// The level of this section's heading, from 1 to 6.
+    pub level: u8,
+
+    /// The text of the heading, as written.
+    pub name: &'t str,
+
+    /// The index of this heading's table of contents entry, if it has one.
+    ///
+    /// This matches the heading's `id` of `toc{index}` in HTML output.
+    pub toc_index: Option<usize>,
+
+    /// The UTF-8 byte span of the heading line, without the newline.
+    pub heading_span: Range<usize>,
+
+    /// The UTF-8 byte span of the whole section, including any subsections.
+    pub span: Range<usize>,
+}
+
+impl Section<'_> {
+    /// Converts the spans to use UTF-16 indices.
+    #[must_use]
+    pub fn to_utf16_indices(&self, map: &Utf16IndexMap) -> Self {
+        let convert =
+            |span: &Range<usize>| map.get_index(span.start)..map.get_index(span.end);
+
+        Section {
+            heading_span: convert(&self.heading_span),
+            span: convert(&self.span),
+            ..self.clone()
+        }
+    }
+
+    /// Converts the spans to refer to the source before it was preprocessed.
+    #[must_use]
+    pub fn to_original_indices(&self, changes: &PreprocessChanges) -> Self {
+        Section {
+            heading_span: changes.original_span(&self.heading_span),
+            span: changes.original_span(&self.span),
+            ..self.clone()
+        }
+    }
+}
+
+/// Gets all the sections in the given wikitext.
+pub fn get_sections<'t>(
+    log: &Logger,
+    tokenization: &Tokenization<'t>,
+    tree: &SyntaxTree<'t>,
+) -> Vec<Section<'t>> {
+    let text = tokenization.full_text().inner();
+    let mut candidates = find_heading_lines(text, tokenization.tokens()).into_iter();
+    let mut sections: Vec<Section> = Vec::new();
+    let mut toc_index = 0;
+
+    // Match each heading in the tree to its line in the source, in order.
+    //
+    // Any heading lines which don't match are ones the parser didn't accept,
+    // for instance because they are within a block's body.
+    for (index, heading) in find_headings(tree).into_iter().enumerate() {
+        let level = heading.level.value();
+        let line = match candidates
+            .find(|line| line.level == level && line.has_toc == heading.has_toc)
+        {
+            Some(line) => line,
+            None => {
+                warn!(
+                    log,
+                    "Unable to find source for heading, stopping";
+                    "index" => index,
+                );
+                break;
+            }
+        };
+
+        let section_toc_index = if heading.has_toc {
+            toc_index += 1;
+            Some(toc_index - 1)
+        } else {
+            None
+        };
+
+        sections.push(Section {
+            index,
+            level,
+            name: line.name,
+            toc_index: section_toc_index,
+            heading_span: line.span.clone(),
+            span: line.span.start..text.len(),
+        });
+    }
+
+    // Each section ends where the next one of the same or higher level begins.
+    for index in 0..sections.len() {
+        let level = sections[index].level;
+        let end = sections[index + 1..]
+            .iter()
+            .find(|section| section.level <= level)
+            .map(|section| section.span.start);
+
+        if let Some(end) = end {
+            sections[index].span.end = end;
+        }
+    }
+
+    info!(log, "Found sections in wikitext"; "sections-len" => sections.len());
+    sections
+}
+
+/// Replaces the source of one section, leaving the rest of the text intact.
+///
+/// The text is the page source as it was before preprocessing, and `changes`
+/// is what `preprocess()` returned for it. The section must have come from
+/// `get_sections()` for the resulting text.
+pub fn replace_section(
+    text: &str,
+    changes: &PreprocessChanges,
+    section: &Section,
+    source: &str,
+) -> String {
+    let span = changes.original_span(&section.span);
+    let before = &text[..span.start];
+    let after = &text[span.end..];
+    let mut output = String::with_capacity(before.len() + source.len() + after.len() + 1);
+
+    output.push_str(before);
+    output.push_str(source);
+
+    // Ensure the next heading still starts on its own line
+    if !after.is_empty() && !source.ends_with('\n') {
+        output.push('\n');
+    }
+
+    output.push_str(after);
+    output
+}
+
+/// A line in the source which looks like a heading.
+#[derive(Debug)]
+struct HeadingLine<'t> {
+    level: u8,
+    has_toc: bool,
+    name: &'t str,
+    span: Range<usize>,
+}
+
+fn find_heading_lines<'t>(
+    text: &'t str,
+    tokens: &[ExtractedToken<'t>],
+) -> Vec<HeadingLine<'t>> {
+    // Skip comments, raw text, and block bodies which aren't wikitext
+    let TokenScan { wikitext, .. } = scan_tokens(text, tokens);
+    let mut lines = Vec::new();
+
+    for (index, token) in tokens.iter().enumerate() {
+        // Same conditions as the parser, headings must be at the
+        // start of a line and followed by a space.
+        let at_line_start = matches!(
+            index.checked_sub(1).map(|i| tokens[i].token),
+            None | Some(Token::InputStart | Token::LineBreak | Token::ParagraphBreak),
+        );
+
+        if token.token != Token::Heading
+            || !wikitext[index]
+            || !at_line_start
+            || tokens.get(index + 1).map(|t| t.token) != Some(Token::Whitespace)
+        {
+            continue;
+        }
+
+        let start = token.span.start;
+        let end = text[start..]
+            .find('\n')
+            .map(|offset| start + offset)
+            .unwrap_or(text.len());
+
+        lines.push(HeadingLine {
+            level: token.slice.trim_end_matches('*').len() as u8,
+            has_toc: !token.slice.ends_with('*'),
+            name: text[tokens[index + 1].span.end..end].trim(),
+            span: start..end,
+        });
+    }
+
+    lines
+}
+
+/// Gets all headings in the tree, in the order they are rendered.
+///
+/// Headings within included pages are skipped.
+fn find_headings(tree: &SyntaxTree) -> Vec<Heading> {
+    #[derive(Debug, Default)]
+    struct HeadingCollector(Vec<Heading>);
+
+    impl<'a> Visitor<'a> for HeadingCollector {
+        fn visit_element(&mut self, element: &'a Element<'a>) {
+            if let Element::Include { .. } = element {
+                return;
+            }
+
+            walk_element(self, element);
+        }
+
+        fn visit_container(&mut self, container: &'a Container<'a>) {
+            if let ContainerType::Header(heading) = container.ctype() {
+                self.0.push(heading);
+            }
+
+            walk_container(self, container);
+        }
+    }
+
+    let mut collector = HeadingCollector::default();
+    collector.visit_elements(&tree.elements);
+    collector.0
+}
+
+#[test]
+fn sections() {
+    use crate::data::PageInfo;
+    use crate::settings::{WikitextMode, WikitextSettings};
+
+    let log = crate::build_logger();
+    let page_info = PageInfo::dummy();
+    let settings = WikitextSettings::from_mode(WikitextMode::Page);
+
+    let text = "Intro\n\n+ First\n\nApple\n\n++ Nested\n\n[[code]]\n+ Not a heading\n[[/code]]\n\n[!--\n+ Also not\n--]\n\n+* Second\n\nBanana\n\n+ Third\nCherry";
+    let tokenization = crate::tokenize(&log, text);
+    let result = crate::parse(&log, &tokenization, &page_info, &settings);
+    let (tree, _warnings) = result.into();
+    let sections = get_sections(&log, &tokenization, &tree);
+
+    let summary: Vec<_> = sections
+        .iter()
+        .map(|section| {
+            (
+                section.index,
+                section.level,
+                section.name,
+                section.toc_index,
+                &text[section.span.clone()],
+            )
+        })
+        .collect();
+
+    assert_eq!(
+        summary,
+        vec![
+            (
+                0,
+                1,
+                "First",
+                Some(0),
+                "+ First\n\nApple\n\n++ Nested\n\n[[code]]\n+ Not a heading\n[[/code]]\n\n[!--\n+ Also not\n--]\n\n",
+            ),
+            (
+                1,
+                2,
+                "Nested",
+                Some(1),
+                "++ Nested\n\n[[code]]\n+ Not a heading\n[[/code]]\n\n[!--\n+ Also not\n--]\n\n",
+            ),
+            (2, 1, "Second", None, "+* Second\n\nBanana\n\n"),
+            (3, 1, "Third", Some(2), "+ Third\nCherry"),
+        ],
+    );
+
+    assert_eq!(&text[sections[2].heading_span.clone()], "+* Second");
+
+    // Replace sections, in the source from before preprocessing
+    let source = text.replace('\n', "\r\n");
+    let mut preprocessed = source.clone();
+    let changes = crate::preprocess(&log, &mut preprocessed, &settings);
+    assert_eq!(preprocessed, text);
+
+    assert_eq!(
+        &source[sections[2].to_original_indices(&changes).heading_span],
+        "+* Second",
+    );
+
+    assert_eq!(
+        replace_section(&source, &changes, &sections[2], "+* Replaced\r\n\r\nDurian\r\n"),
+        "Intro\n\n+ First\n\nApple\n\n++ Nested\n\n[[code]]\n+ Not a heading\n[[/code]]\n\n[!--\n+ Also not\n--]\n\n+* Replaced\n\nDurian\n+ Third\nCherry"
+            .replace('\n', "\r\n"),
+    );
+
+    assert_eq!(
+        replace_section(&source, &changes, &sections[3], "+ Third\r\nElderberry\r\n"),
+        "Intro\n\n+ First\n\nApple\n\n++ Nested\n\n[[code]]\n+ Not a heading\n[[/code]]\n\n[!--\n+ Also not\n--]\n\n+* Second\n\nBanana\n\n+ Third\nElderberry\n"
+            .replace('\n', "\r\n"),
+    );
+
+    // Headings from included pages aren't part of the source
+    let mut tree = tree;
+    let included = {
+        let tokenization = crate::tokenize(&log, "+ Included");
+        let result = crate::parse(&log, &tokenization, &page_info, &settings);
+        let (included_tree, _warnings) = result.into();
+        included_tree.elements[0].to_owned()
+    };
+
+    tree.elements.insert(
+        0,
+        Element::Include {
+            paragraph_safe: false,
+            variables: Default::default(),
+            location: crate::data::PageRef::page_only(cow!("other-page")),
+            elements: vec![included],
+        },
+    );
+
+    assert_eq!(get_sections(&log, &tokenization, &tree), sections);
+}
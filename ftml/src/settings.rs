@@ -18,8 +18,10 @@
  * along with this program. If not, see <http://www.gnu.org/licenses/>.
  */
 
+use crate::url::{get_url_host, host_matches_domain};
+
 /// Settings to tweak behavior in the ftml parser and renderer.
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
 pub struct WikitextSettings {
     /// What mode we're running in.
@@ -47,6 +49,44 @@ pub struct WikitextSettings {
     /// * Files
     /// * Images
     pub allow_local_paths: bool,
+
+    /// Whether headings get buttons to edit their section.
+    ///
+    /// Headings from included pages are not part of this page's source,
+    /// so they do not get one. See [`crate::sections`] for how sections
+    /// are determined.
+    #[serde(default)]
+    pub enable_section_edit: bool,
+
+    /// Whether to produce a schema.org `Article` in JSON-LD for the page.
+    ///
+    /// This is only useful for the contents of a page, which is shared on its own.
+    #[serde(default)]
+    pub enable_json_ld: bool,
+
+    /// Which syntax `HtmlRender` produces.
+    #[serde(default)]
+    pub html_syntax: HtmlSyntax,
+
+    /// Settings for the preprocessor.
+    #[serde(default)]
+    pub preprocess: PreprocessSettings,
+
+    /// Settings for typography substitutions.
+    #[serde(default)]
+    pub typography: TypographySettings,
+
+    /// Settings for rendering `[[html]]` blocks.
+    #[serde(default)]
+    pub html_block: HtmlBlockSettings,
+
+    /// Settings for which iframes are permitted, and how they are sandboxed.
+    #[serde(default)]
+    pub iframe: IframeSettings,
+
+    /// Limits on the resources used when parsing.
+    #[serde(default)]
+    pub limits: ResourceLimits,
 }
 
 impl WikitextSettings {
@@ -57,36 +97,349 @@ impl WikitextSettings {
                 enable_page_syntax: true,
                 use_true_ids: true,
                 allow_local_paths: true,
+                enable_section_edit: false,
+                enable_json_ld: true,
+                html_syntax: HtmlSyntax::Html,
+                preprocess: PreprocessSettings::default(),
+                typography: TypographySettings::default(),
+                html_block: HtmlBlockSettings::default(),
+                iframe: IframeSettings::default(),
+                limits: ResourceLimits::default(),
             },
             WikitextMode::Draft => WikitextSettings {
                 mode,
                 enable_page_syntax: true,
                 use_true_ids: false,
                 allow_local_paths: true,
+                enable_section_edit: false,
+                enable_json_ld: false,
+                html_syntax: HtmlSyntax::Html,
+                preprocess: PreprocessSettings::default(),
+                typography: TypographySettings::default(),
+                html_block: HtmlBlockSettings::default(),
+                iframe: IframeSettings::default(),
+                limits: ResourceLimits::default(),
             },
             WikitextMode::ForumPost | WikitextMode::DirectMessage => WikitextSettings {
                 mode,
                 enable_page_syntax: false,
                 use_true_ids: false,
                 allow_local_paths: false,
+                enable_section_edit: false,
+                enable_json_ld: false,
+                html_syntax: HtmlSyntax::Html,
+                preprocess: PreprocessSettings::default(),
+                typography: TypographySettings::default(),
+                html_block: HtmlBlockSettings::default(),
+                iframe: IframeSettings::default(),
+                limits: ResourceLimits::default(),
             },
             WikitextMode::List => WikitextSettings {
                 mode,
                 enable_page_syntax: true,
                 use_true_ids: false,
                 allow_local_paths: true,
+                enable_section_edit: false,
+                enable_json_ld: false,
+                html_syntax: HtmlSyntax::Html,
+                preprocess: PreprocessSettings::default(),
+                typography: TypographySettings::default(),
+                html_block: HtmlBlockSettings::default(),
+                iframe: IframeSettings::default(),
+                limits: ResourceLimits::default(),
             },
         }
     }
 }
 
+/// Settings to control which substitutions the preprocessor performs.
+///
+/// The whitespace normalization is always performed, since the parser
+/// depends on it, but additional passes can be added per site or per page.
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct PreprocessSettings {
+    /// Additional substitutions, run in order after the built-in ones.
+    pub passes: Vec<PreprocessPass>,
+}
+
+/// Settings for Wikidot's typography substitutions.
+///
+/// These convert `` ``quotes'' `` into curly quotes and `...` into an ellipsis.
+/// They are applied when rendering, and only to text, so code, raw text,
+/// and math are left as-is.
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct TypographySettings {
+    /// Whether typography substitutions are performed.
+    ///
+    /// Sites in other languages or with a lot of code may want to disable this.
+    pub enabled: bool,
+
+    /// Which quotation marks typography substitutions produce.
+    pub quotes: QuoteStyle,
+}
+
+impl Default for TypographySettings {
+    #[inline]
+    fn default() -> Self {
+        TypographySettings {
+            enabled: true,
+            quotes: QuoteStyle::English,
+        }
+    }
+}
+
+/// Settings for how `[[html]]` blocks are rendered.
+///
+/// By default, the HTML is placed directly in a sandboxed iframe using `srcdoc`,
+/// with a strict content security policy, so no remote service is needed.
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct HtmlBlockSettings {
+    /// Where the HTML for the iframe is hosted.
+    pub hosting: HtmlBlockHosting,
+
+    /// Flags for the iframe's `sandbox` attribute, such as `allow-scripts`.
+    ///
+    /// Unknown flags are ignored. For inline HTML, `allow-same-origin`
+    /// is always removed, as it would give the snippet the page's own origin.
+    pub sandbox: Vec<String>,
+
+    /// Whether to add a script which reports the height of the contents
+    /// to the page, so the iframe can be resized to fit.
+    ///
+    /// This only has an effect if `allow-scripts` is among the sandbox flags.
+    pub auto_height: bool,
+
+    /// The URL of a stylesheet to include, such as the site's CSS.
+    pub stylesheet: Option<String>,
+}
+
+impl Default for HtmlBlockSettings {
+    #[inline]
+    fn default() -> Self {
+        HtmlBlockSettings {
+            hosting: HtmlBlockHosting::Inline,
+            sandbox: vec![str!("allow-scripts")],
+            auto_height: false,
+            stylesheet: None,
+        }
+    }
+}
+
+/// Which syntax HTML output is written in.
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Copy, Clone, Hash, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum HtmlSyntax {
+    /// Regular HTML, as served to browsers.
+    Html,
+
+    /// HTML which is also well-formed XML.
+    ///
+    /// Void elements are self-closed, and attributes always have values.
+    Xhtml,
+
+    /// XHTML for inclusion in an EPUB publication.
+    ///
+    /// Footnotes are marked up as EPUB notes, so reading systems can show
+    /// them as popups. This requires the `epub` namespace to be declared
+    /// on the containing document.
+    Epub,
+}
+
+impl HtmlSyntax {
+    /// Whether the output must be well-formed XML.
+    #[inline]
+    pub fn is_xml(self) -> bool {
+        match self {
+            HtmlSyntax::Html => false,
+            HtmlSyntax::Xhtml | HtmlSyntax::Epub => true,
+        }
+    }
+}
+
+impl Default for HtmlSyntax {
+    #[inline]
+    fn default() -> Self {
+        HtmlSyntax::Html
+    }
+}
+
+/// Where the HTML of an `[[html]]` block is hosted.
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Copy, Clone, Hash, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum HtmlBlockHosting {
+    /// The HTML is included in the page itself, using `srcdoc`.
+    Inline,
+
+    /// The HTML is submitted using `Handle::post_html()`,
+    /// and the iframe points to the URL where it is hosted.
+    Remote,
+}
+
+impl Default for HtmlBlockHosting {
+    #[inline]
+    fn default() -> Self {
+        HtmlBlockHosting::Inline
+    }
+}
+
+/// Settings for the `[[iframe]]` block.
+///
+/// Iframes from origins which are not permitted are replaced with
+/// a link to open the content in a new tab, and produce a warning.
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct IframeSettings {
+    /// Which domains iframes may be embedded from, including their subdomains.
+    ///
+    /// If empty, any domain not in `denied_domains` is permitted.
+    pub allowed_domains: Vec<String>,
+
+    /// Which domains iframes may never be embedded from, including their subdomains.
+    ///
+    /// This takes priority over `allowed_domains`.
+    pub denied_domains: Vec<String>,
+
+    /// Flags for the `sandbox` attribute added to every iframe.
+    pub sandbox: Vec<String>,
+
+    /// The value of the `referrerpolicy` attribute added to every iframe.
+    pub referrer_policy: String,
+}
+
+impl IframeSettings {
+    /// Determines if an iframe may be embedded from the given URL.
+    ///
+    /// Only HTTP and HTTPS URLs are ever permitted.
+    pub fn permits_url(&self, url: &str) -> bool {
+        let host = match get_url_host(url) {
+            Some(host) => host,
+            None => return false,
+        };
+
+        let matches = |domain: &String| host_matches_domain(&host, domain);
+
+        if self.denied_domains.iter().any(matches) {
+            return false;
+        }
+
+        self.allowed_domains.is_empty() || self.allowed_domains.iter().any(matches)
+    }
+}
+
+impl Default for IframeSettings {
+    #[inline]
+    fn default() -> Self {
+        IframeSettings {
+            allowed_domains: vec![],
+            denied_domains: vec![],
+            sandbox: vec![
+                str!("allow-scripts"),
+                str!("allow-popups"),
+                str!("allow-forms"),
+            ],
+            referrer_policy: str!("strict-origin-when-cross-origin"),
+        }
+    }
+}
+
+/// Hard limits on the work done when parsing, for untrusted input.
+///
+/// If any of these are exceeded, parsing stops where it is and the elements
+/// produced so far are returned, along with a warning saying which limit was hit.
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct ResourceLimits {
+    /// The maximum length of the input, in bytes.
+    ///
+    /// This is checked after includes are substituted. The preprocessor
+    /// cuts off longer text just past this limit, so it isn't tokenized.
+    pub max_input_length: usize,
+
+    /// The maximum number of elements which may be produced.
+    pub max_elements: usize,
+
+    /// The maximum number of table cells, across all tables.
+    pub max_table_cells: usize,
+
+    /// The maximum number of pages which may be included.
+    ///
+    /// Since `[[include-messy]]` is substituted before parsing,
+    /// the text is instead cut off after the first include past this limit,
+    /// which is left unexpanded and produces a warning when parsed.
+    pub max_include_expansions: usize,
+
+    /// The maximum total size of all CSS, in bytes.
+    pub max_css_size: usize,
+
+    /// The maximum number of steps the parser may take through the tokens.
+    ///
+    /// Since rules backtrack on failure, this can be much higher
+    /// than the number of tokens.
+    pub max_steps: usize,
+}
+
+impl Default for ResourceLimits {
+    #[inline]
+    fn default() -> Self {
+        ResourceLimits {
+            max_input_length: 1_048_576,
+            max_elements: 200_000,
+            max_table_cells: 20_000,
+            max_include_expansions: 100,
+            max_css_size: 262_144,
+            max_steps: 20_000_000,
+        }
+    }
+}
+
+/// Which style of quotation marks to use for typography.
+///
+/// Each lists the marks for ``` ``double'' ```, `` `single' ``, and `,,low''` quotes.
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Copy, Clone, Hash, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum QuoteStyle {
+    /// “double”, ‘single’, „low”. This is what Wikidot uses.
+    English,
+
+    /// «double», ‹single›, „low”.
+    French,
+
+    /// „double“, ‚single‘, „low“.
+    German,
+
+    /// 「double」, 『single』, „low”.
+    Japanese,
+}
+
+impl Default for QuoteStyle {
+    #[inline]
+    fn default() -> Self {
+        QuoteStyle::English
+    }
+}
+
+/// An additional substitution for the preprocessor to perform.
+///
+/// Every match of the regular expression `pattern` is replaced with `replacement`,
+/// which may refer to capture groups, such as `$1` or `${name}`.
+/// Replaced text is not checked for further matches.
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, Hash, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct PreprocessPass {
+    pub pattern: String,
+    pub replacement: String,
+}
+
 /// What mode parsing and rendering is done in.
 ///
 /// Each variant has slightly different behavior associated
 /// with them, beyond the typical flags for the rest of `WikitextSettings`.
 ///
 /// The exact details of each are still being decided as this is implemented.
-#[derive(Serialize, Deserialize, Debug, Copy, Clone, Hash, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Copy, Clone, Hash, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
 pub enum WikitextMode {
     /// Processing for the contents of a page on a site.
@@ -104,3 +457,27 @@ pub enum WikitextMode {
     /// Processing for modules or other contexts such as `ListPages`.
     List,
 }
+
+#[test]
+fn iframe_policy() {
+    let settings = IframeSettings {
+        allowed_domains: vec![str!("youtube.com"), str!("example.org")],
+        denied_domains: vec![str!("evil.example.org")],
+        ..Default::default()
+    };
+
+    assert!(settings.permits_url("https://www.youtube.com/embed/abc"));
+    assert!(settings.permits_url("http://example.org:8080/page"));
+    assert!(settings.permits_url("https://docs.example.org/"));
+    assert!(!settings.permits_url("https://evil.example.org/"));
+    assert!(!settings.permits_url("https://sub.evil.example.org/"));
+    assert!(!settings.permits_url("https://notyoutube.com/"));
+    assert!(!settings.permits_url("https://youtube.com.evil.net/"));
+    assert!(!settings.permits_url("javascript:alert(1)"));
+    assert!(!settings.permits_url("/local/page"));
+
+    let settings = IframeSettings::default();
+    assert!(settings.permits_url("https://example.com/"));
+    assert!(!settings.permits_url("ftp://example.com/"));
+    assert!(!settings.permits_url("data:text/html,<h1>hi</h1>"));
+}
@@ -0,0 +1,234 @@
+/*
+ * stored/mod.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Storing syntax trees, and loading them again to render without re-parsing.
+//!
+//! A stored tree is the syntax tree along with the [`SCHEMA_VERSION`] it was
+//! written with, either as JSON or as [MessagePack]. The JSON form is a subset of
+//! what [`JsonRender`] produces, so its output can be loaded here as well.
+//!
+//! When loading, trees from older versions are brought up to date by running
+//! each migration in turn before being deserialized. Trees from newer versions
+//! cannot be loaded, since there is no way to know what changed.
+//!
+//! [MessagePack]: https://msgpack.org
+//! [`JsonRender`]: crate::render::json::JsonRender
+
+#[cfg(test)]
+mod test;
+
+use crate::log::prelude::*;
+use crate::schema::SCHEMA_VERSION;
+use crate::tree::visit::{walk_element, Visitor};
+use crate::tree::{Element, SyntaxTree};
+use serde_json::Value;
+use std::error::Error;
+use std::fmt::{self, Display};
+
+/// Upgrades a stored tree in place from one schema version to the next.
+///
+/// The value is the whole stored object, including its version field,
+/// which is updated after the migration is run.
+pub type Migration = fn(&mut Value);
+
+/// The migrations for each schema version.
+///
+/// The entry at index `n` upgrades a tree from version `n + 1` to version `n + 2`,
+/// so there should always be one fewer entry than the current version.
+/// When [`SCHEMA_VERSION`] is increased, a migration for the old version
+/// must be added here.
+pub const MIGRATIONS: &[Migration] = &[];
+
+/// Wrapper for the stored form of a syntax tree.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "kebab-case")]
+struct StoredTree<'a, 't> {
+    version: u32,
+    syntax_tree: &'a SyntaxTree<'t>,
+}
+
+impl<'a, 't> StoredTree<'a, 't> {
+    #[inline]
+    fn new(syntax_tree: &'a SyntaxTree<'t>) -> Self {
+        StoredTree {
+            version: SCHEMA_VERSION,
+            syntax_tree,
+        }
+    }
+}
+
+/// Serializes the syntax tree as JSON, for later use with [`from_json`].
+pub fn to_json(syntax_tree: &SyntaxTree) -> String {
+    serde_json::to_string(&StoredTree::new(syntax_tree))
+        .expect("Unable to serialize JSON")
+}
+
+/// Serializes the syntax tree as MessagePack, for later use with [`from_binary`].
+pub fn to_binary(syntax_tree: &SyntaxTree) -> Vec<u8> {
+    rmp_serde::to_vec_named(&StoredTree::new(syntax_tree))
+        .expect("Unable to serialize MessagePack")
+}
+
+/// Loads a syntax tree previously stored with [`to_json`].
+pub fn from_json(log: &Logger, data: &str) -> Result<SyntaxTree<'static>, LoadError> {
+    info!(log, "Loading stored syntax tree from JSON"; "length" => data.len());
+
+    let value = serde_json::from_str(data).map_err(|_| LoadError::InvalidData)?;
+    load_value(log, value)
+}
+
+/// Loads a syntax tree previously stored with [`to_binary`].
+pub fn from_binary(log: &Logger, data: &[u8]) -> Result<SyntaxTree<'static>, LoadError> {
+    info!(log, "Loading stored syntax tree from MessagePack"; "length" => data.len());
+
+    let value = rmp_serde::from_slice(data).map_err(|_| LoadError::InvalidData)?;
+    load_value(log, value)
+}
+
+/// Loads a syntax tree stored in either format.
+///
+/// JSON is detected by the data beginning with `{`, ignoring whitespace,
+/// which a MessagePack map cannot begin with.
+pub fn load(log: &Logger, data: &[u8]) -> Result<SyntaxTree<'static>, LoadError> {
+    let first = data.iter().find(|byte| !byte.is_ascii_whitespace());
+
+    match first {
+        Some(b'{') => {
+            let data = std::str::from_utf8(data).map_err(|_| LoadError::InvalidData)?;
+            from_json(log, data)
+        }
+        _ => from_binary(log, data),
+    }
+}
+
+fn load_value(log: &Logger, mut value: Value) -> Result<SyntaxTree<'static>, LoadError> {
+    let version = match value.get("version").and_then(Value::as_u64) {
+        Some(version) => version,
+        None => return Err(LoadError::MissingVersion),
+    };
+
+    debug!(
+        log,
+        "Checking stored syntax tree version";
+        "version" => version,
+        "current" => SCHEMA_VERSION,
+    );
+
+    if version == 0 || version > u64::from(SCHEMA_VERSION) {
+        return Err(LoadError::UnsupportedVersion(version));
+    }
+
+    // Run each migration from the stored version up to the current one
+    for (index, migration) in MIGRATIONS.iter().enumerate().skip(version as usize - 1) {
+        let next = index as u64 + 2;
+        debug!(log, "Migrating stored syntax tree"; "version" => next);
+
+        migration(&mut value);
+        value["version"] = Value::from(next);
+    }
+
+    let tree: SyntaxTree = match value.get_mut("syntax-tree") {
+        Some(tree) => {
+            serde_json::from_value(tree.take()).map_err(|_| LoadError::InvalidTree)?
+        }
+        None => return Err(LoadError::InvalidTree),
+    };
+
+    check_tree(log, &tree)?;
+    Ok(tree)
+}
+
+/// Ensures the tree is one the parser could have produced, so it can be rendered.
+///
+/// Renderers assume there are no partial elements left in the tree,
+/// and that each footnote reference has an entry in the footnote list.
+fn check_tree(log: &Logger, tree: &SyntaxTree) -> Result<(), LoadError> {
+    let mut checker = TreeChecker::default();
+    checker.visit_syntax_tree(tree);
+
+    debug!(
+        log,
+        "Checking stored syntax tree";
+        "partials" => checker.partials,
+        "footnote-refs" => checker.footnote_refs,
+        "footnotes" => tree.footnotes.len(),
+    );
+
+    if checker.partials > 0 || checker.footnote_refs > tree.footnotes.len() {
+        return Err(LoadError::InvalidTree);
+    }
+
+    Ok(())
+}
+
+/// Counts the elements in a tree which renderers can't handle by themselves.
+#[derive(Debug, Default)]
+struct TreeChecker {
+    partials: usize,
+    footnote_refs: usize,
+}
+
+impl<'a> Visitor<'a> for TreeChecker {
+    fn visit_element(&mut self, element: &'a Element<'a>) {
+        match element {
+            Element::Partial(_) => self.partials += 1,
+            Element::Footnote => self.footnote_refs += 1,
+            _ => (),
+        }
+
+        walk_element(self, element);
+    }
+}
+
+/// Error value for loading a stored syntax tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LoadError {
+    /// The data is not valid JSON or MessagePack.
+    InvalidData,
+
+    /// The data has no schema version.
+    MissingVersion,
+
+    /// The data is from a schema version which cannot be read.
+    ///
+    /// This is either a newer version than this build of ftml knows of,
+    /// or one which never existed.
+    UnsupportedVersion(u64),
+
+    /// The data does not contain a valid syntax tree for its version.
+    InvalidTree,
+}
+
+impl Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LoadError::InvalidData => write!(f, "stored data is not valid JSON or MessagePack"),
+            LoadError::MissingVersion => write!(f, "stored data has no schema version"),
+            LoadError::UnsupportedVersion(version) => write!(
+                f,
+                "stored data has schema version {}, but only versions 1 to {} are supported",
+                version, SCHEMA_VERSION,
+            ),
+            LoadError::InvalidTree => write!(f, "stored data does not contain a valid syntax tree"),
+        }
+    }
+}
+
+impl Error for LoadError {}
@@ -0,0 +1,165 @@
+/*
+ * stored/test.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::{from_binary, from_json, load, to_binary, to_json, LoadError};
+use crate::data::PageInfo;
+use crate::render::html::HtmlRender;
+use crate::render::json::JsonRender;
+use crate::render::Render;
+use crate::schema::SCHEMA_VERSION;
+use crate::settings::{WikitextMode, WikitextSettings};
+use crate::tree::{AttributeMap, Element, ListItem, PartialElement, SyntaxTree};
+
+const INPUT: &str = r#"
++ Heading
+
+**Bold**, //italics//, and a [[[page-link|link]]].[[footnote]]A footnote.[[/footnote]]
+
+[[div class="box" style="color: red;"]]
+* Item
+** Nested item
+[[/div]]
+
+||~ Header ||~ Cell ||
+|| A || B ||
+
+[[collapsible show="Open"]]
+[[image example.png]]
+[[/collapsible]]
+
+[[footnoteblock]]
+"#;
+
+#[test]
+fn round_trip() {
+    let log = crate::build_logger();
+    let page_info = PageInfo::dummy();
+    let settings = WikitextSettings::from_mode(WikitextMode::Page);
+
+    let tokens = crate::tokenize(&log, INPUT);
+    let (tree, _warnings) = crate::parse(&log, &tokens, &page_info, &settings).into();
+    let html = HtmlRender.render(&log, &tree, &page_info, &settings);
+
+    let from_json = from_json(&log, &to_json(&tree)).expect("Unable to load JSON");
+    let from_binary =
+        from_binary(&log, &to_binary(&tree)).expect("Unable to load binary");
+
+    for loaded in [from_json, from_binary] {
+        assert_eq!(loaded, tree, "Loaded syntax tree differs");
+
+        let output = HtmlRender.render(&log, &loaded, &page_info, &settings);
+        assert_eq!(output.body, html.body, "Rendered HTML differs");
+    }
+
+    // Format detection
+    assert_eq!(load(&log, to_json(&tree).as_bytes()), Ok(tree.clone()));
+    assert_eq!(load(&log, &to_binary(&tree)), Ok(tree.clone()));
+
+    // The output of JsonRender can be loaded too
+    let json = JsonRender::pretty().render(&log, &tree, &page_info, &settings);
+    assert_eq!(load(&log, json.as_bytes()), Ok(tree));
+
+    // Example from docs/Serialization.md
+    let empty = r#"{
+        "version": 1,
+        "syntax-tree": {
+            "elements": [],
+            "styles": [],
+            "table-of-contents": [],
+            "footnotes": [],
+            "bibliographies": []
+        }
+    }"#;
+    assert_eq!(load(&log, empty.as_bytes()), Ok(SyntaxTree::default()));
+}
+
+#[test]
+fn errors() {
+    let log = crate::build_logger();
+
+    macro_rules! check {
+        ($data:expr, $error:expr $(,)?) => {
+            assert_eq!(
+                load(&log, $data.as_bytes()),
+                Err($error),
+                "Unexpected result for {:?}",
+                $data,
+            );
+        };
+    }
+
+    check!("", LoadError::InvalidData);
+    check!("{", LoadError::InvalidData);
+    check!(r#"{"syntax-tree": {}}"#, LoadError::MissingVersion);
+    check!(
+        r#"{"version": "1", "syntax-tree": {}}"#,
+        LoadError::MissingVersion
+    );
+    check!(
+        r#"{"version": 0, "syntax-tree": {}}"#,
+        LoadError::UnsupportedVersion(0)
+    );
+    check!(r#"{"version": 1}"#, LoadError::InvalidTree);
+    check!(
+        r#"{"version": 1, "syntax-tree": {"elements": 4}}"#,
+        LoadError::InvalidTree
+    );
+
+    let newer = format!(
+        r#"{{"version": {}, "syntax-tree": {{}}}}"#,
+        SCHEMA_VERSION + 1
+    );
+    check!(
+        newer,
+        LoadError::UnsupportedVersion(u64::from(SCHEMA_VERSION) + 1)
+    );
+
+    // Trees which would crash a renderer
+    let partial = SyntaxTree {
+        elements: vec![Element::Partial(PartialElement::ListItem(
+            ListItem::Elements {
+                attributes: AttributeMap::new(),
+                elements: vec![],
+            },
+        ))],
+        ..SyntaxTree::default()
+    };
+    check!(to_json(&partial), LoadError::InvalidTree);
+
+    let missing_footnote = SyntaxTree {
+        elements: vec![Element::Footnote, Element::Footnote],
+        footnotes: vec![vec![]],
+        ..SyntaxTree::default()
+    };
+    check!(to_json(&missing_footnote), LoadError::InvalidTree);
+    assert_eq!(
+        from_binary(&log, &to_binary(&missing_footnote)),
+        Err(LoadError::InvalidTree),
+    );
+}
+
+#[test]
+fn migrations() {
+    assert_eq!(
+        super::MIGRATIONS.len(),
+        SCHEMA_VERSION as usize - 1,
+        "There should be one migration for each previous schema version",
+    );
+}
@@ -114,6 +114,11 @@ struct Test<'a> {
     #[serde(skip)]
     name: String,
     input: String,
+
+    /// The language of the page, if it is not the default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    language: Option<String>,
+
     tree: SyntaxTree<'a>,
     warnings: Vec<ParseWarning>,
 
@@ -209,7 +214,10 @@ impl Test<'_> {
             alt_title: None,
             rating: 0.0,
             tags: vec![cow!("fruit"), cow!("component")],
-            language: cow!("default"),
+            language: match self.language {
+                Some(ref language) => cow!(language),
+                None => cow!("default"),
+            },
         };
 
         let settings = WikitextSettings::from_mode(WikitextMode::Page);
@@ -218,7 +226,7 @@ impl Test<'_> {
             crate::include(log, &self.input, &settings, TestIncluder, || unreachable!())
                 .void_unwrap();
 
-        crate::preprocess(log, &mut text);
+        crate::preprocess(log, &mut text, &settings);
         let tokens = crate::tokenize(log, &text);
         let result = crate::parse(log, &tokens, &page_info, &settings);
         let (tree, warnings) = result.into();
@@ -20,7 +20,7 @@
 
 use crate::data::PageInfo;
 use crate::parsing::{ParseWarningKind, Token};
-use crate::settings::{WikitextMode, WikitextSettings};
+use crate::settings::{ResourceLimits, WikitextMode, WikitextSettings};
 use crate::tree::{Element, SyntaxTree};
 use std::borrow::Cow;
 
@@ -47,7 +47,7 @@ fn recursion_depth() {
     }
 
     // Run parser steps
-    crate::preprocess(&log, &mut input);
+    crate::preprocess(&log, &mut input, &settings);
     let tokens = crate::tokenize(&log, &input);
     let (tree, warnings) = crate::parse(&log, &tokens, &page_info, &settings).into();
 
@@ -101,10 +101,104 @@ In hac habitasse platea dictumst. Vestibulum fermentum libero nec erat porttitor
     }
 
     // Run parser steps
-    crate::preprocess(&log, &mut input);
+    crate::preprocess(&log, &mut input, &settings);
     let tokens = crate::tokenize(&log, &input);
     let (_tree, warnings) = crate::parse(&log, &tokens, &page_info, &settings).into();
 
     // Check output
     assert_eq!(warnings.len(), ITERATIONS * 3);
 }
+
+/// Test that exceeding resource limits stops parsing, keeping the output so far.
+#[test]
+fn resource_limits() {
+    use crate::render::{text::TextRender, Render};
+
+    let log = crate::build_logger();
+    let page_info = PageInfo::dummy();
+
+    macro_rules! check {
+        ($input:expr, $limits:expr, $kind:expr, $expected:expr $(,)?) => {{
+            let mut settings = WikitextSettings::from_mode(WikitextMode::Page);
+            settings.limits = $limits;
+
+            let mut input = str!($input);
+            crate::preprocess(&log, &mut input, &settings);
+            let tokens = crate::tokenize(&log, &input);
+            let (tree, warnings) =
+                crate::parse(&log, &tokens, &page_info, &settings).into();
+
+            let output = TextRender.render(&log, &tree, &page_info, &settings);
+
+            println!("Input:    {:?}", $input);
+            println!("Output:   {:?}", output);
+            println!("Warnings: {:#?}", warnings);
+
+            assert!(
+                warnings.iter().any(|warning| warning.kind() == $kind),
+                "Expected warning {:?} not produced",
+                $kind,
+            );
+            assert!(
+                output.starts_with($expected),
+                "Output doesn't start with the text before the limit",
+            );
+            assert!(!output.contains("LATER"), "Text after the limit was parsed",);
+        }};
+    }
+
+    check!(
+        "Some text here\n\nLATER text",
+        ResourceLimits {
+            max_input_length: 10,
+            ..ResourceLimits::default()
+        },
+        ParseWarningKind::InputTooLong,
+        "Some",
+    );
+    check!(
+        "//a// //b// //c// LATER",
+        ResourceLimits {
+            max_elements: 4,
+            ..ResourceLimits::default()
+        },
+        ParseWarningKind::TooManyElements,
+        "a b",
+    );
+    check!(
+        "|| A || B ||\n|| C || D ||\n\n[[table]]\n[[row]]\n[[cell]]\nLATER\n[[/cell]]\n[[/row]]\n[[/table]]",
+        ResourceLimits {
+            max_table_cells: 4,
+            ..ResourceLimits::default()
+        },
+        ParseWarningKind::TooManyTableCells,
+        "|| A || B ||\n|| C || D ||",
+    );
+    check!(
+        "[[include-elements first]]\n[[include-elements second]]\n\nLATER",
+        ResourceLimits {
+            max_include_expansions: 1,
+            ..ResourceLimits::default()
+        },
+        ParseWarningKind::TooManyIncludes,
+        "<INCLUDED PAGE (ELEMENTS)>",
+    );
+    check!(
+        "First\n\n[[css]]\n.a { color: red; }\n[[/css]]\n\nLATER",
+        ResourceLimits {
+            max_css_size: 8,
+            ..ResourceLimits::default()
+        },
+        ParseWarningKind::CssTooLarge,
+        "First",
+    );
+    check!(
+        "Beginning text, then LATER",
+        ResourceLimits {
+            max_steps: 3,
+            ..ResourceLimits::default()
+        },
+        ParseWarningKind::StepLimitExceeded,
+        "Beginning",
+    );
+}
@@ -23,9 +23,9 @@ use crate::render::{html::HtmlRender, text::TextRender, Render};
 use crate::settings::{WikitextMode, WikitextSettings};
 use crate::tree::attribute::SAFE_ATTRIBUTES;
 use crate::tree::{
-    Alignment, AnchorTarget, AttributeMap, ClearFloat, Container, ContainerType, Element,
-    FloatAlignment, Heading, HeadingLevel, ImageSource, LinkLabel, LinkLocation,
-    ListItem, ListType, Module, SyntaxTree,
+    Alignment, AnchorTarget, AttributeMap, BibliographyList, ClearFloat, Container,
+    ContainerType, Element, FloatAlignment, Heading, HeadingLevel, ImageSource,
+    LinkLabel, LinkLocation, ListItem, ListType, Module, SyntaxTree,
 };
 use proptest::option;
 use proptest::prelude::*;
@@ -277,6 +277,9 @@ where
         Just(ContainerType::Span),
         Just(ContainerType::Div),
         Just(ContainerType::Mark),
+        Just(ContainerType::Ruby),
+        Just(ContainerType::RubyBase),
+        Just(ContainerType::RubyText),
         Just(ContainerType::Blockquote),
         Just(ContainerType::Insertion),
         Just(ContainerType::Deletion),
@@ -396,6 +399,7 @@ fn arb_tree() -> impl Strategy<Value = SyntaxTree<'static>> {
                 styles,
                 table_of_contents,
                 footnotes,
+                bibliographies: BibliographyList::new(),
             },
         )
 }
@@ -39,7 +39,7 @@ fn settings() {
         ($mode:expr, $input:expr, $substring:expr, $contains:expr) => {{
             let settings = WikitextSettings::from_mode($mode);
             let mut text = str!($input);
-            crate::preprocess(log, &mut text);
+            crate::preprocess(log, &mut text, &settings);
 
             let tokens = crate::tokenize(log, &text);
             let result = crate::parse(log, &tokens, &page_info, &settings);
@@ -100,4 +100,24 @@ fn settings() {
         "local-file.png",
         [true, true, false, false, true],
     );
+    check!(
+        "[[gallery]]\n: local-file.png\n[[/gallery]]",
+        "wj-gallery-item",
+        [true, true, false, false, true],
+    );
+    check!(
+        "[[gallery]]\n: local-file.png\n[[/gallery]]",
+        "wj-gallery-links",
+        [false, false, true, true, false],
+    );
+    check!(
+        "[[audio /some-page/local-file.ogg]]",
+        "local-file.ogg",
+        [true, true, false, false, true],
+    );
+    check!(
+        "[[video https://example.com/file.mp4 captions=\"/local-file.vtt\"]]",
+        "local-file.vtt",
+        [true, true, false, false, true],
+    );
 }
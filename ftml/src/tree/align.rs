@@ -21,7 +21,7 @@
 use regex::Regex;
 use std::convert::TryFrom;
 
-#[derive(Serialize, Deserialize, Debug, Copy, Clone, Hash, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Copy, Clone, Hash, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
 pub enum Alignment {
     Left,
@@ -64,7 +64,7 @@ impl TryFrom<&'_ str> for Alignment {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Copy, Clone, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
 pub struct FloatAlignment {
     pub align: Alignment,
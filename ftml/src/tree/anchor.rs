@@ -22,7 +22,16 @@ use std::convert::TryFrom;
 use strum_macros::IntoStaticStr;
 
 #[derive(
-    Serialize, Deserialize, IntoStaticStr, Debug, Copy, Clone, Hash, PartialEq, Eq,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    IntoStaticStr,
+    Debug,
+    Copy,
+    Clone,
+    Hash,
+    PartialEq,
+    Eq,
 )]
 #[serde(rename_all = "kebab-case")]
 pub enum AnchorTarget {
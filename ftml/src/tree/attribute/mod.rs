@@ -22,6 +22,9 @@ mod safe;
 
 use super::clone::string_to_owned;
 use crate::parsing::parse_boolean;
+use schemars::gen::SchemaGenerator;
+use schemars::schema::Schema;
+use schemars::JsonSchema;
 use std::borrow::Cow;
 use std::collections::{BTreeMap, HashMap};
 use std::fmt::{self, Debug};
@@ -112,6 +115,17 @@ impl<'t> Debug for AttributeMap<'t> {
     }
 }
 
+// Derived schemas lose the map when it is flattened.
+impl JsonSchema for AttributeMap<'_> {
+    fn schema_name() -> String {
+        str!("AttributeMap")
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        <BTreeMap<String, String>>::json_schema(gen)
+    }
+}
+
 impl<'t> From<BTreeMap<Cow<'t, str>, Cow<'t, str>>> for AttributeMap<'t> {
     #[inline]
     fn from(map: BTreeMap<Cow<'t, str>, Cow<'t, str>>) -> AttributeMap<'t> {
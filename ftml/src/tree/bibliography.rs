@@ -0,0 +1,142 @@
+/*
+ * tree/bibliography.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::clone::{elements_to_owned, string_to_owned};
+use super::Element;
+use std::borrow::Cow;
+
+/// All of the bibliographies on a page, in order.
+///
+/// Bibliography entries are numbered in the order they appear,
+/// continuing from one `[[bibliography]]` block to the next.
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Default, Clone, PartialEq, Eq)]
+#[serde(transparent)]
+pub struct BibliographyList<'t>(Vec<Bibliography<'t>>);
+
+impl<'t> BibliographyList<'t> {
+    #[inline]
+    pub fn new() -> Self {
+        BibliographyList::default()
+    }
+
+    /// Adds a bibliography, returning its index.
+    pub fn push(&mut self, bibliography: Bibliography<'t>) -> usize {
+        let index = self.0.len();
+        self.0.push(bibliography);
+        index
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = &Bibliography<'t>> {
+        self.0.iter()
+    }
+
+    #[inline]
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut Bibliography<'t>> {
+        self.0.iter_mut()
+    }
+
+    /// Gets the bibliography at the given index,
+    /// along with the number of its first entry.
+    pub fn get_bibliography(&self, index: usize) -> Option<(usize, &Bibliography<'t>)> {
+        let bibliography = self.0.get(index)?;
+        let first = self.0[..index]
+            .iter()
+            .map(|bibliography| bibliography.entries.len())
+            .sum::<usize>()
+            + 1;
+
+        Some((first, bibliography))
+    }
+
+    /// Finds the entry with the given label, along with its number.
+    ///
+    /// If a label is defined more than once, the first definition is used.
+    pub fn get_reference(&self, label: &str) -> Option<(usize, &BibliographyEntry<'t>)> {
+        self.0
+            .iter()
+            .flat_map(|bibliography| bibliography.entries.iter())
+            .enumerate()
+            .find(|(_, entry)| entry.label == label)
+            .map(|(index, entry)| (index + 1, entry))
+    }
+
+    pub fn to_owned(&self) -> BibliographyList<'static> {
+        BibliographyList(self.0.iter().map(|bib| bib.to_owned()).collect())
+    }
+}
+
+impl<'t> IntoIterator for BibliographyList<'t> {
+    type Item = Bibliography<'t>;
+    type IntoIter = std::vec::IntoIter<Bibliography<'t>>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+/// A single `[[bibliography]]` block, which is a list of labelled citations.
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Default, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct Bibliography<'t> {
+    pub entries: Vec<BibliographyEntry<'t>>,
+}
+
+impl Bibliography<'_> {
+    pub fn to_owned(&self) -> Bibliography<'static> {
+        Bibliography {
+            entries: self.entries.iter().map(|entry| entry.to_owned()).collect(),
+        }
+    }
+}
+
+/// One citation in a bibliography, which can be referred to by `((bibcite label))`.
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct BibliographyEntry<'t> {
+    pub label: Cow<'t, str>,
+    pub elements: Vec<Element<'t>>,
+
+    /// How many times this entry is cited on the page.
+    ///
+    /// Used to produce links back to each citation.
+    pub citations: usize,
+}
+
+impl BibliographyEntry<'_> {
+    pub fn to_owned(&self) -> BibliographyEntry<'static> {
+        BibliographyEntry {
+            label: string_to_owned(&self.label),
+            elements: elements_to_owned(&self.elements),
+            citations: self.citations,
+        }
+    }
+}
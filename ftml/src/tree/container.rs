@@ -25,7 +25,7 @@ use super::{Alignment, AttributeMap, Element, Heading, HtmlTag};
 use crate::next_index::{NextIndex, TableOfContentsIndex};
 use strum_macros::IntoStaticStr;
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
 pub struct Container<'t> {
     #[serde(rename = "type")]
@@ -58,6 +58,11 @@ impl<'t> Container<'t> {
         &self.elements
     }
 
+    #[inline]
+    pub fn elements_mut(&mut self) -> &mut Vec<Element<'t>> {
+        &mut self.elements
+    }
+
     #[inline]
     pub fn attributes(&self) -> &AttributeMap<'t> {
         &self.attributes
@@ -87,7 +92,16 @@ impl<'t> From<Container<'t>> for Vec<Element<'t>> {
 }
 
 #[derive(
-    Serialize, Deserialize, IntoStaticStr, Debug, Copy, Clone, Hash, PartialEq, Eq,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    IntoStaticStr,
+    Debug,
+    Copy,
+    Clone,
+    Hash,
+    PartialEq,
+    Eq,
 )]
 #[serde(rename_all = "kebab-case")]
 pub enum ContainerType {
@@ -101,6 +115,9 @@ pub enum ContainerType {
     Span,
     Div,
     Mark,
+    Ruby,
+    RubyBase,
+    RubyText,
     Blockquote,
     Insertion,
     Deletion,
@@ -131,6 +148,9 @@ impl ContainerType {
             ContainerType::Span => HtmlTag::new("span"),
             ContainerType::Div => HtmlTag::new("div"),
             ContainerType::Mark => HtmlTag::new("mark"),
+            ContainerType::Ruby => HtmlTag::new("ruby"),
+            ContainerType::RubyBase => HtmlTag::new("rb"),
+            ContainerType::RubyText => HtmlTag::new("rt"),
             ContainerType::Blockquote => HtmlTag::new("blockquote"),
             ContainerType::Insertion => HtmlTag::new("ins"),
             ContainerType::Deletion => HtmlTag::new("del"),
@@ -161,6 +181,9 @@ impl ContainerType {
             ContainerType::Span => true,
             ContainerType::Div => false,
             ContainerType::Mark => true,
+            ContainerType::Ruby => true,
+            ContainerType::RubyBase => true,
+            ContainerType::RubyText => true,
             ContainerType::Blockquote => false,
             ContainerType::Insertion => true,
             ContainerType::Deletion => true,
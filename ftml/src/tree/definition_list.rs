@@ -23,7 +23,7 @@ use super::Element;
 
 pub type DefinitionList<'t> = Vec<DefinitionListItem<'t>>;
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq, Eq)]
 pub struct DefinitionListItem<'t> {
     pub key: Vec<Element<'t>>,
     pub value: Vec<Element<'t>>,
@@ -30,7 +30,7 @@ use std::slice;
 ///
 /// It also contains a field marking whether all of the
 /// contents are paragraph-safe or not, used by `ParagraphStack`.
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq, Eq)]
 pub enum Elements<'t> {
     Multiple(Vec<Element<'t>>),
     Single(Element<'t>),
@@ -22,14 +22,15 @@ use crate::data::PageRef;
 use crate::tree::clone::*;
 use crate::tree::{
     Alignment, AnchorTarget, AttributeMap, ClearFloat, Container, Date,
-    DefinitionListItem, Embed, FloatAlignment, ImageSource, LinkLabel, LinkLocation,
-    ListItem, ListType, Module, PartialElement, Tab, Table, VariableMap,
+    DefinitionListItem, Embed, FloatAlignment, GalleryImage, GallerySize, ImageSource,
+    LinkLabel, LinkLocation, ListItem, ListType, Media, Module, PartialElement, Tab,
+    Table, VariableMap,
 };
 use ref_map::*;
 use std::borrow::Cow;
 use std::num::NonZeroU32;
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case", tag = "element", content = "data")]
 pub enum Element<'t> {
     /// Generic element that contains other elements within it.
@@ -108,6 +109,21 @@ pub enum Element<'t> {
         attributes: AttributeMap<'t>,
     },
 
+    /// A grid of images, as produced by `[[gallery]]`.
+    ///
+    /// Each image is shown at the given thumbnail size,
+    /// with its own title, caption, and link.
+    Gallery {
+        images: Vec<GalleryImage<'t>>,
+        size: GallerySize,
+        attributes: AttributeMap<'t>,
+    },
+
+    /// An audio or video file.
+    ///
+    /// This is boxed, as it is considerably larger than other elements.
+    Media(Box<Media<'t>>),
+
     /// An ordered or unordered list.
     List {
         #[serde(rename = "type")]
@@ -179,6 +195,22 @@ pub enum Element<'t> {
         hide: bool,
     },
 
+    /// A citation of a bibliography entry, from `((bibcite label))`.
+    ///
+    /// The number of the citation is determined when rendering,
+    /// by looking up the label in the `bibliographies` list in the syntax tree.
+    BibliographyCite { label: Cow<'t, str> },
+
+    /// A bibliography block, containing a list of citations.
+    ///
+    /// The index refers to the `bibliographies` list in the syntax tree,
+    /// which is where the entries themselves are stored.
+    BibliographyBlock {
+        index: usize,
+        title: Option<Cow<'t, str>>,
+        hide: bool,
+    },
+
     /// A user block, linking to their information and possibly showing their avatar.
     #[serde(rename_all = "kebab-case")]
     User {
@@ -298,6 +330,8 @@ impl Element<'_> {
             Element::Anchor { .. } => "Anchor",
             Element::Link { .. } => "Link",
             Element::Image { .. } => "Image",
+            Element::Gallery { .. } => "Gallery",
+            Element::Media(_) => "Media",
             Element::List { .. } => "List",
             Element::DefinitionList(_) => "DefinitionList",
             Element::RadioButton { .. } => "RadioButton",
@@ -306,6 +340,8 @@ impl Element<'_> {
             Element::TableOfContents { .. } => "TableOfContents",
             Element::Footnote => "Footnote",
             Element::FootnoteBlock { .. } => "FootnoteBlock",
+            Element::BibliographyCite { .. } => "BibliographyCite",
+            Element::BibliographyBlock { .. } => "BibliographyBlock",
             Element::User { .. } => "User",
             Element::Date { .. } => "Date",
             Element::Color { .. } => "Color",
@@ -346,6 +382,8 @@ impl Element<'_> {
             Element::TabView(_) => false,
             Element::Anchor { .. } | Element::Link { .. } => true,
             Element::Image { .. } => true,
+            Element::Gallery { .. } => false,
+            Element::Media(_) => true,
             Element::List { .. } => false,
             Element::DefinitionList(_) => false,
             Element::RadioButton { .. } | Element::CheckBox { .. } => true,
@@ -353,6 +391,8 @@ impl Element<'_> {
             Element::TableOfContents { .. } => false,
             Element::Footnote => true,
             Element::FootnoteBlock { .. } => false,
+            Element::BibliographyCite { .. } => true,
+            Element::BibliographyBlock { .. } => false,
             Element::User { .. } => true,
             Element::Date { .. } => true,
             Element::Color { .. } => true,
@@ -427,6 +467,16 @@ impl Element<'_> {
                 alignment: *alignment,
                 attributes: attributes.to_owned(),
             },
+            Element::Gallery {
+                images,
+                size,
+                attributes,
+            } => Element::Gallery {
+                images: images.iter().map(|image| image.to_owned()).collect(),
+                size: *size,
+                attributes: attributes.to_owned(),
+            },
+            Element::Media(media) => Element::Media(Box::new(Media::to_owned(media))),
             Element::DefinitionList(items) => Element::DefinitionList(
                 items.iter().map(|item| item.to_owned()).collect(),
             ),
@@ -472,6 +522,16 @@ impl Element<'_> {
                 title: option_string_to_owned(title),
                 hide: *hide,
             },
+            Element::BibliographyCite { label } => Element::BibliographyCite {
+                label: string_to_owned(label),
+            },
+            Element::BibliographyBlock { index, title, hide } => {
+                Element::BibliographyBlock {
+                    index: *index,
+                    title: option_string_to_owned(title),
+                    hide: *hide,
+                }
+            }
             Element::User { name, show_avatar } => Element::User {
                 name: string_to_owned(name),
                 show_avatar: *show_avatar,
@@ -21,7 +21,7 @@
 use super::clone::string_to_owned;
 use std::borrow::Cow;
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case", tag = "embed", content = "data")]
 pub enum Embed<'t> {
     #[serde(rename_all = "kebab-case")]
@@ -0,0 +1,122 @@
+/*
+ * tree/gallery.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::clone::option_string_to_owned;
+use super::{AttributeMap, ImageSource, LinkLocation};
+use ref_map::*;
+use std::borrow::Cow;
+use std::convert::TryFrom;
+
+/// The thumbnail size used for images in a gallery.
+///
+/// These correspond to the sizes Wikidot offers for `[[gallery]]`.
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Copy, Clone, Hash, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum GallerySize {
+    Square,
+    Thumbnail,
+    Small,
+    Medium,
+}
+
+impl GallerySize {
+    pub fn name(self) -> &'static str {
+        match self {
+            GallerySize::Square => "square",
+            GallerySize::Thumbnail => "thumbnail",
+            GallerySize::Small => "small",
+            GallerySize::Medium => "medium",
+        }
+    }
+
+    pub fn html_class(self) -> &'static str {
+        match self {
+            GallerySize::Square => "wj-gallery-square",
+            GallerySize::Thumbnail => "wj-gallery-thumbnail",
+            GallerySize::Small => "wj-gallery-small",
+            GallerySize::Medium => "wj-gallery-medium",
+        }
+    }
+}
+
+impl Default for GallerySize {
+    #[inline]
+    fn default() -> Self {
+        GallerySize::Thumbnail
+    }
+}
+
+impl TryFrom<&'_ str> for GallerySize {
+    type Error = ();
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value.to_ascii_lowercase().as_str() {
+            "square" => Ok(GallerySize::Square),
+            "thumbnail" => Ok(GallerySize::Thumbnail),
+            "small" => Ok(GallerySize::Small),
+            "medium" => Ok(GallerySize::Medium),
+            _ => Err(()),
+        }
+    }
+}
+
+/// A single image within a `[[gallery]]` block.
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct GalleryImage<'t> {
+    pub source: ImageSource<'t>,
+    pub title: Option<Cow<'t, str>>,
+    pub caption: Option<Cow<'t, str>>,
+    pub link: Option<LinkLocation<'t>>,
+    pub attributes: AttributeMap<'t>,
+}
+
+impl GalleryImage<'_> {
+    /// Gets a human-readable label for this image.
+    ///
+    /// This is the title or caption if present, falling back to
+    /// the image's file name or URL.
+    pub fn label(&self) -> &str {
+        if let Some(title) = &self.title {
+            return title;
+        }
+
+        if let Some(caption) = &self.caption {
+            return caption;
+        }
+
+        match &self.source {
+            ImageSource::Url(url) => url,
+            ImageSource::File1 { file }
+            | ImageSource::File2 { file, .. }
+            | ImageSource::File3 { file, .. } => file,
+        }
+    }
+
+    pub fn to_owned(&self) -> GalleryImage<'static> {
+        GalleryImage {
+            source: self.source.to_owned(),
+            title: option_string_to_owned(&self.title),
+            caption: option_string_to_owned(&self.caption),
+            link: self.link.ref_map(|link| link.to_owned()),
+            attributes: self.attributes.to_owned(),
+        }
+    }
+}
@@ -22,7 +22,7 @@ use super::HtmlTag;
 use crate::next_index::{NextIndex, TableOfContentsIndex};
 use std::convert::TryFrom;
 
-#[derive(Serialize, Deserialize, Debug, Copy, Clone, Hash, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Copy, Clone, Hash, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
 pub struct Heading {
     /// The depth that this heading extends to.
@@ -74,7 +74,17 @@ impl TryFrom<&'_ str> for Heading {
     }
 }
 
-#[derive(Serialize_repr, Deserialize_repr, Debug, Copy, Clone, Hash, PartialEq, Eq)]
+#[derive(
+    Serialize_repr,
+    Deserialize_repr,
+    JsonSchema_repr,
+    Debug,
+    Copy,
+    Clone,
+    Hash,
+    PartialEq,
+    Eq,
+)]
 #[repr(u8)]
 pub enum HeadingLevel {
     One = 1,
@@ -23,7 +23,9 @@ use crate::url::is_url;
 use std::borrow::Cow;
 use strum_macros::IntoStaticStr;
 
-#[derive(Serialize, Deserialize, IntoStaticStr, Debug, Hash, Clone, PartialEq, Eq)]
+#[derive(
+    Serialize, Deserialize, JsonSchema, IntoStaticStr, Debug, Hash, Clone, PartialEq, Eq,
+)]
 #[serde(rename_all = "kebab-case", tag = "type", content = "data")]
 pub enum ImageSource<'a> {
     /// Image is sourced from an arbitrary URL.
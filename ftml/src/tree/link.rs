@@ -23,7 +23,7 @@ use crate::data::PageRef;
 use crate::url::is_url;
 use std::borrow::Cow;
 
-#[derive(Serialize, Deserialize, Debug, Hash, Clone, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Hash, Clone, PartialEq, Eq)]
 #[serde(untagged)]
 pub enum LinkLocation<'a> {
     /// This link points to a particular page on a wiki.
@@ -132,7 +132,7 @@ fn test_link_location() {
     check!("page:multiple:category" => None, "page:multiple:category");
 }
 
-#[derive(Serialize, Deserialize, Debug, Hash, Clone, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Hash, Clone, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
 pub enum LinkLabel<'a> {
     /// Custom text link label.
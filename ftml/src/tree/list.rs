@@ -23,7 +23,7 @@ use super::clone::elements_to_owned;
 use super::Element;
 use strum_macros::IntoStaticStr;
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case", tag = "item-type")]
 pub enum ListItem<'t> {
     /// This item is a series of elements.
@@ -66,7 +66,16 @@ impl ListItem<'_> {
 }
 
 #[derive(
-    Serialize, Deserialize, IntoStaticStr, Debug, Copy, Clone, Hash, PartialEq, Eq,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    IntoStaticStr,
+    Debug,
+    Copy,
+    Clone,
+    Hash,
+    PartialEq,
+    Eq,
 )]
 #[serde(rename_all = "kebab-case")]
 pub enum ListType {
@@ -0,0 +1,101 @@
+/*
+ * tree/media.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::{AttributeMap, ImageSource};
+use ref_map::*;
+use strum_macros::IntoStaticStr;
+
+/// An audio or video file, as produced by `[[audio]]` and `[[video]]`.
+///
+/// Sources (including the poster and captions) are resolved
+/// the same way as images.
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct Media<'t> {
+    #[serde(rename = "type")]
+    pub mtype: MediaType,
+    pub source: ImageSource<'t>,
+    pub poster: Option<ImageSource<'t>>,
+    pub captions: Option<ImageSource<'t>>,
+    pub controls: bool,
+    #[serde(rename = "loop")]
+    pub looped: bool,
+
+    /// Whether the media plays automatically.
+    ///
+    /// Browsers only permit autoplay for muted media,
+    /// so this also mutes it.
+    pub autoplay: bool,
+    pub attributes: AttributeMap<'t>,
+}
+
+impl Media<'_> {
+    pub fn to_owned(&self) -> Media<'static> {
+        Media {
+            mtype: self.mtype,
+            source: self.source.to_owned(),
+            poster: self.poster.ref_map(|source| source.to_owned()),
+            captions: self.captions.ref_map(|source| source.to_owned()),
+            controls: self.controls,
+            looped: self.looped,
+            autoplay: self.autoplay,
+            attributes: self.attributes.to_owned(),
+        }
+    }
+}
+
+#[derive(
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    IntoStaticStr,
+    Debug,
+    Copy,
+    Clone,
+    Hash,
+    PartialEq,
+    Eq,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum MediaType {
+    Audio,
+    Video,
+}
+
+impl MediaType {
+    #[inline]
+    pub fn name(self) -> &'static str {
+        self.into()
+    }
+
+    pub fn html_tag(self) -> &'static str {
+        match self {
+            MediaType::Audio => "audio",
+            MediaType::Video => "video",
+        }
+    }
+
+    pub fn html_class(self) -> &'static str {
+        match self {
+            MediaType::Audio => "wj-audio",
+            MediaType::Video => "wj-video",
+        }
+    }
+}
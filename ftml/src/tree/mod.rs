@@ -19,9 +19,11 @@
  */
 
 pub mod attribute;
+pub mod visit;
 
 mod align;
 mod anchor;
+mod bibliography;
 mod clear_float;
 mod clone;
 mod container;
@@ -29,10 +31,12 @@ mod date;
 mod definition_list;
 mod element;
 mod embed;
+mod gallery;
 mod heading;
 mod image;
 mod link;
 mod list;
+mod media;
 mod module;
 mod partial;
 mod tab;
@@ -43,16 +47,19 @@ mod variables;
 pub use self::align::*;
 pub use self::anchor::*;
 pub use self::attribute::AttributeMap;
+pub use self::bibliography::*;
 pub use self::clear_float::*;
 pub use self::container::*;
 pub use self::date::Date;
 pub use self::definition_list::*;
 pub use self::element::*;
 pub use self::embed::*;
+pub use self::gallery::*;
 pub use self::heading::*;
 pub use self::image::*;
 pub use self::link::*;
 pub use self::list::*;
+pub use self::media::*;
 pub use self::module::*;
 pub use self::partial::*;
 pub use self::tab::*;
@@ -64,7 +71,7 @@ use self::clone::{elements_lists_to_owned, elements_to_owned, strings_to_owned};
 use crate::parsing::{ParseOutcome, ParseWarning};
 use std::borrow::Cow;
 
-#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Default, Clone, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
 pub struct SyntaxTree<'t> {
     /// The list of elements that compose this tree.
@@ -88,6 +95,10 @@ pub struct SyntaxTree<'t> {
 
     /// The full footnote list for this page.
     pub footnotes: Vec<Vec<Element<'t>>>,
+
+    /// The bibliographies for this page, with all of their entries.
+    #[serde(default)]
+    pub bibliographies: BibliographyList<'t>,
 }
 
 impl<'t> SyntaxTree<'t> {
@@ -97,12 +108,14 @@ impl<'t> SyntaxTree<'t> {
         styles: Vec<Cow<'t, str>>,
         table_of_contents: Vec<Element<'t>>,
         footnotes: Vec<Vec<Element<'t>>>,
+        bibliographies: BibliographyList<'t>,
     ) -> ParseOutcome<Self> {
         let tree = SyntaxTree {
             elements,
             styles,
             table_of_contents,
             footnotes,
+            bibliographies,
         };
         ParseOutcome::new(tree, warnings)
     }
@@ -113,6 +126,7 @@ impl<'t> SyntaxTree<'t> {
             styles: strings_to_owned(&self.styles),
             table_of_contents: elements_to_owned(&self.table_of_contents),
             footnotes: elements_lists_to_owned(&self.footnotes),
+            bibliographies: self.bibliographies.to_owned(),
         }
     }
 }
@@ -26,7 +26,7 @@ use crate::parsing::ParseWarningKind;
 /// These are used by specific rules attempting to
 /// build complex or nested structures. From any other
 /// context, they are errors are parsing will fail.
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq, Eq)]
 pub enum PartialElement<'t> {
     /// An item or sub-list within some list.
     ListItem(ListItem<'t>),
@@ -22,7 +22,7 @@ use super::clone::{elements_to_owned, string_to_owned};
 use super::Element;
 use std::borrow::Cow;
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq, Eq)]
 pub struct Tab<'t> {
     pub label: Cow<'t, str>,
     pub elements: Vec<Element<'t>>,
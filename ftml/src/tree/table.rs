@@ -22,7 +22,7 @@ use super::clone::elements_to_owned;
 use super::{Alignment, AttributeMap, Element};
 use std::num::NonZeroU32;
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
 pub struct Table<'t> {
     pub attributes: AttributeMap<'t>,
@@ -38,7 +38,7 @@ impl Table<'_> {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
 pub struct TableRow<'t> {
     pub attributes: AttributeMap<'t>,
@@ -54,7 +54,7 @@ impl TableRow<'_> {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
 pub struct TableCell<'t> {
     pub header: bool,
@@ -76,7 +76,7 @@ impl TableCell<'_> {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
 pub enum TableItem<'t> {
     Row(TableRow<'t>),
@@ -0,0 +1,485 @@
+/*
+ * tree/visit/fold.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use crate::data::PageRef;
+use crate::tree::{
+    AttributeMap, Bibliography, BibliographyEntry, BibliographyList, Container,
+    DefinitionListItem, Element, GalleryImage, ImageSource, LinkLocation, ListItem,
+    Media, PartialElement, SyntaxTree, Tab, Table, TableCell, TableRow,
+};
+use std::mem;
+
+/// Transforms a syntax tree by taking ownership of it and rebuilding it.
+///
+/// Unlike [`VisitorMut`](super::VisitorMut), [`fold_elements`](Fold::fold_elements)
+/// can change the number of elements in a list, such as to remove or unwrap elements.
+///
+/// See the [module-level documentation](self) for details.
+pub trait Fold<'t> {
+    fn fold_syntax_tree(&mut self, tree: SyntaxTree<'t>) -> SyntaxTree<'t> {
+        fold_syntax_tree(self, tree)
+    }
+
+    fn fold_elements(&mut self, elements: Vec<Element<'t>>) -> Vec<Element<'t>> {
+        fold_elements(self, elements)
+    }
+
+    fn fold_element(&mut self, element: Element<'t>) -> Element<'t> {
+        fold_element(self, element)
+    }
+
+    fn fold_container(&mut self, container: Container<'t>) -> Container<'t> {
+        fold_container(self, container)
+    }
+
+    fn fold_table(&mut self, table: Table<'t>) -> Table<'t> {
+        fold_table(self, table)
+    }
+
+    fn fold_table_row(&mut self, row: TableRow<'t>) -> TableRow<'t> {
+        fold_table_row(self, row)
+    }
+
+    fn fold_table_cell(&mut self, cell: TableCell<'t>) -> TableCell<'t> {
+        fold_table_cell(self, cell)
+    }
+
+    fn fold_tab(&mut self, tab: Tab<'t>) -> Tab<'t> {
+        fold_tab(self, tab)
+    }
+
+    fn fold_list_item(&mut self, item: ListItem<'t>) -> ListItem<'t> {
+        fold_list_item(self, item)
+    }
+
+    fn fold_definition_list_item(
+        &mut self,
+        item: DefinitionListItem<'t>,
+    ) -> DefinitionListItem<'t> {
+        fold_definition_list_item(self, item)
+    }
+
+    fn fold_gallery_image(&mut self, image: GalleryImage<'t>) -> GalleryImage<'t> {
+        fold_gallery_image(self, image)
+    }
+
+    fn fold_bibliography_entry(
+        &mut self,
+        entry: BibliographyEntry<'t>,
+    ) -> BibliographyEntry<'t> {
+        fold_bibliography_entry(self, entry)
+    }
+
+    fn fold_link_location(&mut self, link: LinkLocation<'t>) -> LinkLocation<'t> {
+        fold_link_location(self, link)
+    }
+
+    fn fold_page_ref(&mut self, page_ref: PageRef<'t>) -> PageRef<'t> {
+        page_ref
+    }
+
+    fn fold_image_source(&mut self, source: ImageSource<'t>) -> ImageSource<'t> {
+        source
+    }
+
+    fn fold_attributes(&mut self, attributes: AttributeMap<'t>) -> AttributeMap<'t> {
+        attributes
+    }
+}
+
+/// Folds the page's elements, followed by its table of contents,
+/// footnotes, and bibliographies.
+pub fn fold_syntax_tree<'t, F>(folder: &mut F, tree: SyntaxTree<'t>) -> SyntaxTree<'t>
+where
+    F: Fold<'t> + ?Sized,
+{
+    let SyntaxTree {
+        elements,
+        styles,
+        table_of_contents,
+        footnotes,
+        bibliographies,
+    } = tree;
+
+    let elements = folder.fold_elements(elements);
+    let table_of_contents = folder.fold_elements(table_of_contents);
+    let footnotes = footnotes
+        .into_iter()
+        .map(|footnote| folder.fold_elements(footnote))
+        .collect();
+
+    let mut folded_bibliographies = BibliographyList::new();
+    for bibliography in bibliographies {
+        let entries = bibliography
+            .entries
+            .into_iter()
+            .map(|entry| folder.fold_bibliography_entry(entry))
+            .collect();
+
+        folded_bibliographies.push(Bibliography { entries });
+    }
+
+    SyntaxTree {
+        elements,
+        styles,
+        table_of_contents,
+        footnotes,
+        bibliographies: folded_bibliographies,
+    }
+}
+
+pub fn fold_elements<'t, F>(
+    folder: &mut F,
+    elements: Vec<Element<'t>>,
+) -> Vec<Element<'t>>
+where
+    F: Fold<'t> + ?Sized,
+{
+    elements
+        .into_iter()
+        .map(|element| folder.fold_element(element))
+        .collect()
+}
+
+pub fn fold_element<'t, F>(folder: &mut F, element: Element<'t>) -> Element<'t>
+where
+    F: Fold<'t> + ?Sized,
+{
+    match element {
+        Element::Container(container) => {
+            Element::Container(folder.fold_container(container))
+        }
+        Element::Table(table) => Element::Table(folder.fold_table(table)),
+        Element::TabView(tabs) => {
+            Element::TabView(tabs.into_iter().map(|tab| folder.fold_tab(tab)).collect())
+        }
+        Element::Anchor {
+            target,
+            attributes,
+            elements,
+        } => Element::Anchor {
+            target,
+            attributes: folder.fold_attributes(attributes),
+            elements: folder.fold_elements(elements),
+        },
+        Element::Link {
+            link,
+            label,
+            target,
+        } => Element::Link {
+            link: folder.fold_link_location(link),
+            label,
+            target,
+        },
+        Element::Image {
+            source,
+            link,
+            alignment,
+            attributes,
+        } => Element::Image {
+            source: folder.fold_image_source(source),
+            link: link.map(|link| folder.fold_link_location(link)),
+            alignment,
+            attributes: folder.fold_attributes(attributes),
+        },
+        Element::Gallery {
+            images,
+            size,
+            attributes,
+        } => Element::Gallery {
+            images: images
+                .into_iter()
+                .map(|image| folder.fold_gallery_image(image))
+                .collect(),
+            size,
+            attributes: folder.fold_attributes(attributes),
+        },
+        Element::Media(media) => {
+            let Media {
+                mtype,
+                source,
+                poster,
+                captions,
+                controls,
+                looped,
+                autoplay,
+                attributes,
+            } = *media;
+
+            Element::Media(Box::new(Media {
+                mtype,
+                source: folder.fold_image_source(source),
+                poster: poster.map(|poster| folder.fold_image_source(poster)),
+                captions: captions.map(|captions| folder.fold_image_source(captions)),
+                controls,
+                looped,
+                autoplay,
+                attributes: folder.fold_attributes(attributes),
+            }))
+        }
+        Element::List {
+            ltype,
+            attributes,
+            items,
+        } => Element::List {
+            ltype,
+            attributes: folder.fold_attributes(attributes),
+            items: items
+                .into_iter()
+                .map(|item| folder.fold_list_item(item))
+                .collect(),
+        },
+        Element::DefinitionList(items) => Element::DefinitionList(
+            items
+                .into_iter()
+                .map(|item| folder.fold_definition_list_item(item))
+                .collect(),
+        ),
+        Element::RadioButton {
+            name,
+            checked,
+            attributes,
+        } => Element::RadioButton {
+            name,
+            checked,
+            attributes: folder.fold_attributes(attributes),
+        },
+        Element::CheckBox {
+            checked,
+            attributes,
+        } => Element::CheckBox {
+            checked,
+            attributes: folder.fold_attributes(attributes),
+        },
+        Element::Collapsible {
+            elements,
+            attributes,
+            start_open,
+            show_text,
+            hide_text,
+            show_top,
+            show_bottom,
+        } => Element::Collapsible {
+            elements: folder.fold_elements(elements),
+            attributes: folder.fold_attributes(attributes),
+            start_open,
+            show_text,
+            hide_text,
+            show_top,
+            show_bottom,
+        },
+        Element::TableOfContents { attributes, align } => Element::TableOfContents {
+            attributes: folder.fold_attributes(attributes),
+            align,
+        },
+        Element::Color { color, elements } => Element::Color {
+            color,
+            elements: folder.fold_elements(elements),
+        },
+        Element::Iframe { attributes, url } => Element::Iframe {
+            attributes: folder.fold_attributes(attributes),
+            url,
+        },
+        Element::Include {
+            paragraph_safe,
+            variables,
+            location,
+            elements,
+        } => Element::Include {
+            paragraph_safe,
+            variables,
+            location: folder.fold_page_ref(location),
+            elements: folder.fold_elements(elements),
+        },
+        Element::Partial(partial) => Element::Partial(match partial {
+            PartialElement::ListItem(item) => {
+                PartialElement::ListItem(folder.fold_list_item(item))
+            }
+            PartialElement::TableRow(row) => {
+                PartialElement::TableRow(folder.fold_table_row(row))
+            }
+            PartialElement::TableCell(cell) => {
+                PartialElement::TableCell(folder.fold_table_cell(cell))
+            }
+            PartialElement::Tab(tab) => PartialElement::Tab(folder.fold_tab(tab)),
+        }),
+        element @ (Element::Module(_)
+        | Element::Text(_)
+        | Element::Raw(_)
+        | Element::Variable(_)
+        | Element::Email(_)
+        | Element::Footnote
+        | Element::FootnoteBlock { .. }
+        | Element::BibliographyCite { .. }
+        | Element::BibliographyBlock { .. }
+        | Element::User { .. }
+        | Element::Date { .. }
+        | Element::Code { .. }
+        | Element::Math { .. }
+        | Element::MathInline { .. }
+        | Element::EquationReference(_)
+        | Element::Embed(_)
+        | Element::Html { .. }
+        | Element::LineBreak
+        | Element::LineBreaks(_)
+        | Element::ClearFloat(_)
+        | Element::HorizontalRule) => element,
+    }
+}
+
+pub fn fold_container<'t, F>(
+    folder: &mut F,
+    mut container: Container<'t>,
+) -> Container<'t>
+where
+    F: Fold<'t> + ?Sized,
+{
+    let attributes = mem::take(container.attributes_mut());
+    *container.attributes_mut() = folder.fold_attributes(attributes);
+
+    let elements = mem::take(container.elements_mut());
+    *container.elements_mut() = folder.fold_elements(elements);
+
+    container
+}
+
+pub fn fold_table<'t, F>(folder: &mut F, table: Table<'t>) -> Table<'t>
+where
+    F: Fold<'t> + ?Sized,
+{
+    Table {
+        attributes: folder.fold_attributes(table.attributes),
+        rows: table
+            .rows
+            .into_iter()
+            .map(|row| folder.fold_table_row(row))
+            .collect(),
+    }
+}
+
+pub fn fold_table_row<'t, F>(folder: &mut F, row: TableRow<'t>) -> TableRow<'t>
+where
+    F: Fold<'t> + ?Sized,
+{
+    TableRow {
+        attributes: folder.fold_attributes(row.attributes),
+        cells: row
+            .cells
+            .into_iter()
+            .map(|cell| folder.fold_table_cell(cell))
+            .collect(),
+    }
+}
+
+pub fn fold_table_cell<'t, F>(folder: &mut F, cell: TableCell<'t>) -> TableCell<'t>
+where
+    F: Fold<'t> + ?Sized,
+{
+    TableCell {
+        attributes: folder.fold_attributes(cell.attributes),
+        elements: folder.fold_elements(cell.elements),
+        ..cell
+    }
+}
+
+pub fn fold_tab<'t, F>(folder: &mut F, tab: Tab<'t>) -> Tab<'t>
+where
+    F: Fold<'t> + ?Sized,
+{
+    Tab {
+        label: tab.label,
+        elements: folder.fold_elements(tab.elements),
+    }
+}
+
+pub fn fold_list_item<'t, F>(folder: &mut F, item: ListItem<'t>) -> ListItem<'t>
+where
+    F: Fold<'t> + ?Sized,
+{
+    match item {
+        ListItem::Elements {
+            attributes,
+            elements,
+        } => ListItem::Elements {
+            attributes: folder.fold_attributes(attributes),
+            elements: folder.fold_elements(elements),
+        },
+        ListItem::SubList { element } => ListItem::SubList {
+            element: Box::new(folder.fold_element(*element)),
+        },
+    }
+}
+
+pub fn fold_definition_list_item<'t, F>(
+    folder: &mut F,
+    item: DefinitionListItem<'t>,
+) -> DefinitionListItem<'t>
+where
+    F: Fold<'t> + ?Sized,
+{
+    DefinitionListItem {
+        key: folder.fold_elements(item.key),
+        value: folder.fold_elements(item.value),
+    }
+}
+
+pub fn fold_gallery_image<'t, F>(
+    folder: &mut F,
+    image: GalleryImage<'t>,
+) -> GalleryImage<'t>
+where
+    F: Fold<'t> + ?Sized,
+{
+    GalleryImage {
+        source: folder.fold_image_source(image.source),
+        title: image.title,
+        caption: image.caption,
+        link: image.link.map(|link| folder.fold_link_location(link)),
+        attributes: folder.fold_attributes(image.attributes),
+    }
+}
+
+pub fn fold_bibliography_entry<'t, F>(
+    folder: &mut F,
+    entry: BibliographyEntry<'t>,
+) -> BibliographyEntry<'t>
+where
+    F: Fold<'t> + ?Sized,
+{
+    BibliographyEntry {
+        elements: folder.fold_elements(entry.elements),
+        ..entry
+    }
+}
+
+pub fn fold_link_location<'t, F>(
+    folder: &mut F,
+    link: LinkLocation<'t>,
+) -> LinkLocation<'t>
+where
+    F: Fold<'t> + ?Sized,
+{
+    match link {
+        LinkLocation::Page(page_ref) => {
+            LinkLocation::Page(folder.fold_page_ref(page_ref))
+        }
+        LinkLocation::Url(url) => LinkLocation::Url(url),
+    }
+}
@@ -0,0 +1,65 @@
+/*
+ * tree/visit/mod.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Traversal of syntax trees, for inspecting or transforming every element.
+//!
+//! There are three traits here, each with a method for every kind of node
+//! which can appear in a syntax tree:
+//!
+//! * [`Visitor`] borrows the tree, for extracting information from it.
+//! * [`VisitorMut`] mutably borrows the tree, for changing it in place.
+//! * [`Fold`] takes ownership of the tree and rebuilds it, for transformations
+//!   which replace, remove, or insert elements.
+//!
+//! Every method has a default implementation, which calls the corresponding
+//! `walk_*` or `fold_*` function in this module to recurse into the node's
+//! children. An implementation only overrides the methods for the nodes it
+//! cares about, calling the function itself if it still wants to recurse.
+//!
+//! When new elements are added, only these functions need to be updated
+//! for all implementations to traverse them.
+//!
+//! ```
+//! # use ftml::tree::visit::{walk_element, Visitor};
+//! # use ftml::tree::Element;
+//! #[derive(Default)]
+//! struct CodeCounter(usize);
+//!
+//! impl<'a> Visitor<'a> for CodeCounter {
+//!     fn visit_element(&mut self, element: &'a Element<'a>) {
+//!         if let Element::Code { .. } = element {
+//!             self.0 += 1;
+//!         }
+//!
+//!         walk_element(self, element);
+//!     }
+//! }
+//! ```
+
+#[cfg(test)]
+mod test;
+
+mod fold;
+mod visitor;
+mod visitor_mut;
+
+pub use self::fold::*;
+pub use self::visitor::*;
+pub use self::visitor_mut::*;
@@ -0,0 +1,174 @@
+/*
+ * tree/visit/test.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::*;
+use crate::data::{PageInfo, PageRef};
+use crate::settings::{WikitextMode, WikitextSettings};
+use crate::tree::{Container, ContainerType, Element, SyntaxTree};
+use std::borrow::Cow;
+
+/// Wikitext with page links nested in each kind of element.
+const INPUT: &str = "\
+**Bold [[[bold-link]]]**
+
+|| Cell [[[table-link]]] ||
+
+* Item [[[list-link]]]
+ * Nested [[[sublist-link]]]
+
+: Term : Definition [[[definition-link]]]
+
+[[collapsible]]
+Hidden [[[collapsible-link]]]
+[[/collapsible]]
+
+[[tabview]]
+[[tab First]]
+Tab [[[tab-link]]]
+[[/tab]]
+[[/tabview]]
+
+Note[[footnote]]Footnote [[[footnote-link]]][[/footnote]]
+
+[[bibliography]]
+: source : Entry [[[bibliography-link]]]
+[[/bibliography]]";
+
+const LINKS: [&str; 9] = [
+    "bibliography-link",
+    "bold-link",
+    "collapsible-link",
+    "definition-link",
+    "footnote-link",
+    "list-link",
+    "sublist-link",
+    "tab-link",
+    "table-link",
+];
+
+fn parse(text: &str) -> SyntaxTree<'static> {
+    let log = crate::build_logger();
+    let page_info = PageInfo::dummy();
+    let settings = WikitextSettings::from_mode(WikitextMode::Page);
+
+    let tokens = crate::tokenize(&log, text);
+    let (tree, _warnings) = crate::parse(&log, &tokens, &page_info, &settings).into();
+    tree.to_owned()
+}
+
+#[derive(Debug, Default)]
+struct LinkCollector<'a> {
+    pages: Vec<&'a str>,
+    bold: usize,
+}
+
+impl<'a> Visitor<'a> for LinkCollector<'a> {
+    fn visit_container(&mut self, container: &'a Container<'a>) {
+        if container.ctype() == ContainerType::Bold {
+            self.bold += 1;
+        }
+
+        walk_container(self, container);
+    }
+
+    fn visit_page_ref(&mut self, page_ref: &'a PageRef<'a>) {
+        self.pages.push(page_ref.page());
+    }
+}
+
+fn collect_links(tree: &SyntaxTree) -> (Vec<String>, usize) {
+    let mut collector = LinkCollector::default();
+    collector.visit_syntax_tree(tree);
+
+    let mut pages: Vec<String> = collector.pages.iter().map(|&page| str!(page)).collect();
+    pages.sort();
+
+    (pages, collector.bold)
+}
+
+#[test]
+fn visitor() {
+    let tree = parse(INPUT);
+    let (pages, bold) = collect_links(&tree);
+
+    assert_eq!(pages, LINKS);
+    assert_eq!(bold, 1);
+}
+
+#[test]
+fn visitor_mut() {
+    struct LinkRewriter;
+
+    impl<'t> VisitorMut<'t> for LinkRewriter {
+        fn visit_page_ref_mut(&mut self, page_ref: &mut PageRef<'t>) {
+            page_ref.site = Some(cow!("archive"));
+            page_ref.page = Cow::Owned(format!("old-{}", page_ref.page));
+        }
+    }
+
+    let mut tree = parse(INPUT);
+    LinkRewriter.visit_syntax_tree_mut(&mut tree);
+
+    let mut expected: Vec<String> =
+        LINKS.iter().map(|page| format!("old-{}", page)).collect();
+    expected.sort();
+    let (pages, _) = collect_links(&tree);
+
+    assert_eq!(pages, expected);
+}
+
+#[test]
+fn fold() {
+    /// Replaces bold text with its contents, and removes line breaks.
+    struct Unbolder;
+
+    impl<'t> Fold<'t> for Unbolder {
+        fn fold_elements(&mut self, elements: Vec<Element<'t>>) -> Vec<Element<'t>> {
+            let mut folded = Vec::new();
+
+            for element in elements {
+                match self.fold_element(element) {
+                    Element::Container(container)
+                        if container.ctype() == ContainerType::Bold =>
+                    {
+                        folded.extend(Vec::from(container));
+                    }
+                    Element::LineBreak => (),
+                    element => folded.push(element),
+                }
+            }
+
+            folded
+        }
+    }
+
+    let tree = Unbolder.fold_syntax_tree(parse(INPUT));
+    let (pages, bold) = collect_links(&tree);
+
+    assert_eq!(pages, LINKS, "Links were lost while folding");
+    assert_eq!(bold, 0, "Bold containers were not removed");
+
+    // Folding with no overrides changes nothing
+    struct Identity;
+    impl<'t> Fold<'t> for Identity {}
+
+    let tree = parse(INPUT);
+    assert_eq!(Identity.fold_syntax_tree(tree.clone()), tree);
+}
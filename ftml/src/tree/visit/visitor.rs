@@ -0,0 +1,337 @@
+/*
+ * tree/visit/visitor.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use crate::data::PageRef;
+use crate::tree::{
+    AttributeMap, BibliographyEntry, Container, DefinitionListItem, Element,
+    GalleryImage, ImageSource, LinkLocation, ListItem, PartialElement, SyntaxTree, Tab,
+    Table, TableCell, TableRow,
+};
+
+/// Traverses a syntax tree by reference.
+///
+/// See the [module-level documentation](self) for details.
+pub trait Visitor<'a> {
+    fn visit_syntax_tree(&mut self, tree: &'a SyntaxTree<'a>) {
+        walk_syntax_tree(self, tree);
+    }
+
+    fn visit_elements(&mut self, elements: &'a [Element<'a>]) {
+        walk_elements(self, elements);
+    }
+
+    fn visit_element(&mut self, element: &'a Element<'a>) {
+        walk_element(self, element);
+    }
+
+    fn visit_container(&mut self, container: &'a Container<'a>) {
+        walk_container(self, container);
+    }
+
+    fn visit_table(&mut self, table: &'a Table<'a>) {
+        walk_table(self, table);
+    }
+
+    fn visit_table_row(&mut self, row: &'a TableRow<'a>) {
+        walk_table_row(self, row);
+    }
+
+    fn visit_table_cell(&mut self, cell: &'a TableCell<'a>) {
+        walk_table_cell(self, cell);
+    }
+
+    fn visit_tab(&mut self, tab: &'a Tab<'a>) {
+        walk_tab(self, tab);
+    }
+
+    fn visit_list_item(&mut self, item: &'a ListItem<'a>) {
+        walk_list_item(self, item);
+    }
+
+    fn visit_definition_list_item(&mut self, item: &'a DefinitionListItem<'a>) {
+        walk_definition_list_item(self, item);
+    }
+
+    fn visit_gallery_image(&mut self, image: &'a GalleryImage<'a>) {
+        walk_gallery_image(self, image);
+    }
+
+    fn visit_bibliography_entry(&mut self, entry: &'a BibliographyEntry<'a>) {
+        walk_bibliography_entry(self, entry);
+    }
+
+    fn visit_link_location(&mut self, link: &'a LinkLocation<'a>) {
+        walk_link_location(self, link);
+    }
+
+    fn visit_page_ref(&mut self, _page_ref: &'a PageRef<'a>) {}
+
+    fn visit_image_source(&mut self, _source: &'a ImageSource<'a>) {}
+
+    fn visit_attributes(&mut self, _attributes: &'a AttributeMap<'a>) {}
+}
+
+/// Visits the page's elements, followed by its table of contents,
+/// footnotes, and bibliographies.
+pub fn walk_syntax_tree<'a, V>(visitor: &mut V, tree: &'a SyntaxTree<'a>)
+where
+    V: Visitor<'a> + ?Sized,
+{
+    visitor.visit_elements(&tree.elements);
+    visitor.visit_elements(&tree.table_of_contents);
+
+    for footnote in &tree.footnotes {
+        visitor.visit_elements(footnote);
+    }
+
+    for bibliography in tree.bibliographies.iter() {
+        for entry in &bibliography.entries {
+            visitor.visit_bibliography_entry(entry);
+        }
+    }
+}
+
+pub fn walk_elements<'a, V>(visitor: &mut V, elements: &'a [Element<'a>])
+where
+    V: Visitor<'a> + ?Sized,
+{
+    for element in elements {
+        visitor.visit_element(element);
+    }
+}
+
+pub fn walk_element<'a, V>(visitor: &mut V, element: &'a Element<'a>)
+where
+    V: Visitor<'a> + ?Sized,
+{
+    match element {
+        Element::Container(container) => visitor.visit_container(container),
+        Element::Table(table) => visitor.visit_table(table),
+        Element::TabView(tabs) => {
+            for tab in tabs {
+                visitor.visit_tab(tab);
+            }
+        }
+        Element::Anchor {
+            attributes,
+            elements,
+            ..
+        } => {
+            visitor.visit_attributes(attributes);
+            visitor.visit_elements(elements);
+        }
+        Element::Link { link, .. } => visitor.visit_link_location(link),
+        Element::Image {
+            source,
+            link,
+            attributes,
+            ..
+        } => {
+            visitor.visit_image_source(source);
+
+            if let Some(link) = link {
+                visitor.visit_link_location(link);
+            }
+
+            visitor.visit_attributes(attributes);
+        }
+        Element::Gallery {
+            images, attributes, ..
+        } => {
+            for image in images {
+                visitor.visit_gallery_image(image);
+            }
+
+            visitor.visit_attributes(attributes);
+        }
+        Element::Media(media) => {
+            visitor.visit_image_source(&media.source);
+
+            if let Some(poster) = &media.poster {
+                visitor.visit_image_source(poster);
+            }
+
+            if let Some(captions) = &media.captions {
+                visitor.visit_image_source(captions);
+            }
+
+            visitor.visit_attributes(&media.attributes);
+        }
+        Element::List {
+            attributes, items, ..
+        } => {
+            visitor.visit_attributes(attributes);
+
+            for item in items {
+                visitor.visit_list_item(item);
+            }
+        }
+        Element::DefinitionList(items) => {
+            for item in items {
+                visitor.visit_definition_list_item(item);
+            }
+        }
+        Element::RadioButton { attributes, .. }
+        | Element::CheckBox { attributes, .. }
+        | Element::TableOfContents { attributes, .. }
+        | Element::Iframe { attributes, .. } => visitor.visit_attributes(attributes),
+        Element::Collapsible {
+            elements,
+            attributes,
+            ..
+        } => {
+            visitor.visit_attributes(attributes);
+            visitor.visit_elements(elements);
+        }
+        Element::Color { elements, .. } => visitor.visit_elements(elements),
+        Element::Include {
+            location, elements, ..
+        } => {
+            visitor.visit_page_ref(location);
+            visitor.visit_elements(elements);
+        }
+        Element::Partial(partial) => match partial {
+            PartialElement::ListItem(item) => visitor.visit_list_item(item),
+            PartialElement::TableRow(row) => visitor.visit_table_row(row),
+            PartialElement::TableCell(cell) => visitor.visit_table_cell(cell),
+            PartialElement::Tab(tab) => visitor.visit_tab(tab),
+        },
+        Element::Module(_)
+        | Element::Text(_)
+        | Element::Raw(_)
+        | Element::Variable(_)
+        | Element::Email(_)
+        | Element::Footnote
+        | Element::FootnoteBlock { .. }
+        | Element::BibliographyCite { .. }
+        | Element::BibliographyBlock { .. }
+        | Element::User { .. }
+        | Element::Date { .. }
+        | Element::Code { .. }
+        | Element::Math { .. }
+        | Element::MathInline { .. }
+        | Element::EquationReference(_)
+        | Element::Embed(_)
+        | Element::Html { .. }
+        | Element::LineBreak
+        | Element::LineBreaks(_)
+        | Element::ClearFloat(_)
+        | Element::HorizontalRule => (),
+    }
+}
+
+pub fn walk_container<'a, V>(visitor: &mut V, container: &'a Container<'a>)
+where
+    V: Visitor<'a> + ?Sized,
+{
+    visitor.visit_attributes(container.attributes());
+    visitor.visit_elements(container.elements());
+}
+
+pub fn walk_table<'a, V>(visitor: &mut V, table: &'a Table<'a>)
+where
+    V: Visitor<'a> + ?Sized,
+{
+    visitor.visit_attributes(&table.attributes);
+
+    for row in &table.rows {
+        visitor.visit_table_row(row);
+    }
+}
+
+pub fn walk_table_row<'a, V>(visitor: &mut V, row: &'a TableRow<'a>)
+where
+    V: Visitor<'a> + ?Sized,
+{
+    visitor.visit_attributes(&row.attributes);
+
+    for cell in &row.cells {
+        visitor.visit_table_cell(cell);
+    }
+}
+
+pub fn walk_table_cell<'a, V>(visitor: &mut V, cell: &'a TableCell<'a>)
+where
+    V: Visitor<'a> + ?Sized,
+{
+    visitor.visit_attributes(&cell.attributes);
+    visitor.visit_elements(&cell.elements);
+}
+
+pub fn walk_tab<'a, V>(visitor: &mut V, tab: &'a Tab<'a>)
+where
+    V: Visitor<'a> + ?Sized,
+{
+    visitor.visit_elements(&tab.elements);
+}
+
+pub fn walk_list_item<'a, V>(visitor: &mut V, item: &'a ListItem<'a>)
+where
+    V: Visitor<'a> + ?Sized,
+{
+    match item {
+        ListItem::Elements {
+            attributes,
+            elements,
+        } => {
+            visitor.visit_attributes(attributes);
+            visitor.visit_elements(elements);
+        }
+        ListItem::SubList { element } => visitor.visit_element(element),
+    }
+}
+
+pub fn walk_definition_list_item<'a, V>(visitor: &mut V, item: &'a DefinitionListItem<'a>)
+where
+    V: Visitor<'a> + ?Sized,
+{
+    visitor.visit_elements(&item.key);
+    visitor.visit_elements(&item.value);
+}
+
+pub fn walk_gallery_image<'a, V>(visitor: &mut V, image: &'a GalleryImage<'a>)
+where
+    V: Visitor<'a> + ?Sized,
+{
+    visitor.visit_image_source(&image.source);
+
+    if let Some(link) = &image.link {
+        visitor.visit_link_location(link);
+    }
+
+    visitor.visit_attributes(&image.attributes);
+}
+
+pub fn walk_bibliography_entry<'a, V>(visitor: &mut V, entry: &'a BibliographyEntry<'a>)
+where
+    V: Visitor<'a> + ?Sized,
+{
+    visitor.visit_elements(&entry.elements);
+}
+
+pub fn walk_link_location<'a, V>(visitor: &mut V, link: &'a LinkLocation<'a>)
+where
+    V: Visitor<'a> + ?Sized,
+{
+    match link {
+        LinkLocation::Page(page_ref) => visitor.visit_page_ref(page_ref),
+        LinkLocation::Url(_) => (),
+    }
+}
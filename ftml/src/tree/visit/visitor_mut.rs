@@ -0,0 +1,341 @@
+/*
+ * tree/visit/visitor_mut.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use crate::data::PageRef;
+use crate::tree::{
+    AttributeMap, BibliographyEntry, Container, DefinitionListItem, Element,
+    GalleryImage, ImageSource, LinkLocation, ListItem, PartialElement, SyntaxTree, Tab,
+    Table, TableCell, TableRow,
+};
+
+/// Traverses a syntax tree by mutable reference, to modify it in place.
+///
+/// See the [module-level documentation](self) for details.
+pub trait VisitorMut<'t> {
+    fn visit_syntax_tree_mut(&mut self, tree: &mut SyntaxTree<'t>) {
+        walk_syntax_tree_mut(self, tree);
+    }
+
+    fn visit_elements_mut(&mut self, elements: &mut Vec<Element<'t>>) {
+        walk_elements_mut(self, elements);
+    }
+
+    fn visit_element_mut(&mut self, element: &mut Element<'t>) {
+        walk_element_mut(self, element);
+    }
+
+    fn visit_container_mut(&mut self, container: &mut Container<'t>) {
+        walk_container_mut(self, container);
+    }
+
+    fn visit_table_mut(&mut self, table: &mut Table<'t>) {
+        walk_table_mut(self, table);
+    }
+
+    fn visit_table_row_mut(&mut self, row: &mut TableRow<'t>) {
+        walk_table_row_mut(self, row);
+    }
+
+    fn visit_table_cell_mut(&mut self, cell: &mut TableCell<'t>) {
+        walk_table_cell_mut(self, cell);
+    }
+
+    fn visit_tab_mut(&mut self, tab: &mut Tab<'t>) {
+        walk_tab_mut(self, tab);
+    }
+
+    fn visit_list_item_mut(&mut self, item: &mut ListItem<'t>) {
+        walk_list_item_mut(self, item);
+    }
+
+    fn visit_definition_list_item_mut(&mut self, item: &mut DefinitionListItem<'t>) {
+        walk_definition_list_item_mut(self, item);
+    }
+
+    fn visit_gallery_image_mut(&mut self, image: &mut GalleryImage<'t>) {
+        walk_gallery_image_mut(self, image);
+    }
+
+    fn visit_bibliography_entry_mut(&mut self, entry: &mut BibliographyEntry<'t>) {
+        walk_bibliography_entry_mut(self, entry);
+    }
+
+    fn visit_link_location_mut(&mut self, link: &mut LinkLocation<'t>) {
+        walk_link_location_mut(self, link);
+    }
+
+    fn visit_page_ref_mut(&mut self, _page_ref: &mut PageRef<'t>) {}
+
+    fn visit_image_source_mut(&mut self, _source: &mut ImageSource<'t>) {}
+
+    fn visit_attributes_mut(&mut self, _attributes: &mut AttributeMap<'t>) {}
+}
+
+/// Visits the page's elements, followed by its table of contents,
+/// footnotes, and bibliographies.
+pub fn walk_syntax_tree_mut<'t, V>(visitor: &mut V, tree: &mut SyntaxTree<'t>)
+where
+    V: VisitorMut<'t> + ?Sized,
+{
+    visitor.visit_elements_mut(&mut tree.elements);
+    visitor.visit_elements_mut(&mut tree.table_of_contents);
+
+    for footnote in &mut tree.footnotes {
+        visitor.visit_elements_mut(footnote);
+    }
+
+    for bibliography in tree.bibliographies.iter_mut() {
+        for entry in &mut bibliography.entries {
+            visitor.visit_bibliography_entry_mut(entry);
+        }
+    }
+}
+
+pub fn walk_elements_mut<'t, V>(visitor: &mut V, elements: &mut Vec<Element<'t>>)
+where
+    V: VisitorMut<'t> + ?Sized,
+{
+    for element in elements {
+        visitor.visit_element_mut(element);
+    }
+}
+
+pub fn walk_element_mut<'t, V>(visitor: &mut V, element: &mut Element<'t>)
+where
+    V: VisitorMut<'t> + ?Sized,
+{
+    match element {
+        Element::Container(container) => visitor.visit_container_mut(container),
+        Element::Table(table) => visitor.visit_table_mut(table),
+        Element::TabView(tabs) => {
+            for tab in tabs {
+                visitor.visit_tab_mut(tab);
+            }
+        }
+        Element::Anchor {
+            attributes,
+            elements,
+            ..
+        } => {
+            visitor.visit_attributes_mut(attributes);
+            visitor.visit_elements_mut(elements);
+        }
+        Element::Link { link, .. } => visitor.visit_link_location_mut(link),
+        Element::Image {
+            source,
+            link,
+            attributes,
+            ..
+        } => {
+            visitor.visit_image_source_mut(source);
+
+            if let Some(link) = link {
+                visitor.visit_link_location_mut(link);
+            }
+
+            visitor.visit_attributes_mut(attributes);
+        }
+        Element::Gallery {
+            images, attributes, ..
+        } => {
+            for image in images {
+                visitor.visit_gallery_image_mut(image);
+            }
+
+            visitor.visit_attributes_mut(attributes);
+        }
+        Element::Media(media) => {
+            visitor.visit_image_source_mut(&mut media.source);
+
+            if let Some(poster) = &mut media.poster {
+                visitor.visit_image_source_mut(poster);
+            }
+
+            if let Some(captions) = &mut media.captions {
+                visitor.visit_image_source_mut(captions);
+            }
+
+            visitor.visit_attributes_mut(&mut media.attributes);
+        }
+        Element::List {
+            attributes, items, ..
+        } => {
+            visitor.visit_attributes_mut(attributes);
+
+            for item in items {
+                visitor.visit_list_item_mut(item);
+            }
+        }
+        Element::DefinitionList(items) => {
+            for item in items {
+                visitor.visit_definition_list_item_mut(item);
+            }
+        }
+        Element::RadioButton { attributes, .. }
+        | Element::CheckBox { attributes, .. }
+        | Element::TableOfContents { attributes, .. }
+        | Element::Iframe { attributes, .. } => visitor.visit_attributes_mut(attributes),
+        Element::Collapsible {
+            elements,
+            attributes,
+            ..
+        } => {
+            visitor.visit_attributes_mut(attributes);
+            visitor.visit_elements_mut(elements);
+        }
+        Element::Color { elements, .. } => visitor.visit_elements_mut(elements),
+        Element::Include {
+            location, elements, ..
+        } => {
+            visitor.visit_page_ref_mut(location);
+            visitor.visit_elements_mut(elements);
+        }
+        Element::Partial(partial) => match partial {
+            PartialElement::ListItem(item) => visitor.visit_list_item_mut(item),
+            PartialElement::TableRow(row) => visitor.visit_table_row_mut(row),
+            PartialElement::TableCell(cell) => visitor.visit_table_cell_mut(cell),
+            PartialElement::Tab(tab) => visitor.visit_tab_mut(tab),
+        },
+        Element::Module(_)
+        | Element::Text(_)
+        | Element::Raw(_)
+        | Element::Variable(_)
+        | Element::Email(_)
+        | Element::Footnote
+        | Element::FootnoteBlock { .. }
+        | Element::BibliographyCite { .. }
+        | Element::BibliographyBlock { .. }
+        | Element::User { .. }
+        | Element::Date { .. }
+        | Element::Code { .. }
+        | Element::Math { .. }
+        | Element::MathInline { .. }
+        | Element::EquationReference(_)
+        | Element::Embed(_)
+        | Element::Html { .. }
+        | Element::LineBreak
+        | Element::LineBreaks(_)
+        | Element::ClearFloat(_)
+        | Element::HorizontalRule => (),
+    }
+}
+
+pub fn walk_container_mut<'t, V>(visitor: &mut V, container: &mut Container<'t>)
+where
+    V: VisitorMut<'t> + ?Sized,
+{
+    visitor.visit_attributes_mut(container.attributes_mut());
+    visitor.visit_elements_mut(container.elements_mut());
+}
+
+pub fn walk_table_mut<'t, V>(visitor: &mut V, table: &mut Table<'t>)
+where
+    V: VisitorMut<'t> + ?Sized,
+{
+    visitor.visit_attributes_mut(&mut table.attributes);
+
+    for row in &mut table.rows {
+        visitor.visit_table_row_mut(row);
+    }
+}
+
+pub fn walk_table_row_mut<'t, V>(visitor: &mut V, row: &mut TableRow<'t>)
+where
+    V: VisitorMut<'t> + ?Sized,
+{
+    visitor.visit_attributes_mut(&mut row.attributes);
+
+    for cell in &mut row.cells {
+        visitor.visit_table_cell_mut(cell);
+    }
+}
+
+pub fn walk_table_cell_mut<'t, V>(visitor: &mut V, cell: &mut TableCell<'t>)
+where
+    V: VisitorMut<'t> + ?Sized,
+{
+    visitor.visit_attributes_mut(&mut cell.attributes);
+    visitor.visit_elements_mut(&mut cell.elements);
+}
+
+pub fn walk_tab_mut<'t, V>(visitor: &mut V, tab: &mut Tab<'t>)
+where
+    V: VisitorMut<'t> + ?Sized,
+{
+    visitor.visit_elements_mut(&mut tab.elements);
+}
+
+pub fn walk_list_item_mut<'t, V>(visitor: &mut V, item: &mut ListItem<'t>)
+where
+    V: VisitorMut<'t> + ?Sized,
+{
+    match item {
+        ListItem::Elements {
+            attributes,
+            elements,
+        } => {
+            visitor.visit_attributes_mut(attributes);
+            visitor.visit_elements_mut(elements);
+        }
+        ListItem::SubList { element } => visitor.visit_element_mut(element),
+    }
+}
+
+pub fn walk_definition_list_item_mut<'t, V>(
+    visitor: &mut V,
+    item: &mut DefinitionListItem<'t>,
+) where
+    V: VisitorMut<'t> + ?Sized,
+{
+    visitor.visit_elements_mut(&mut item.key);
+    visitor.visit_elements_mut(&mut item.value);
+}
+
+pub fn walk_gallery_image_mut<'t, V>(visitor: &mut V, image: &mut GalleryImage<'t>)
+where
+    V: VisitorMut<'t> + ?Sized,
+{
+    visitor.visit_image_source_mut(&mut image.source);
+
+    if let Some(link) = &mut image.link {
+        visitor.visit_link_location_mut(link);
+    }
+
+    visitor.visit_attributes_mut(&mut image.attributes);
+}
+
+pub fn walk_bibliography_entry_mut<'t, V>(
+    visitor: &mut V,
+    entry: &mut BibliographyEntry<'t>,
+) where
+    V: VisitorMut<'t> + ?Sized,
+{
+    visitor.visit_elements_mut(&mut entry.elements);
+}
+
+pub fn walk_link_location_mut<'t, V>(visitor: &mut V, link: &mut LinkLocation<'t>)
+where
+    V: VisitorMut<'t> + ?Sized,
+{
+    match link {
+        LinkLocation::Page(page_ref) => visitor.visit_page_ref_mut(page_ref),
+        LinkLocation::Url(_) => (),
+    }
+}
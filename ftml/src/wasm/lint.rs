@@ -0,0 +1,104 @@
+/*
+ * wasm/lint.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::error::error_to_js;
+use super::parsing::SyntaxTree;
+use super::prelude::*;
+use super::tokenizer::Tokenization;
+use crate::lint::LintConfig;
+use crate::utf16::Utf16IndexMap;
+
+// Typescript declarations
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_APPEND_CONTENT: &str = r#"
+
+export type ILintSeverity = "info" | "warning" | "error";
+
+export type ILintLevel = "off" | ILintSeverity;
+
+export interface ILintConfig {
+    rules?: Record<string, ILintLevel>;
+    "deprecated-aliases"?: Record<string, string>;
+}
+
+export interface ITextEdit {
+    span: {
+        start: number;
+        end: number;
+    };
+    replacement: string;
+}
+
+export interface ILint {
+    rule: string;
+    severity: ILintSeverity;
+    message: string;
+    span: {
+        start: number;
+        end: number;
+    };
+    fix: {
+        message: string;
+        edits: ITextEdit[];
+    } | null;
+}
+
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ILintConfig")]
+    pub type ILintConfig;
+
+    #[wasm_bindgen(typescript_type = "ILint[]")]
+    pub type ILintArray;
+}
+
+// Exported functions
+
+#[wasm_bindgen]
+pub fn lint(
+    tokens: Tokenization,
+    syntax_tree: SyntaxTree,
+    config: ILintConfig,
+) -> Result<ILintArray, JsValue> {
+    let log = &*LOGGER;
+    let config: LintConfig = config.into_serde().map_err(error_to_js)?;
+
+    // Borrow and run the linter
+    let tokenization = tokens.get();
+    let lints = crate::lint::lint(log, tokenization, syntax_tree.get(), &config);
+
+    // Convert spans to use UTF-16 indices
+    let lints = if lints.is_empty() {
+        lints
+    } else {
+        let full_text = tokenization.full_text().inner();
+        let utf16_map = Utf16IndexMap::new(full_text);
+
+        lints
+            .iter()
+            .map(|lint| lint.to_utf16_indices(&utf16_map))
+            .collect()
+    };
+
+    rust_to_js!(lints)
+}
@@ -22,13 +22,16 @@
 mod macros;
 
 mod error;
+mod lint;
 mod log;
 mod misc;
 mod page_info;
 mod parsing;
 mod preproc;
 mod render;
+mod sections;
 mod settings;
+mod stored;
 mod tokenizer;
 mod utf16;
 
@@ -41,9 +44,12 @@ mod prelude {
 #[cfg(feature = "wasm-log")]
 pub use self::log::ConsoleLogger;
 
+pub use self::lint::lint;
 pub use self::misc::version;
 pub use self::parsing::{parse, ParseOutcome, SyntaxTree};
 pub use self::preproc::preprocess;
 pub use self::render::{render_html, render_text};
+pub use self::sections::sections;
 pub use self::settings::WikitextSettings;
+pub use self::stored::{load_stored_binary, load_stored_json, store_binary, store_json};
 pub use self::tokenizer::{tokenize, Tokenization};
@@ -106,6 +106,13 @@ pub struct SyntaxTree {
 
 #[wasm_bindgen]
 impl SyntaxTree {
+    #[inline]
+    pub(crate) fn new(tree: RustSyntaxTree<'static>) -> Self {
+        SyntaxTree {
+            inner: Arc::new(tree),
+        }
+    }
+
     #[inline]
     pub(crate) fn get(&self) -> &RustSyntaxTree<'static> {
         &self.inner
@@ -19,11 +19,22 @@
  */
 
 use super::prelude::*;
+use super::settings::WikitextSettings;
+use crate::settings::{
+    WikitextMode as RustWikitextMode, WikitextSettings as RustWikitextSettings,
+};
 
 #[wasm_bindgen]
-pub fn preprocess(mut text: String) -> String {
+pub fn preprocess(mut text: String, settings: Option<WikitextSettings>) -> String {
     let log = &*LOGGER;
 
-    crate::preprocess(log, &mut text);
+    match settings {
+        Some(settings) => crate::preprocess(log, &mut text, settings.get()),
+        None => {
+            let settings = RustWikitextSettings::from_mode(RustWikitextMode::Page);
+            crate::preprocess(log, &mut text, &settings)
+        }
+    };
+
     text
 }
@@ -33,21 +33,31 @@ use std::sync::Arc;
 const TS_APPEND_CONTENT: &str = r#"
 
 export interface IHtmlOutput {
+    backlinks: IBacklinks;
     body: string;
-    style: string;
+    json_ld?: string | null;
     meta: IHtmlMeta[];
+    styles: string[];
 }
 
 export interface IHtmlMeta {
-    tag_type: string;
     name: string;
+    tag_type: HtmlMetaType;
     value: string;
 }
 
+export type HtmlMetaType = "name" | "http-equiv" | "property";
+
 export interface IBacklinks {
-    included_pages: string[];
-    internal_links: string[];
-    external_links: string[];
+    "external-links": string[];
+    "included-pages": IPageRef[];
+    "internal-links": IPageRef[];
+    "missing-links": IPageRef[];
+}
+
+export interface IPageRef {
+    page: string;
+    site?: string | null;
 }
 
 "#;
@@ -96,6 +106,11 @@ impl HtmlOutput {
         rust_to_js!(self.inner.meta)
     }
 
+    #[wasm_bindgen]
+    pub fn json_ld(&self) -> Option<String> {
+        self.inner.json_ld.clone()
+    }
+
     #[wasm_bindgen(typescript_type = "IBacklinks")]
     pub fn backlinks(&self) -> Result<IBacklinks, JsValue> {
         rust_to_js!(self.inner.backlinks)
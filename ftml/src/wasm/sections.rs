@@ -0,0 +1,76 @@
+/*
+ * wasm/sections.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::parsing::SyntaxTree;
+use super::prelude::*;
+use super::tokenizer::Tokenization;
+use crate::utf16::Utf16IndexMap;
+
+// Typescript declarations
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_APPEND_CONTENT: &str = r#"
+
+export interface ISection {
+    index: number;
+    level: number;
+    name: string;
+    "toc-index": number | null;
+    "heading-span": {
+        start: number;
+        end: number;
+    };
+    span: {
+        start: number;
+        end: number;
+    };
+}
+
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ISection[]")]
+    pub type ISectionArray;
+}
+
+// Exported functions
+
+#[wasm_bindgen]
+pub fn sections(
+    tokens: Tokenization,
+    syntax_tree: SyntaxTree,
+) -> Result<ISectionArray, JsValue> {
+    let log = &*LOGGER;
+
+    // Borrow and find sections
+    let tokenization = tokens.get();
+    let sections = crate::sections::get_sections(log, tokenization, syntax_tree.get());
+
+    // Convert spans to use UTF-16 indices
+    let full_text = tokenization.full_text().inner();
+    let utf16_map = Utf16IndexMap::new(full_text);
+    let sections: Vec<_> = sections
+        .iter()
+        .map(|section| section.to_utf16_indices(&utf16_map))
+        .collect();
+
+    rust_to_js!(sections)
+}
@@ -31,18 +31,69 @@ use std::sync::Arc;
 const TS_APPEND_CONTENT: &str = r#"
 
 export interface IWikitextSettings {
+    "allow-local-paths": boolean;
+    "enable-json-ld"?: boolean;
+    "enable-page-syntax": boolean;
+    "enable-section-edit"?: boolean;
+    "html-block"?: IHtmlBlockSettings;
+    "html-syntax"?: HtmlSyntax;
+    iframe?: IIframeSettings;
+    limits?: IResourceLimits;
     mode: WikitextMode;
-    enable_page_syntax: boolean;
-    use_true_ids: boolean;
-    allow_local_paths: boolean;
+    preprocess?: IPreprocessSettings;
+    typography?: ITypographySettings;
+    "use-true-ids": boolean;
 }
 
 export type WikitextMode =
-    | 'page'
-    | 'draft'
-    | 'forum-post'
-    | 'direct-message'
-    | 'list'
+    | "page"
+    | "draft"
+    | "forum-post"
+    | "direct-message"
+    | "list";
+
+export type HtmlSyntax = "html" | "xhtml" | "epub";
+
+export interface IPreprocessSettings {
+    passes?: IPreprocessPass[];
+}
+
+export interface IPreprocessPass {
+    pattern: string;
+    replacement: string;
+}
+
+export interface ITypographySettings {
+    enabled?: boolean;
+    quotes?: QuoteStyle;
+}
+
+export type QuoteStyle = "english" | "french" | "german" | "japanese";
+
+export interface IHtmlBlockSettings {
+    "auto-height"?: boolean;
+    hosting?: HtmlBlockHosting;
+    sandbox?: string[];
+    stylesheet?: string | null;
+}
+
+export type HtmlBlockHosting = "inline" | "remote";
+
+export interface IIframeSettings {
+    "allowed-domains"?: string[];
+    "denied-domains"?: string[];
+    "referrer-policy"?: string;
+    sandbox?: string[];
+}
+
+export interface IResourceLimits {
+    "max-css-size"?: number;
+    "max-elements"?: number;
+    "max-include-expansions"?: number;
+    "max-input-length"?: number;
+    "max-steps"?: number;
+    "max-table-cells"?: number;
+}
 
 "#;
 
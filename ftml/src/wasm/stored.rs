@@ -0,0 +1,51 @@
+/*
+ * wasm/stored.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2021 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::error::error_to_js;
+use super::parsing::SyntaxTree;
+use super::prelude::*;
+
+// Exported functions
+
+#[wasm_bindgen]
+pub fn store_json(syntax_tree: SyntaxTree) -> String {
+    crate::stored::to_json(syntax_tree.get())
+}
+
+#[wasm_bindgen]
+pub fn store_binary(syntax_tree: SyntaxTree) -> Vec<u8> {
+    crate::stored::to_binary(syntax_tree.get())
+}
+
+#[wasm_bindgen]
+pub fn load_stored_json(data: &str) -> Result<SyntaxTree, JsValue> {
+    let log = &*LOGGER;
+    let tree = crate::stored::from_json(log, data).map_err(error_to_js)?;
+
+    Ok(SyntaxTree::new(tree))
+}
+
+#[wasm_bindgen]
+pub fn load_stored_binary(data: &[u8]) -> Result<SyntaxTree, JsValue> {
+    let log = &*LOGGER;
+    let tree = crate::stored::from_binary(log, data).map_err(error_to_js)?;
+
+    Ok(SyntaxTree::new(tree))
+}
@@ -37,6 +37,12 @@ cmftml-lint =
 
   .footnotes-nested = This footnote is invalid because it's inside another footnote.
 
+  .bibliography-contains-non-entry = This bibliography can only contain entries, in the form ": label : citation".
+
+  .no-such-bibliography-label = The citation '{ $slice }' doesn't refer to any bibliography entry.
+
+  .unused-bibliography-label = The bibliography entry '{ $slice }' is never cited.
+
   .blockquote-depth-exceeded = This blockquote is nested too deeply, and can't be rendered.
 
   .no-such-block = Unknown block '{ $slice }'.
@@ -65,6 +71,20 @@ cmftml-lint =
 
   .invalid-url = The URL '{ $slice }' is invalid.
 
+  .iframe-not-permitted = Iframes from this URL are not permitted on this site.
+
+  .input-too-long = This page is too long, so the rest of it was not processed.
+
+  .too-many-elements = This page has too many elements, so the rest of it was not processed.
+
+  .too-many-table-cells = This page has too many table cells, so the rest of it was not processed.
+
+  .too-many-includes = This page includes too many other pages, so the rest of it was not processed.
+
+  .css-too-large = This page has too much CSS, so the rest of it was not processed.
+
+  .step-limit-exceeded = This page is too complex to process, so the rest of it was not processed.
+
 ## Block Acceptance
 
 cmftml-accepts =
@@ -5,3 +5,102 @@ wikitext-rendering = Rendering...
 wikitext-perf =
   .title = Performance
   .render = RENDER:
+
+## Parser Warnings
+
+wikitext-severity =
+  .error = error
+  .warning = warning
+  .info = info
+  .internal = note
+
+wikitext-warning =
+  .recursion-depth-exceeded = Too much recursion in markup.
+
+  .end-of-input = Rule of type '{ $rule }' couldn't be processed before the end of the document was reached.
+
+  .no-rules-match = The string '{ $slice }' doesn't match anything and will be rendered as plain text.
+
+  .rule-failed = The rule '{ $rule }' failed to match here, and had to fallback another rule.
+
+  .not-supported-mode = The syntax '{ $slice }' isn't supported in this context.
+
+  .not-start-of-line = The rule '{ $rule }' failed to match here, as it can only match on the start of a new line.
+
+  .invalid-include = This include is invalid and won't be rendered.
+
+  .list-empty = This list has nothing inside of it.
+
+  .list-contains-non-item = This list has direct children that aren't list-item blocks.
+
+  .list-item-outside-list = This list-item isn't within a list.
+
+  .list-depth-exceeded = This list is nested too deeply, and can't be rendered.
+
+  .table-contains-non-row = This table has direct children that aren't table rows.
+
+  .table-row-contains-non-cell = This table-row has direct children that aren't cells.
+
+  .table-row-outside-table = This table-row isn't within a table.
+
+  .table-cell-outside-table = This table-cell isn't within a table-row.
+
+  .tab-view-empty = This tabview has no tabs inside of it.
+
+  .tab-view-contains-non-tab = This tabview has direct children that aren't tabs.
+
+  .tab-outside-tab-view = This tab isn't within a tabview.
+
+  .footnotes-nested = This footnote is invalid because it's inside another footnote.
+
+  .bibliography-contains-non-entry = This bibliography can only contain entries, in the form ": label : citation".
+
+  .no-such-bibliography-label = The citation '{ $slice }' doesn't refer to any bibliography entry.
+
+  .unused-bibliography-label = The bibliography entry '{ $slice }' is never cited.
+
+  .blockquote-depth-exceeded = This blockquote is nested too deeply, and can't be rendered.
+
+  .no-such-block = Unknown block '{ $slice }'.
+
+  .block-disallows-star = Block '{ $slice }' doesn't support a star invocation. (starting '*' character)
+
+  .block-disallows-score = Block '{ $slice }' doesn't support a score invocation. (starting '_' character)
+
+  .block-missing-name = Block '{ $slice }' requires a name/value, but none is specified.
+
+  .block-missing-close-brackets = This block is missing closing ']]' brackets.
+
+  .block-malformed-arguments = Block '{ $slice }' has malformed arguments.
+
+  .block-missing-arguments = Block '{ $slice }' is missing one or more required arguments.
+
+  .block-expected-end = The block of type '{ $rule }' was expected to end by at least this point.
+
+  .block-end-mismatch = The block of type '{ $rule }' was expected to end here, not '{ $slice }'.
+
+  .no-such-embed = Unknown embed '{ $slice }'.
+
+  .no-such-module = Unknown module '{ $slice }'.
+
+  .module-missing-name = A module name was expected to be provided.
+
+  .no-such-page = The page '{ $slice }' doesn't exist.
+
+  .no-such-variable = The variable '{ $slice }' isn't defined, and won't be substituted.
+
+  .invalid-url = The URL '{ $slice }' is invalid.
+
+  .iframe-not-permitted = Iframes from this URL are not permitted on this site.
+
+  .input-too-long = This page is too long, so the rest of it was not processed.
+
+  .too-many-elements = This page has too many elements, so the rest of it was not processed.
+
+  .too-many-table-cells = This page has too many table cells, so the rest of it was not processed.
+
+  .too-many-includes = This page includes too many other pages, so the rest of it was not processed.
+
+  .css-too-large = This page has too much CSS, so the rest of it was not processed.
+
+  .step-limit-exceeded = This page is too complex to process, so the rest of it was not processed.
@@ -121,6 +121,7 @@ final class DeepwellService
         return [
             'included-pages' => $backlinks->inclusions,
             'internal-links' => $backlinks->internal_links,
+            'missing-links' => $backlinks->missing_links,
             'external-links' => $backlinks->external_links,
         ];
     }
@@ -14,15 +14,18 @@ class Backlinks
     public array $inclusions;
     public array $internal_links;
     public array $external_links;
+    public array $missing_links;
 
     public function __construct(
         array $inclusions,
         array $internal_links,
-        array $external_links
+        array $external_links,
+        array $missing_links = []
     ) {
         $this->inclusions = $inclusions;
         $this->internal_links = $internal_links;
         $this->external_links = $external_links;
+        $this->missing_links = $missing_links;
     }
 
     public static function fromWikiObject(Text_Wiki $wiki): Backlinks
@@ -126,6 +126,66 @@ final class FtmlFfi
         return OutputConversion::makeTextOutput($output);
     }
 
+    public static function renderStoredHtml(
+        string $data,
+        Wikitext\PageInfo $page_info,
+        Wikitext\WikitextSettings $settings
+    ): ?HtmlOutput {
+        // Convert objects
+        $c_data = self::bytes($data);
+        $c_page_info = new PageInfo($page_info);
+        $c_settings = new WikitextSettings($settings);
+        $output = self::make(self::$FTML_HTML_OUTPUT);
+
+        // Render call
+        $loaded = self::$ffi->ftml_render_stored_html(
+            FFI::addr($output),
+            $c_data,
+            strlen($data),
+            $c_page_info->pointer(),
+            $c_settings->pointer(),
+        );
+        FFI::free($c_data);
+
+        // Output is only written if the stored tree could be loaded
+        if (!$loaded) {
+            return null;
+        }
+
+        // Convert result back to PHP
+        return OutputConversion::makeHtmlOutput($output);
+    }
+
+    public static function renderStoredText(
+        string $data,
+        Wikitext\PageInfo $page_info,
+        Wikitext\WikitextSettings $settings
+    ): ?TextOutput {
+        // Convert objects
+        $c_data = self::bytes($data);
+        $c_page_info = new PageInfo($page_info);
+        $c_settings = new WikitextSettings($settings);
+        $output = self::make(self::$FTML_TEXT_OUTPUT);
+
+        // Render call
+        $loaded = self::$ffi->ftml_render_stored_text(
+            FFI::addr($output),
+            $c_data,
+            strlen($data),
+            $c_page_info->pointer(),
+            $c_settings->pointer(),
+        );
+        FFI::free($c_data);
+
+        // Output is only written if the stored tree could be loaded
+        if (!$loaded) {
+            return null;
+        }
+
+        // Convert result back to PHP
+        return OutputConversion::makeTextOutput($output);
+    }
+
     public static function freeHtmlOutput(FFI\CData $data)
     {
         self::$ffi->ftml_destroy_html_output(FFI::addr($data));
@@ -236,6 +296,27 @@ final class FtmlFfi
         return $buffer;
     }
 
+    /**
+     * Clones a PHP string into a newly-allocated C byte buffer.
+     *
+     * Unlike string(), no null byte is added, so the data may be binary.
+     * You must run FFI::free() on the buffer when you're done with it.
+     *
+     * @param string $value The bytes to be cloned
+     * @return FFI\CData The C buffer created (uint8_t[])
+     */
+    public static function bytes(string $value): FFI\CData
+    {
+        // Allocate C buffer, which cannot be zero-width
+        $length = strlen($value);
+        $type = self::arrayType(self::type('uint8_t'), [max($length, 1)]);
+        $buffer = self::make($type);
+
+        // Copy data
+        FFI::memcpy($buffer, $value, $length);
+        return $buffer;
+    }
+
     /**
      * Converts a list in the form of a PHP array into a pointer
      * suitable for passing into C FFIs. Applies a transformation
@@ -60,6 +60,7 @@ final class OutputConversion
         $body = FFI::string($data->body);
         $styles = self::makeStylesArray($data->styles_list, $data->styles_len);
         $meta = self::makeHtmlMetaArray($data->meta_list, $data->meta_len);
+        $json_ld = FtmlFfi::nullableString($data->json_ld);
         $warnings = self::makeParseWarningArray($data->warning_list, $data->warning_len);
         $backlinks = self::makeBacklinks($data->backlinks);
 
@@ -68,7 +69,7 @@ final class OutputConversion
         FFI::free($data);
 
         // Return object
-        return new HtmlOutput($body, $styles, $meta, $warnings, $backlinks);
+        return new HtmlOutput($body, $styles, $meta, $warnings, $backlinks, $json_ld);
     }
 
     private static function makeStylesArray(?FFI\CData $pointer, int $length): array
@@ -129,13 +130,24 @@ final class OutputConversion
             fn(FFI\CData $data) => self::makePageRef($data),
         );
 
+        $missing_links = FtmlFfi::pointerToList(
+            $data->missing_links_list,
+            $data->missing_links_len,
+            fn(FFI\CData $data) => self::makePageRef($data),
+        );
+
         $external_links = FtmlFfi::pointerToList(
             $data->external_links_list,
             $data->external_links_len,
             fn(FFI\CData $data) => FFI::string($data),
         );
 
-        return new Backlinks($inclusions, $internal_links, $external_links);
+        return new Backlinks(
+            $inclusions,
+            $internal_links,
+            $external_links,
+            $missing_links,
+        );
     }
 
     private static function makePageRef(FFI\CData $data): PageRef
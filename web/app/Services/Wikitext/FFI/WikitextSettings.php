@@ -32,6 +32,8 @@ class WikitextSettings
         $this->c_data->enable_page_syntax = $settings->enable_page_syntax;
         $this->c_data->use_true_ids = $settings->use_true_ids;
         $this->c_data->allow_local_paths = $settings->allow_local_paths;
+        $this->c_data->enable_section_edit = $settings->enable_section_edit;
+        $this->c_data->enable_json_ld = $settings->enable_json_ld;
     }
 
     public function pointer(): FFI\CData
@@ -46,6 +48,8 @@ class WikitextSettings
             $this->c_data->enable_page_syntax,
             $this->c_data->use_true_ids,
             $this->c_data->allow_local_paths,
+            $this->c_data->enable_section_edit,
+            $this->c_data->enable_json_ld,
         );
     }
 
@@ -26,6 +26,13 @@ class HtmlOutput
      */
     public array $meta;
 
+    /**
+     * @var ?string A schema.org Article describing the page in JSON-LD, if enabled.
+     *
+     * This is escaped so that it can be placed directly in a <script type="application/ld+json"> tag.
+     */
+    public ?string $json_ld;
+
     /**
      * @var array The list of ParseWarning objects, if any, generated during parsing.
      */
@@ -41,12 +48,14 @@ class HtmlOutput
         array $styles,
         array $meta,
         array $warnings,
-        Backlinks $link_stats
+        Backlinks $link_stats,
+        ?string $json_ld = null
     ) {
         $this->body = $body;
         $this->styles = $styles;
         $this->meta = $meta;
         $this->warnings = $warnings;
         $this->link_stats = $link_stats;
+        $this->json_ld = $json_ld;
     }
 }
@@ -16,6 +16,8 @@ class WikitextSettings
     public bool $enable_page_syntax;
     public bool $use_true_ids;
     public bool $allow_local_paths;
+    public bool $enable_section_edit;
+    public bool $enable_json_ld;
 
     /**
      * Creates a new instance of WikitextSettings.
@@ -24,17 +26,23 @@ class WikitextSettings
      * @param bool $enable_page_syntax
      * @param bool $use_true_ids
      * @param bool $allow_local_paths
+     * @param bool $enable_section_edit
+     * @param bool $enable_json_ld
      */
     public function __construct(
         int $mode,
         bool $enable_page_syntax,
         bool $use_true_ids,
-        bool $allow_local_paths
+        bool $allow_local_paths,
+        bool $enable_section_edit = false,
+        bool $enable_json_ld = false
     ) {
         $this->mode = $mode;
         $this->enable_page_syntax = $enable_page_syntax;
         $this->use_true_ids = $use_true_ids;
         $this->allow_local_paths = $allow_local_paths;
+        $this->enable_section_edit = $enable_section_edit;
+        $this->enable_json_ld = $enable_json_ld;
     }
 
     /**
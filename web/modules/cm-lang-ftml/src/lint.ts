@@ -29,6 +29,9 @@ const warningConfig: Record<string, "info" | "warning" | "error" | null> = {
   "table-row-outside-table": "error",
   "table-cell-outside-table": "error",
   "footnotes-nested": "error",
+  "bibliography-contains-non-entry": "error",
+  "no-such-bibliography-label": "warning",
+  "unused-bibliography-label": "info",
   "blockquote-depth-exceeded": "error",
   "no-such-block": "error",
   "block-disallows-star": "warning",
@@ -42,7 +45,14 @@ const warningConfig: Record<string, "info" | "warning" | "error" | null> = {
   "no-such-module": "error",
   "module-missing-name": "error",
   "no-such-page": "error",
-  "invalid-url": "warning"
+  "invalid-url": "warning",
+  "iframe-not-permitted": "warning",
+  "input-too-long": "error",
+  "too-many-elements": "error",
+  "too-many-table-cells": "error",
+  "too-many-includes": "error",
+  "css-too-large": "error",
+  "step-limit-exceeded": "error"
 }
 
 // generate warnings from configuration
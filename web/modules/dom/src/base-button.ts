@@ -32,7 +32,7 @@ export abstract class BaseButton extends HTMLElement {
 
   /** Ensures that accessibility attributes are set. */
   private baseEnsureAttributes() {
-    if (!this.hasAttribute("tab-index")) this.setAttribute("tab-index", "0")
+    if (!this.hasAttribute("tabindex")) this.setAttribute("tabindex", "0")
     if (!this.hasAttribute("role")) this.setAttribute("role", "button")
   }
 
@@ -18,6 +18,21 @@ export class CollapsibleBottomButtonElement extends BaseButton {
   }
 }
 
+// Keeps the `aria-expanded` state of the top button in sync with the
+// collapsible. The `toggle` event doesn't bubble, so it is listened for
+// during the capture phase instead.
+document.addEventListener(
+  "toggle",
+  evt => {
+    const details = evt.target
+    if (!(details instanceof HTMLDetailsElement)) return
+    if (!details.classList.contains("wj-collapsible")) return
+    const button = details.querySelector(":scope > .wj-collapsible-button-top")
+    button?.setAttribute("aria-expanded", String(details.open))
+  },
+  true
+)
+
 declare global {
   interface HTMLElementTagNameMap {
     "wj-collapsible-button-bottom": CollapsibleBottomButtonElement
@@ -0,0 +1,32 @@
+/** Message posted by FTML `[[html]]` blocks rendered with auto-height enabled. */
+interface HtmlBlockHeightMessage {
+  type: "wj-html-height"
+  height: number
+}
+
+function isHeightMessage(data: unknown): data is HtmlBlockHeightMessage {
+  if (typeof data !== "object" || data === null) return false
+  const message = data as Partial<HtmlBlockHeightMessage>
+  return message.type === "wj-html-height" && typeof message.height === "number"
+}
+
+/**
+ * Resizes the FTML `[[html]]` iframe which sent the given message to fit its
+ * contents. Only iframes rendered with auto-height enabled are resized.
+ */
+export function resizeHtmlBlock(event: MessageEvent) {
+  if (!isHeightMessage(event.data)) return
+
+  const iframes = document.querySelectorAll<HTMLIFrameElement>(
+    "iframe.wj-html-block[data-auto-height]"
+  )
+
+  for (const iframe of iframes) {
+    if (iframe.contentWindow === event.source) {
+      iframe.style.height = `${Math.ceil(event.data.height)}px`
+      break
+    }
+  }
+}
+
+window.addEventListener("message", resizeHtmlBlock)
@@ -118,30 +118,36 @@ export class TabviewButtonElement extends BaseButton {
     if (["ArrowRight", "ArrowLeft", "Home", "End"].includes(evt.key)) {
       const list = this.relativeList()
 
+      let target: HTMLElement
       // prettier-ignore
       switch(evt.key) {
-        case "ArrowRight": list.next.focus()  ; break
-        case "ArrowLeft":  list.prev.focus()  ; break
-        case "Home":       list.start.focus() ; break
-        case "End":        list.end.focus()   ; break
+        case "ArrowRight": target = list.next  ; break
+        case "ArrowLeft":  target = list.prev  ; break
+        case "Home":       target = list.start ; break
+        default:           target = list.end   ; break
       }
 
+      // tabs are selected as soon as they're focused
+      target.focus()
+      target.click()
+
       evt.preventDefault()
     }
   }
 
   /**
    * Returns an object that contains info about the parent list, e.g. the
-   * button after this one.
+   * button after this one. Moving past either end of the list wraps around.
    */
   private relativeList() {
     const children = Array.from(this.parent.children) as HTMLElement[]
     const idx = children.indexOf(this)
+    const len = children.length
     return {
       start: children[0],
-      end: children[children.length - 1],
-      prev: children[idx - 1],
-      next: children[idx + 1]
+      end: children[len - 1],
+      prev: children[(idx - 1 + len) % len],
+      next: children[(idx + 1) % len]
     }
   }
 }
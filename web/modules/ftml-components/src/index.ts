@@ -1,5 +1,6 @@
 export * from "./components/code/code"
 export * from "./components/collapsible/collapsible"
 export * from "./components/footnotes/footnotes"
+export * from "./components/html-block/html-block"
 export * from "./components/math/math"
 export * from "./components/tabview/tabview"
@@ -78,7 +78,8 @@ describe("ftml-wasm", () => {
         ],
         "styles": [],
         "table-of-contents": [],
-        "footnotes": []
+        "footnotes": [],
+        "bibliographies": []
       },
       "warnings": []
     })